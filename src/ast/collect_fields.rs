@@ -1,114 +1,481 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
-use super::{AbstractTypeDefinitionExtension, OperationVisitorContext, SchemaDocumentExtension};
-use crate::ast::ext::{SubTypeExtension, TypeDefinitionExtension};
+use lazy_static::lazy_static;
+use serde_json::{Map, Value as JsonValue};
+
+use super::{OperationVisitorContext, Schema, SchemaDocumentExtension};
+use crate::ast::ext::TypeDefinitionExtension;
+use crate::parser::query::{Directive, Text, Value};
 use crate::static_graphql::{
     query::{self, Selection, TypeCondition},
     schema::{self, TypeDefinition},
 };
+
+/// Fields of `selection_set` that apply to `parent_type`, grouped by field name. See
+/// `collect_fields_by_response_key` for the `CollectFields` algorithm of the spec, which this
+/// runs without applying `@skip` and `@include`.
 pub fn collect_fields<'a>(
     selection_set: &query::SelectionSet,
     parent_type: &schema::TypeDefinition,
     known_fragments: &HashMap<&str, &query::FragmentDefinition>,
     context: &'a OperationVisitorContext<'a>,
 ) -> HashMap<String, Vec<query::Field>> {
-    let mut map = HashMap::new();
-    let mut visited_fragments_names: Vec<String> = Vec::new();
+    let mut grouped_field_set = GroupedFieldSet::new();
 
     collect_fields_inner(
-        selection_set,
+        context.indexed_schema(),
         parent_type,
+        selection_set,
         known_fragments,
-        context,
-        &mut map,
-        &mut visited_fragments_names,
+        None,
+        &mut grouped_field_set,
+        &mut HashSet::new(),
     );
 
-    map
+    group_by_field_name(grouped_field_set)
 }
 
-fn does_fragment_condition_match<'a>(
-    fragment_condition: &'a Option<TypeCondition>,
-    current_selection_set_type: &'a TypeDefinition,
+/// Same as `collect_fields`, but leaves out the selections excluded by `@skip` or `@include`
+/// with the given variable values, like `CollectFields` in the spec.
+pub fn collect_fields_with_variables<'a>(
+    selection_set: &query::SelectionSet,
+    parent_type: &schema::TypeDefinition,
+    known_fragments: &HashMap<&str, &query::FragmentDefinition>,
     context: &'a OperationVisitorContext<'a>,
+    variables: &Map<String, JsonValue>,
+) -> HashMap<String, Vec<query::Field>> {
+    group_by_field_name(collect_fields_by_response_key(
+        context.indexed_schema(),
+        parent_type,
+        selection_set,
+        known_fragments,
+        variables,
+    ))
+}
+
+fn group_by_field_name(grouped_field_set: GroupedFieldSet) -> HashMap<String, Vec<query::Field>> {
+    let mut map: HashMap<String, Vec<query::Field>> = HashMap::new();
+
+    for (_, fields) in grouped_field_set {
+        for field in fields {
+            map.entry(field.name.clone()).or_default().push(field);
+        }
+    }
+
+    map
+}
+
+/// Checks the `@skip` and `@include` directives of a selection: it is excluded when the `if`
+/// argument of `@skip` is `true`, or the one of `@include` is `false`, either as a literal or as
+/// a variable value.
+pub fn should_include_selection<'a, T: Text<'a>>(
+    directives: &[Directive<'a, T>],
+    variables: &Map<String, JsonValue>,
 ) -> bool {
-    if let Some(TypeCondition::On(type_name)) = fragment_condition {
-        if let Some(conditional_type) = context.schema.type_by_name(type_name) {
-            if conditional_type
-                .name()
-                .eq(current_selection_set_type.name())
-            {
-                return true;
-            }
+    directives.iter().all(|directive| {
+        let condition = directive
+            .arguments
+            .iter()
+            .find(|(name, _)| name.as_ref() == "if")
+            .and_then(|(_, value)| match value {
+                Value::Boolean(value) => Some(*value),
+                Value::Variable(name) => match variables.get(name.as_ref()) {
+                    Some(JsonValue::Bool(value)) => Some(*value),
+                    _ => None,
+                },
+                _ => None,
+            });
 
-            if conditional_type.is_abstract_type() {
-                match conditional_type {
-                    TypeDefinition::Interface(interface_type) => {
-                        return interface_type.is_implemented_by(current_selection_set_type)
-                    }
-                    TypeDefinition::Union(union_type) => {
-                        return union_type.has_sub_type(current_selection_set_type.name())
-                    }
-                    _ => return false,
-                }
+        !matches!(
+            (directive.name.as_ref(), condition),
+            ("skip", Some(true)) | ("include", Some(false))
+        )
+    })
+}
+
+/// Field nodes grouped by response key (the alias of a field, or its name), in the order each
+/// response key first appears, which is the order of the fields in the response. See
+/// `collect_fields_by_response_key`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GroupedFieldSet {
+    groups: Vec<(String, Vec<query::Field>)>,
+    /// Index of each response key in `groups`.
+    indexes: HashMap<String, usize>,
+}
+
+impl GroupedFieldSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The field nodes selected with the given response key.
+    pub fn get(&self, response_key: &str) -> Option<&[query::Field]> {
+        self.indexes
+            .get(response_key)
+            .map(|index| self.groups[*index].1.as_slice())
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &[query::Field])> {
+        self.groups
+            .iter()
+            .map(|(response_key, fields)| (response_key.as_str(), fields.as_slice()))
+    }
+
+    pub fn response_keys(&self) -> impl Iterator<Item = &str> {
+        self.groups
+            .iter()
+            .map(|(response_key, _)| response_key.as_str())
+    }
+
+    pub fn len(&self) -> usize {
+        self.groups.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.groups.is_empty()
+    }
+
+    fn push(&mut self, field: &query::Field) {
+        let response_key = field.alias.as_ref().unwrap_or(&field.name);
+
+        match self.indexes.get(response_key) {
+            Some(index) => self.groups[*index].1.push(field.clone()),
+            None => {
+                self.indexes.insert(response_key.clone(), self.groups.len());
+                self.groups
+                    .push((response_key.clone(), vec![field.clone()]));
             }
         }
+    }
+}
 
-        false
-    } else {
-        true
+impl IntoIterator for GroupedFieldSet {
+    type Item = (String, Vec<query::Field>);
+    type IntoIter = std::vec::IntoIter<(String, Vec<query::Field>)>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.groups.into_iter()
     }
 }
 
-fn collect_fields_inner<'a>(
+/// Collects the fields of `selection_set` for an object type, following `CollectFields` in the
+/// spec: fields are grouped by response key in the order they appear, selections excluded by
+/// `@skip` or `@include` are left out, and fragments only apply when their type condition is
+/// `object_type`, or an interface or union it belongs to (looked up in `schema`).
+///
+/// `object_type` is the runtime type of the selection set: for an abstract type, collect the
+/// fields once for each of its possible types.
+pub fn collect_fields_by_response_key(
+    schema: &Schema,
+    object_type: &TypeDefinition,
     selection_set: &query::SelectionSet,
-    parent_type: &schema::TypeDefinition,
-    known_fragments: &HashMap<&str, &query::FragmentDefinition>,
-    context: &'a OperationVisitorContext<'a>,
-    result_arr: &mut HashMap<String, Vec<query::Field>>,
-    visited_fragments_names: &mut Vec<String>,
+    fragments: &HashMap<&str, &query::FragmentDefinition>,
+    variables: &Map<String, JsonValue>,
+) -> GroupedFieldSet {
+    let mut grouped_field_set = GroupedFieldSet::new();
+
+    collect_fields_inner(
+        schema,
+        object_type,
+        selection_set,
+        fragments,
+        Some(variables),
+        &mut grouped_field_set,
+        &mut HashSet::new(),
+    );
+
+    grouped_field_set
+}
+
+fn collect_fields_inner<'d>(
+    schema: &Schema,
+    object_type: &TypeDefinition,
+    selection_set: &'d query::SelectionSet,
+    fragments: &HashMap<&str, &'d query::FragmentDefinition>,
+    variables: Option<&Map<String, JsonValue>>,
+    grouped_field_set: &mut GroupedFieldSet,
+    visited_fragments: &mut HashSet<&'d str>,
 ) {
-    selection_set.items.iter().for_each(|item| match item {
-        Selection::Field(f) => {
-            let existing = result_arr.entry(f.name.clone()).or_default();
-            existing.push(f.clone());
-        }
-        Selection::InlineFragment(f) => {
-            if does_fragment_condition_match(&f.type_condition, parent_type, context) {
-                collect_fields_inner(
-                    &f.selection_set,
-                    parent_type,
-                    known_fragments,
-                    context,
-                    result_arr,
-                    visited_fragments_names,
-                );
+    // Without variables, `@skip` and `@include` are not applied
+    let is_included = |directives: &[query::Directive]| {
+        variables.map_or(true, |variables| {
+            should_include_selection(directives, variables)
+        })
+    };
+
+    for selection in &selection_set.items {
+        match selection {
+            Selection::Field(field) => {
+                if is_included(&field.directives) {
+                    grouped_field_set.push(field);
+                }
             }
-        }
-        Selection::FragmentSpread(f) => {
-            if !visited_fragments_names
-                .iter()
-                .any(|name| f.fragment_name.eq(name))
-            {
-                visited_fragments_names.push(f.fragment_name.clone());
-
-                if let Some(fragment) = known_fragments.get(f.fragment_name.as_str()) {
-                    if does_fragment_condition_match(
-                        &Some(fragment.type_condition.clone()),
-                        parent_type,
-                        context,
-                    ) {
+            Selection::FragmentSpread(spread) => {
+                if !is_included(&spread.directives)
+                    || !visited_fragments.insert(spread.fragment_name.as_str())
+                {
+                    continue;
+                }
+
+                if let Some(fragment) = fragments.get(spread.fragment_name.as_str()) {
+                    if does_fragment_type_apply(schema, object_type, &fragment.type_condition) {
                         collect_fields_inner(
+                            schema,
+                            object_type,
                             &fragment.selection_set,
-                            parent_type,
-                            known_fragments,
-                            context,
-                            result_arr,
-                            visited_fragments_names,
+                            fragments,
+                            variables,
+                            grouped_field_set,
+                            visited_fragments,
                         );
                     }
                 }
             }
+            Selection::InlineFragment(inline_fragment) => {
+                if !is_included(&inline_fragment.directives) {
+                    continue;
+                }
+
+                let applies = inline_fragment
+                    .type_condition
+                    .as_ref()
+                    .map_or(true, |type_condition| {
+                        does_fragment_type_apply(schema, object_type, type_condition)
+                    });
+
+                if applies {
+                    collect_fields_inner(
+                        schema,
+                        object_type,
+                        &inline_fragment.selection_set,
+                        fragments,
+                        variables,
+                        grouped_field_set,
+                        visited_fragments,
+                    );
+                }
+            }
+        }
+    }
+}
+
+/// `DoesFragmentTypeApply` in the spec.
+fn does_fragment_type_apply(
+    schema: &Schema,
+    object_type: &TypeDefinition,
+    type_condition: &TypeCondition,
+) -> bool {
+    let TypeCondition::On(type_name) = type_condition;
+
+    match schema.type_by_name(type_name) {
+        Some(fragment_type) => {
+            fragment_type.name() == object_type.name()
+                || schema.is_possible_type(fragment_type, object_type)
+        }
+        None => false,
+    }
+}
+
+/// The definition of a field selected on `object_type`, including the `__typename` meta field
+/// (`__typename: String!`), which every object type has.
+pub fn field_definition<'t>(
+    object_type: &'t TypeDefinition,
+    field_name: &str,
+) -> Option<&'t schema::Field> {
+    lazy_static! {
+        static ref TYPENAME_FIELD: schema::Field = schema::Field {
+            position: Default::default(),
+            description: None,
+            name: "__typename".to_string(),
+            arguments: vec![],
+            field_type: schema::Type::NonNullType(Box::new(schema::Type::NamedType(
+                "String".to_string()
+            ))),
+            directives: vec![],
+        };
+    }
+
+    match field_name {
+        "__typename" => Some(&TYPENAME_FIELD),
+        _ => match object_type {
+            TypeDefinition::Object(object) => object.fields.iter().find(|f| f.name == field_name),
+            TypeDefinition::Interface(interface) => {
+                interface.fields.iter().find(|f| f.name == field_name)
+            }
+            _ => None,
+        },
+    }
+}
+
+#[test]
+fn collects_fields_with_skip_and_include() {
+    let schema = crate::parser::parse_schema::<String>(
+        "
+        type Query { me: User }
+        interface Node { id: ID! }
+        type User implements Node { id: ID! name: String email: String friends: [User] }
+        type Post implements Node { id: ID! title: String }
+        ",
+    )
+    .expect("Failed to parse schema")
+    .into_static();
+    let document = crate::parser::parse_query::<String>(
+        "
+        query($withEmail: Boolean!, $withFriends: Boolean!) {
+          me {
+            id @skip(if: false)
+            name @include(if: $withEmail)
+            ...Email @include(if: $withEmail)
+            ...Email
+            ... on Node @include(if: $withFriends) { friends: id }
+            ... @skip(if: $withFriends) { friends { id } }
+            ... on Post { title }
+          }
         }
-    });
+
+        fragment Email on User { email }
+        ",
+    )
+    .expect("Failed to parse query")
+    .into_static();
+
+    let schema = super::Schema::new(&schema);
+    let context = OperationVisitorContext::new(&document, &schema);
+    let user_type = schema.type_by_name("User").unwrap();
+    let selection_set = match &document.definitions[0] {
+        query::Definition::Operation(query::OperationDefinition::Query(query)) => {
+            match &query.selection_set.items[0] {
+                Selection::Field(field) => &field.selection_set,
+                _ => unreachable!(),
+            }
+        }
+        _ => unreachable!(),
+    };
+    let variables = serde_json::json!({ "withEmail": false, "withFriends": true })
+        .as_object()
+        .cloned()
+        .unwrap();
+
+    let mut fields = collect_fields_with_variables(
+        selection_set,
+        user_type,
+        &context.known_fragments,
+        &context,
+        &variables,
+    )
+    .into_keys()
+    .collect::<Vec<_>>();
+    fields.sort();
+
+    // The second spread of `Email` is collected, even though the first one is skipped
+    assert_eq!(fields, vec!["email", "id"]);
+
+    let mut fields = collect_fields(selection_set, user_type, &context.known_fragments, &context)
+        .into_keys()
+        .collect::<Vec<_>>();
+    fields.sort();
+
+    assert_eq!(fields, vec!["email", "friends", "id", "name"]);
+}
+
+#[test]
+fn collects_fields_by_response_key_in_order() {
+    let schema = crate::parser::parse_schema::<String>(
+        "
+        type Query { search: [Result] }
+        interface Node { id: ID! }
+        type User implements Node { id: ID! name: String }
+        type Post implements Node { id: ID! title: String }
+        union Result = User | Post
+        ",
+    )
+    .expect("Failed to parse schema")
+    .into_static();
+    let document = crate::parser::parse_query::<String>(
+        "
+        query($withId: Boolean!) {
+          search {
+            __typename
+            ... on Node { id @include(if: $withId) }
+            ...UserFields
+            ... on Post { label: title id }
+            ... on Result { name: __typename }
+            ...UserFields
+          }
+        }
+
+        fragment UserFields on User { name label: name id }
+        ",
+    )
+    .expect("Failed to parse query")
+    .into_static();
+
+    let schema = Schema::new(&schema);
+    let context = OperationVisitorContext::new(&document, &schema);
+    let selection_set = match &document.definitions[0] {
+        query::Definition::Operation(query::OperationDefinition::Query(query)) => {
+            match &query.selection_set.items[0] {
+                Selection::Field(field) => &field.selection_set,
+                _ => unreachable!(),
+            }
+        }
+        _ => unreachable!(),
+    };
+    let variables = serde_json::json!({ "withId": false })
+        .as_object()
+        .cloned()
+        .unwrap();
+    let collect = |type_name: &str| {
+        collect_fields_by_response_key(
+            &schema,
+            schema.type_by_name(type_name).unwrap(),
+            selection_set,
+            &context.known_fragments,
+            &variables,
+        )
+    };
+
+    let user_fields = collect("User");
+    let grouped = user_fields
+        .iter()
+        .map(|(response_key, fields)| {
+            let names = fields
+                .iter()
+                .map(|field| field.name.as_str())
+                .collect::<Vec<_>>();
+
+            (response_key, names)
+        })
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        grouped,
+        vec![
+            ("__typename", vec!["__typename"]),
+            ("name", vec!["name", "__typename"]),
+            ("label", vec!["name"]),
+            ("id", vec!["id"]),
+        ]
+    );
+
+    let post_fields = collect("Post");
+
+    assert_eq!(
+        post_fields.response_keys().collect::<Vec<_>>(),
+        vec!["__typename", "label", "id", "name"]
+    );
+    assert_eq!(post_fields.get("label").unwrap()[0].name, "title");
+    assert!(post_fields.get("title").is_none());
+
+    let post_type = schema.type_by_name("Post").unwrap();
+
+    assert_eq!(
+        field_definition(post_type, "__typename")
+            .unwrap()
+            .field_type
+            .to_string(),
+        "String!"
+    );
+    assert_eq!(field_definition(post_type, "title").unwrap().name, "title");
+    assert!(field_definition(post_type, "name").is_none());
 }
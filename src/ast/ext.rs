@@ -2,6 +2,8 @@ use std::collections::HashMap;
 
 use lazy_static::lazy_static;
 
+use crate::parser::Pos;
+
 use crate::static_graphql::query::{
     self, Directive, FragmentSpread, OperationDefinition, SelectionSet, Type, Value,
     VariableDefinition,
@@ -10,6 +12,8 @@ use crate::static_graphql::schema::{
     self, DirectiveDefinition, InputValue, InterfaceType, ObjectType, TypeDefinition, UnionType,
 };
 
+// Takes a `&String` to keep implementations outside of this crate compiling.
+#[allow(clippy::ptr_arg)]
 pub trait FieldByNameExtension {
     fn field_by_name(&self, name: &String) -> Option<&schema::Field>;
     fn input_field_by_name(&self, name: &String) -> Option<&InputValue>;
@@ -42,6 +46,7 @@ pub trait OperationDefinitionExtension {
     fn variable_definitions(&self) -> &[VariableDefinition];
     fn directives(&self) -> &[Directive];
     fn selection_set(&self) -> &SelectionSet;
+    fn position(&self) -> Pos;
 }
 
 impl OperationDefinitionExtension for OperationDefinition {
@@ -71,67 +76,33 @@ impl OperationDefinitionExtension for OperationDefinition {
             OperationDefinition::Subscription(subscription) => &subscription.directives,
         }
     }
+
+    fn position(&self) -> Pos {
+        match self {
+            OperationDefinition::Query(query) => query.position,
+            OperationDefinition::SelectionSet(selection_set) => selection_set.span.0,
+            OperationDefinition::Mutation(mutation) => mutation.position,
+            OperationDefinition::Subscription(subscription) => subscription.position,
+        }
+    }
 }
 
 pub trait SchemaDocumentExtension {
     fn type_by_name(&self, name: &str) -> Option<&TypeDefinition>;
     fn type_map(&self) -> HashMap<&str, &TypeDefinition>;
     fn directive_by_name(&self, name: &str) -> Option<&DirectiveDefinition>;
-    fn object_type_by_name(&self, name: &str) -> Option<&ObjectType>;
     fn schema_definition(&self) -> &schema::SchemaDefinition;
-    fn query_type(&self) -> &ObjectType;
-    fn mutation_type(&self) -> Option<&ObjectType>;
-    fn subscription_type(&self) -> Option<&ObjectType>;
-    fn is_subtype(&self, sub_type: &Type, super_type: &Type) -> bool;
-    fn is_named_subtype(&self, sub_type_name: &str, super_type_name: &str) -> bool;
     fn is_possible_type(
         &self,
         abstract_type: &TypeDefinition,
         possible_type: &TypeDefinition,
     ) -> bool;
-}
 
-impl SchemaDocumentExtension for schema::Document {
-    fn type_by_name(&self, name: &str) -> Option<&TypeDefinition> {
-        for def in &self.definitions {
-            if let schema::Definition::TypeDefinition(type_def) = def {
-                if type_def.name().eq(name) {
-                    return Some(type_def);
-                }
-            }
-        }
-
-        None
-    }
-
-    fn directive_by_name(&self, name: &str) -> Option<&DirectiveDefinition> {
-        for def in &self.definitions {
-            if let schema::Definition::DirectiveDefinition(directive_def) = def {
-                if directive_def.name.eq(name) {
-                    return Some(directive_def);
-                }
-            }
-        }
-
-        None
-    }
-
-    fn schema_definition(&self) -> &schema::SchemaDefinition {
-        lazy_static! {
-            static ref DEFAULT_SCHEMA_DEF: schema::SchemaDefinition = {
-                schema::SchemaDefinition {
-                    query: Some("Query".to_string()),
-                    ..Default::default()
-                }
-            };
+    fn object_type_by_name(&self, name: &str) -> Option<&ObjectType> {
+        match self.type_by_name(name) {
+            Some(TypeDefinition::Object(object_def)) => Some(object_def),
+            _ => None,
         }
-        self.definitions
-            .iter()
-            .find_map(|definition| match definition {
-                schema::Definition::SchemaDefinition(schema_definition) => Some(schema_definition),
-                _ => None,
-            })
-            .unwrap_or(&*DEFAULT_SCHEMA_DEF)
     }
 
     fn query_type(&self) -> &ObjectType {
@@ -159,25 +130,6 @@ impl SchemaDocumentExtension for schema::Document {
             .and_then(|name| self.object_type_by_name(name))
     }
 
-    fn object_type_by_name(&self, name: &str) -> Option<&ObjectType> {
-        match self.type_by_name(name) {
-            Some(TypeDefinition::Object(object_def)) => Some(object_def),
-            _ => None,
-        }
-    }
-
-    fn type_map(&self) -> HashMap<&str, &TypeDefinition> {
-        let mut type_map = HashMap::new();
-
-        for def in &self.definitions {
-            if let schema::Definition::TypeDefinition(type_def) = def {
-                type_map.insert(type_def.name(), type_def);
-            }
-        }
-
-        type_map
-    }
-
     fn is_named_subtype(&self, sub_type_name: &str, super_type_name: &str) -> bool {
         if sub_type_name == super_type_name {
             true
@@ -191,27 +143,6 @@ impl SchemaDocumentExtension for schema::Document {
         }
     }
 
-    fn is_possible_type(
-        &self,
-        abstract_type: &TypeDefinition,
-        possible_type: &TypeDefinition,
-    ) -> bool {
-        match abstract_type {
-            TypeDefinition::Union(union_typedef) => {
-                return union_typedef
-                    .types
-                    .iter()
-                    .any(|t| t == possible_type.name());
-            }
-            TypeDefinition::Interface(interface_typedef) => {
-                let implementes_interfaces = possible_type.interfaces();
-
-                implementes_interfaces.contains(&interface_typedef.name)
-            }
-            _ => false,
-        }
-    }
-
     fn is_subtype(&self, sub_type: &Type, super_type: &Type) -> bool {
         // Equivalent type is a valid subtype
         if sub_type == super_type {
@@ -260,6 +191,81 @@ impl SchemaDocumentExtension for schema::Document {
     }
 }
 
+impl SchemaDocumentExtension for schema::Document {
+    fn type_by_name(&self, name: &str) -> Option<&TypeDefinition> {
+        for def in &self.definitions {
+            if let schema::Definition::TypeDefinition(type_def) = def {
+                if type_def.name().eq(name) {
+                    return Some(type_def);
+                }
+            }
+        }
+
+        None
+    }
+
+    fn directive_by_name(&self, name: &str) -> Option<&DirectiveDefinition> {
+        for def in &self.definitions {
+            if let schema::Definition::DirectiveDefinition(directive_def) = def {
+                if directive_def.name.eq(name) {
+                    return Some(directive_def);
+                }
+            }
+        }
+
+        None
+    }
+
+    fn schema_definition(&self) -> &schema::SchemaDefinition {
+        lazy_static! {
+            static ref DEFAULT_SCHEMA_DEF: schema::SchemaDefinition = {
+                schema::SchemaDefinition {
+                    query: Some("Query".to_string()),
+                    ..Default::default()
+                }
+            };
+        }
+        self.definitions
+            .iter()
+            .find_map(|definition| match definition {
+                schema::Definition::SchemaDefinition(schema_definition) => Some(schema_definition),
+                _ => None,
+            })
+            .unwrap_or(&*DEFAULT_SCHEMA_DEF)
+    }
+
+    fn type_map(&self) -> HashMap<&str, &TypeDefinition> {
+        let mut type_map = HashMap::new();
+
+        for def in &self.definitions {
+            if let schema::Definition::TypeDefinition(type_def) = def {
+                type_map.insert(type_def.name(), type_def);
+            }
+        }
+
+        type_map
+    }
+
+    fn is_possible_type(
+        &self,
+        abstract_type: &TypeDefinition,
+        possible_type: &TypeDefinition,
+    ) -> bool {
+        match abstract_type {
+            TypeDefinition::Union(union_typedef) => union_typedef
+                .types
+                .iter()
+                .any(|t| t == possible_type.name()),
+            TypeDefinition::Interface(interface_typedef) => {
+                let implementes_interfaces = possible_type.interfaces();
+
+                implementes_interfaces.contains(&interface_typedef.name)
+            }
+            _ => false,
+        }
+    }
+}
+
 pub trait TypeExtension {
     fn inner_type(&self) -> &str;
     fn is_non_null(&self) -> bool;
@@ -286,24 +292,15 @@ impl TypeExtension for Type {
     }
 
     fn is_non_null(&self) -> bool {
-        match self {
-            Type::NonNullType(_) => true,
-            _ => false,
-        }
+        matches!(self, Type::NonNullType(_))
     }
 
     fn is_list_type(&self) -> bool {
-        match self {
-            Type::ListType(_) => true,
-            _ => false,
-        }
+        matches!(self, Type::ListType(_))
     }
 
     fn is_named_type(&self) -> bool {
-        match self {
-            Type::NamedType(_) => true,
-            _ => false,
-        }
+        matches!(self, Type::NamedType(_))
     }
 }
 
@@ -334,10 +331,7 @@ impl ValueExtension for Value {
         match self {
             Value::Variable(v) => vec![v],
             Value::List(list) => list.iter().flat_map(|v| v.variables_in_use()).collect(),
-            Value::Object(object) => object
-                .iter()
-                .flat_map(|(_, v)| v.variables_in_use())
-                .collect(),
+            Value::Object(object) => object.values().flat_map(|v| v.variables_in_use()).collect(),
             _ => vec![],
         }
     }
@@ -359,6 +353,48 @@ impl InputValueHelpers for InputValue {
     }
 }
 
+pub trait TypeExtensionHelpers {
+    fn name(&self) -> &str;
+    fn position(&self) -> Pos;
+    /// The kind of type this extends, as used in error messages (`"object"`, `"input object"`, ...).
+    fn kind(&self) -> &'static str;
+}
+
+impl TypeExtensionHelpers for schema::TypeExtension {
+    fn name(&self) -> &str {
+        match self {
+            schema::TypeExtension::Scalar(scalar) => &scalar.name,
+            schema::TypeExtension::Object(object) => &object.name,
+            schema::TypeExtension::Interface(interface) => &interface.name,
+            schema::TypeExtension::Union(union) => &union.name,
+            schema::TypeExtension::Enum(enum_) => &enum_.name,
+            schema::TypeExtension::InputObject(input_object) => &input_object.name,
+        }
+    }
+
+    fn position(&self) -> Pos {
+        match self {
+            schema::TypeExtension::Scalar(scalar) => scalar.position,
+            schema::TypeExtension::Object(object) => object.position,
+            schema::TypeExtension::Interface(interface) => interface.position,
+            schema::TypeExtension::Union(union) => union.position,
+            schema::TypeExtension::Enum(enum_) => enum_.position,
+            schema::TypeExtension::InputObject(input_object) => input_object.position,
+        }
+    }
+
+    fn kind(&self) -> &'static str {
+        match self {
+            schema::TypeExtension::Scalar(_) => "scalar",
+            schema::TypeExtension::Object(_) => "object",
+            schema::TypeExtension::Interface(_) => "interface",
+            schema::TypeExtension::Union(_) => "union",
+            schema::TypeExtension::Enum(_) => "enum",
+            schema::TypeExtension::InputObject(_) => "input object",
+        }
+    }
+}
+
 pub trait AbstractTypeDefinitionExtension {
     fn is_implemented_by(&self, other_type: &dyn ImplementingInterfaceExtension) -> bool;
 }
@@ -374,6 +410,7 @@ pub trait TypeDefinitionExtension {
     fn is_scalar_type(&self) -> bool;
     fn is_abstract_type(&self) -> bool;
     fn name(&self) -> &str;
+    fn position(&self) -> Pos;
 }
 
 pub trait ImplementingInterfaceExtension {
@@ -396,7 +433,7 @@ impl ImplementingInterfaceExtension for TypeDefinition {
             TypeDefinition::Interface(interface_type) => {
                 interface_type.is_implemented_by(other_type)
             }
-            TypeDefinition::Union(union_type) => return union_type.has_sub_type(other_type.name()),
+            TypeDefinition::Union(union_type) => union_type.has_sub_type(other_type.name()),
             _ => false,
         }
     }
@@ -425,8 +462,8 @@ impl PossibleTypesExtension for TypeDefinition {
             TypeDefinition::Scalar(_) => vec![],
             TypeDefinition::Interface(i) => schema
                 .type_map()
-                .iter()
-                .filter_map(|(_type_name, type_def)| {
+                .values()
+                .filter_map(|type_def| {
                     if let TypeDefinition::Object(o) = type_def {
                         if i.is_implemented_by(*type_def) {
                             return Some(o);
@@ -565,6 +602,13 @@ impl TypeDefinitionExtension for Option<&schema::TypeDefinition> {
             _ => "",
         }
     }
+
+    fn position(&self) -> Pos {
+        match self {
+            Some(t) => t.position(),
+            _ => Pos::default(),
+        }
+    }
 }
 
 impl TypeDefinitionExtension for schema::TypeDefinition {
@@ -579,45 +623,51 @@ impl TypeDefinitionExtension for schema::TypeDefinition {
         }
     }
 
-    fn is_abstract_type(&self) -> bool {
+    fn position(&self) -> Pos {
         match self {
-            schema::TypeDefinition::Interface(_i) => true,
-            schema::TypeDefinition::Union(_u) => true,
-            _ => false,
+            schema::TypeDefinition::Object(o) => o.position,
+            schema::TypeDefinition::Interface(i) => i.position,
+            schema::TypeDefinition::Union(u) => u.position,
+            schema::TypeDefinition::Scalar(s) => s.position,
+            schema::TypeDefinition::Enum(e) => e.position,
+            schema::TypeDefinition::InputObject(i) => i.position,
         }
     }
 
+    fn is_abstract_type(&self) -> bool {
+        matches!(
+            self,
+            schema::TypeDefinition::Interface(_) | schema::TypeDefinition::Union(_)
+        )
+    }
+
     fn is_interface_type(&self) -> bool {
-        match self {
-            schema::TypeDefinition::Interface(_i) => true,
-            _ => false,
-        }
+        matches!(self, schema::TypeDefinition::Interface(_i))
     }
 
     fn is_leaf_type(&self) -> bool {
-        match self {
-            schema::TypeDefinition::Scalar(_u) => true,
-            schema::TypeDefinition::Enum(_u) => true,
-            _ => false,
-        }
+        matches!(
+            self,
+            schema::TypeDefinition::Scalar(_) | schema::TypeDefinition::Enum(_)
+        )
     }
 
     fn is_input_type(&self) -> bool {
-        match self {
-            schema::TypeDefinition::Scalar(_u) => true,
-            schema::TypeDefinition::Enum(_u) => true,
-            schema::TypeDefinition::InputObject(_u) => true,
-            _ => false,
-        }
+        matches!(
+            self,
+            schema::TypeDefinition::Scalar(_)
+                | schema::TypeDefinition::Enum(_)
+                | schema::TypeDefinition::InputObject(_)
+        )
     }
 
     fn is_composite_type(&self) -> bool {
-        match self {
-            schema::TypeDefinition::Object(_o) => true,
-            schema::TypeDefinition::Interface(_i) => true,
-            schema::TypeDefinition::Union(_u) => true,
-            _ => false,
-        }
+        matches!(
+            self,
+            schema::TypeDefinition::Object(_)
+                | schema::TypeDefinition::Interface(_)
+                | schema::TypeDefinition::Union(_)
+        )
     }
 
     fn is_object_type(&self) -> bool {
@@ -665,7 +715,10 @@ impl AstNodeWithName for query::FragmentSpread {
 }
 
 pub trait FragmentSpreadExtraction {
+    /// Fragment spreads at any depth of the selection set, without following them into their
+    /// fragment definitions (see `transitive_fragment_names` for that).
     fn get_recursive_fragment_spreads(&self) -> Vec<&FragmentSpread>;
+    /// Fragment spreads directly in the selection set.
     fn get_fragment_spreads(&self) -> Vec<&FragmentSpread>;
 }
 
@@ -675,8 +728,10 @@ impl FragmentSpreadExtraction for query::SelectionSet {
             .iter()
             .flat_map(|v| match v {
                 query::Selection::FragmentSpread(f) => vec![f],
-                query::Selection::Field(f) => f.selection_set.get_fragment_spreads(),
-                query::Selection::InlineFragment(f) => f.selection_set.get_fragment_spreads(),
+                query::Selection::Field(f) => f.selection_set.get_recursive_fragment_spreads(),
+                query::Selection::InlineFragment(f) => {
+                    f.selection_set.get_recursive_fragment_spreads()
+                }
             })
             .collect()
     }
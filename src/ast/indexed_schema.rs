@@ -0,0 +1,301 @@
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
+
+use crate::static_graphql::schema::{
+    self, DirectiveDefinition, ObjectType, SchemaDefinition, TypeDefinition,
+};
+
+use super::{SchemaDocumentExtension, TypeDefinitionExtension};
+
+/// A schema `Document` together with lookup tables for its types, directives and abstract types.
+///
+/// `SchemaDocumentExtension` is implemented for `schema::Document` too, but there every lookup
+/// scans the definitions. Building a `Schema` walks the document once, and every lookup after
+/// that is a hash map access. Build it once and pass it to as many `validate` calls as needed.
+#[derive(Clone, Debug)]
+pub struct Schema<'a> {
+    document: &'a schema::Document,
+    schema_definition: &'a SchemaDefinition,
+    types: HashMap<&'a str, &'a TypeDefinition>,
+    directives: HashMap<&'a str, &'a DirectiveDefinition>,
+    /// Object and interface types implementing an interface, by interface name.
+    implementations: HashMap<&'a str, Vec<&'a TypeDefinition>>,
+    /// Object types that are possible types of an abstract type, by abstract type name.
+    possible_types: HashMap<&'a str, Vec<&'a ObjectType>>,
+    /// Names of the union members and interface implementations, by abstract type name.
+    sub_types: HashMap<&'a str, HashSet<&'a str>>,
+}
+
+impl<'a> Schema<'a> {
+    pub fn new(document: &'a schema::Document) -> Self {
+        let mut types = HashMap::new();
+        let mut directives = HashMap::new();
+
+        for definition in &document.definitions {
+            match definition {
+                schema::Definition::TypeDefinition(type_def) => {
+                    types.entry(type_def.name()).or_insert(type_def);
+                }
+                schema::Definition::DirectiveDefinition(directive_def) => {
+                    directives
+                        .entry(directive_def.name.as_str())
+                        .or_insert(directive_def);
+                }
+                _ => {}
+            }
+        }
+
+        let mut implementations: HashMap<&str, Vec<&TypeDefinition>> = HashMap::new();
+        let mut possible_types: HashMap<&str, Vec<&ObjectType>> = HashMap::new();
+        let mut sub_types: HashMap<&str, HashSet<&str>> = HashMap::new();
+
+        for definition in &document.definitions {
+            match definition {
+                schema::Definition::TypeDefinition(TypeDefinition::Union(union_type)) => {
+                    let members = sub_types.entry(union_type.name.as_str()).or_default();
+                    let objects = possible_types.entry(union_type.name.as_str()).or_default();
+
+                    for member in &union_type.types {
+                        members.insert(member.as_str());
+
+                        if let Some(TypeDefinition::Object(object_type)) =
+                            types.get(member.as_str())
+                        {
+                            objects.push(object_type);
+                        }
+                    }
+                }
+                schema::Definition::TypeDefinition(type_def) => {
+                    let interfaces = match type_def {
+                        TypeDefinition::Object(object_type) => &object_type.implements_interfaces,
+                        TypeDefinition::Interface(interface_type) => {
+                            &interface_type.implements_interfaces
+                        }
+                        _ => continue,
+                    };
+
+                    for interface in interfaces {
+                        implementations
+                            .entry(interface.as_str())
+                            .or_default()
+                            .push(type_def);
+                        sub_types
+                            .entry(interface.as_str())
+                            .or_default()
+                            .insert(type_def.name());
+
+                        if let TypeDefinition::Object(object_type) = type_def {
+                            possible_types
+                                .entry(interface.as_str())
+                                .or_default()
+                                .push(object_type);
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        Schema {
+            document,
+            schema_definition: document.schema_definition(),
+            types,
+            directives,
+            implementations,
+            possible_types,
+            sub_types,
+        }
+    }
+
+    /// The document this schema was built from.
+    pub fn document(&self) -> &'a schema::Document {
+        self.document
+    }
+
+    /// Same as `SchemaDocumentExtension::type_by_name`, for a definition living as long as the
+    /// schema document.
+    pub fn type_by_name(&self, name: &str) -> Option<&'a TypeDefinition> {
+        self.types.get(name).copied()
+    }
+
+    /// All the types of the schema, by name. Unlike `SchemaDocumentExtension::type_map`, this
+    /// doesn't build a new map.
+    pub fn type_map(&self) -> &HashMap<&'a str, &'a TypeDefinition> {
+        &self.types
+    }
+
+    /// Same as `SchemaDocumentExtension::directive_by_name`, for a definition living as long as
+    /// the schema document.
+    pub fn directive_by_name(&self, name: &str) -> Option<&'a DirectiveDefinition> {
+        self.directives.get(name).copied()
+    }
+
+    /// Object and interface types implementing the given interface, in document order.
+    pub fn implementations(&self, interface_name: &str) -> &[&'a TypeDefinition] {
+        self.implementations
+            .get(interface_name)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// Object types an abstract type can resolve to, in document order.
+    /// Empty for anything that isn't a union or an interface.
+    pub fn possible_types(&self, abstract_type: &TypeDefinition) -> &[&'a ObjectType] {
+        if !abstract_type.is_abstract_type() {
+            return &[];
+        }
+
+        self.possible_types
+            .get(abstract_type.name())
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+}
+
+impl<'a> SchemaDocumentExtension for Schema<'a> {
+    fn type_by_name(&self, name: &str) -> Option<&TypeDefinition> {
+        Schema::type_by_name(self, name)
+    }
+
+    fn type_map(&self) -> HashMap<&str, &TypeDefinition> {
+        Schema::type_map(self).clone()
+    }
+
+    fn directive_by_name(&self, name: &str) -> Option<&DirectiveDefinition> {
+        Schema::directive_by_name(self, name)
+    }
+
+    fn schema_definition(&self) -> &SchemaDefinition {
+        self.schema_definition
+    }
+
+    fn is_possible_type(
+        &self,
+        abstract_type: &TypeDefinition,
+        possible_type: &TypeDefinition,
+    ) -> bool {
+        abstract_type.is_abstract_type()
+            && self
+                .sub_types
+                .get(abstract_type.name())
+                .is_some_and(|sub_types| sub_types.contains(possible_type.name()))
+    }
+}
+
+/// Anything `validate` can run against: a prebuilt `Schema`, or a schema `Document` that gets
+/// indexed for the duration of the call.
+pub trait IntoSchema<'a> {
+    fn into_schema(self) -> Cow<'a, Schema<'a>>;
+}
+
+impl<'a> IntoSchema<'a> for &'a Schema<'a> {
+    fn into_schema(self) -> Cow<'a, Schema<'a>> {
+        Cow::Borrowed(self)
+    }
+}
+
+impl<'a> IntoSchema<'a> for &'a schema::Document {
+    fn into_schema(self) -> Cow<'a, Schema<'a>> {
+        Cow::Owned(Schema::new(self))
+    }
+}
+
+impl<'a> From<&'a schema::Document> for Schema<'a> {
+    fn from(document: &'a schema::Document) -> Self {
+        Schema::new(document)
+    }
+}
+
+#[test]
+fn indexed_lookups_match_document_lookups() {
+    let document = crate::parser::parse_schema::<String>(
+        "
+type Query { pet: Pet, search: SearchResult }
+interface Node { id: ID! }
+interface Pet implements Node { id: ID!, name: String }
+type Dog implements Pet & Node { id: ID!, name: String }
+type Cat implements Pet & Node { id: ID!, name: String }
+type Human { name: String }
+union SearchResult = Dog | Human | Unknown
+directive @cached(ttl: Int) on FIELD
+",
+    )
+    .expect("Failed to parse schema")
+    .into_static();
+    let schema = Schema::new(&document);
+
+    for name in ["Query", "Node", "Pet", "Dog", "SearchResult", "Missing"] {
+        assert_eq!(schema.type_by_name(name), document.type_by_name(name));
+    }
+    assert_eq!(
+        schema.directive_by_name("cached"),
+        document.directive_by_name("cached")
+    );
+    assert_eq!(schema.directive_by_name("skip"), None);
+    assert_eq!(schema.type_map().len(), document.type_map().len());
+    assert_eq!(schema.query_type().name, "Query");
+    assert_eq!(schema.mutation_type(), None);
+
+    fn names<'a>(types: &[&'a ObjectType]) -> Vec<&'a str> {
+        types.iter().map(|t| t.name.as_str()).collect()
+    }
+
+    let node = schema.type_by_name("Node").unwrap();
+    let pet = schema.type_by_name("Pet").unwrap();
+    let search = schema.type_by_name("SearchResult").unwrap();
+    assert_eq!(names(schema.possible_types(node)), vec!["Dog", "Cat"]);
+    assert_eq!(names(schema.possible_types(search)), vec!["Dog", "Human"]);
+    assert!(schema
+        .possible_types(schema.type_by_name("Dog").unwrap())
+        .is_empty());
+    assert_eq!(
+        schema
+            .implementations("Node")
+            .iter()
+            .map(|t| t.name())
+            .collect::<Vec<_>>(),
+        vec!["Pet", "Dog", "Cat"]
+    );
+
+    for (abstract_type, possible_type) in [
+        (node, "Pet"),
+        (node, "Dog"),
+        (pet, "Cat"),
+        (pet, "Human"),
+        (search, "Human"),
+        (search, "Cat"),
+    ] {
+        let possible_type = schema.type_by_name(possible_type).unwrap();
+        assert_eq!(
+            schema.is_possible_type(abstract_type, possible_type),
+            document.is_possible_type(abstract_type, possible_type)
+        );
+    }
+}
+
+#[test]
+fn visitor_context_accepts_a_document_or_a_prebuilt_schema() {
+    use super::OperationVisitorContext;
+
+    let document = crate::parser::parse_schema::<String>(
+        "type Query { a: Int } directive @cached(ttl: Int) on FIELD",
+    )
+    .expect("Failed to parse schema")
+    .into_static();
+    let schema = Schema::new(&document);
+    let operation = crate::parser::parse_query::<String>("{ a }")
+        .unwrap()
+        .into_static();
+
+    for context in [
+        OperationVisitorContext::new(&operation, &document),
+        OperationVisitorContext::new(&operation, &schema),
+    ] {
+        assert!(std::ptr::eq(context.schema, &document));
+        assert!(context.directives.contains_key("cached"));
+        assert_eq!(
+            context.indexed_schema().type_by_name("Query"),
+            document.type_by_name("Query")
+        );
+    }
+}
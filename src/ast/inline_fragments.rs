@@ -0,0 +1,294 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::parser::query::{
+    Definition, Document, FragmentDefinition, FragmentSpread, InlineFragment, OperationDefinition,
+    Selection, SelectionSet, Text,
+};
+use crate::static_graphql::query;
+
+use super::{OperationTransformer, Transformed, TransformedValue};
+
+/// Replaces fragment spreads with equivalent inline fragments: the type condition of the
+/// fragment, the directives of the spread and the selection set of the fragment, itself inlined.
+/// Fragment definitions that are no longer spread are removed from the document.
+///
+/// Spreads of unknown fragments are left as they are, as well as the spreads that would inline a
+/// fragment inside itself (only possible in operations that fail the `NoFragmentCycles` rule);
+/// the definitions of these fragments are kept.
+pub struct FragmentInliner<'a, T: Text<'a>> {
+    fragments: HashMap<String, FragmentDefinition<'a, T>>,
+    /// Names of the fragments being inlined (or transformed), to detect cycles.
+    fragments_stack: Vec<String>,
+}
+
+impl<'a, T: Text<'a> + Clone> FragmentInliner<'a, T> {
+    /// `document` provides the fragment definitions to inline.
+    pub fn new(document: &Document<'a, T>) -> Self {
+        let fragments = document
+            .definitions
+            .iter()
+            .filter_map(|definition| match definition {
+                Definition::Fragment(fragment) => {
+                    Some((fragment.name.as_ref().to_string(), fragment.clone()))
+                }
+                _ => None,
+            })
+            .collect();
+
+        Self {
+            fragments,
+            fragments_stack: vec![],
+        }
+    }
+}
+
+impl FragmentInliner<'static, String> {
+    /// Uses the fragments known by a visitor, see `OperationVisitorContext::known_fragments`.
+    pub fn from_known_fragments(
+        known_fragments: &HashMap<&str, &query::FragmentDefinition>,
+    ) -> Self {
+        let fragments = known_fragments
+            .iter()
+            .map(|(name, fragment)| (name.to_string(), (*fragment).clone()))
+            .collect();
+
+        Self {
+            fragments,
+            fragments_stack: vec![],
+        }
+    }
+}
+
+impl<'a, T: Text<'a> + Clone> OperationTransformer<'a, T> for FragmentInliner<'a, T> {
+    fn transform_document(
+        &mut self,
+        document: &Document<'a, T>,
+    ) -> TransformedValue<Document<'a, T>> {
+        let transformed = self.default_transform_document(document);
+        let next_document = match &transformed {
+            TransformedValue::Keep => document,
+            TransformedValue::Replace(next_document) => next_document,
+        };
+        let used_fragments = used_fragments(next_document);
+        let is_used = |definition: &Definition<'a, T>| match definition {
+            Definition::Fragment(fragment) => used_fragments.contains(fragment.name.as_ref()),
+            Definition::Operation(_) => true,
+        };
+
+        if next_document.definitions.iter().all(is_used) {
+            return transformed;
+        }
+
+        TransformedValue::Replace(Document {
+            definitions: next_document
+                .definitions
+                .iter()
+                .filter(|definition| is_used(definition))
+                .cloned()
+                .collect(),
+        })
+    }
+
+    fn transform_fragment(
+        &mut self,
+        fragment: &FragmentDefinition<'a, T>,
+    ) -> Transformed<FragmentDefinition<'a, T>> {
+        // Fragments that remain are spread through a cycle, their spreads of themselves are kept
+        self.fragments_stack
+            .push(fragment.name.as_ref().to_string());
+        let transformed = self.default_transform_fragment(fragment);
+        self.fragments_stack.pop();
+
+        transformed
+    }
+
+    fn transform_fragment_spread(
+        &mut self,
+        spread: &FragmentSpread<'a, T>,
+    ) -> Transformed<Selection<'a, T>> {
+        let fragment_name = spread.fragment_name.as_ref();
+
+        if self
+            .fragments_stack
+            .iter()
+            .any(|name| name == fragment_name)
+        {
+            return self.default_transform_fragment_spread(spread);
+        }
+
+        let fragment = match self.fragments.get(fragment_name) {
+            Some(fragment) => fragment.clone(),
+            None => return self.default_transform_fragment_spread(spread),
+        };
+
+        self.fragments_stack.push(fragment_name.to_string());
+        let items = self
+            .transform_selection_set(&fragment.selection_set)
+            .replace_or_else(|| fragment.selection_set.items.clone());
+        self.fragments_stack.pop();
+
+        let directives = self
+            .transform_directives(&spread.directives)
+            .replace_or_else(|| spread.directives.clone());
+
+        Transformed::Replace(Selection::InlineFragment(InlineFragment {
+            position: spread.position,
+            type_condition: Some(fragment.type_condition),
+            directives,
+            selection_set: SelectionSet {
+                span: fragment.selection_set.span,
+                items,
+            },
+        }))
+    }
+}
+
+/// Returns `document` with its fragments inlined by `FragmentInliner`.
+pub fn inline_fragments<'a, T: Text<'a> + Clone>(document: &Document<'a, T>) -> Document<'a, T> {
+    FragmentInliner::new(document)
+        .transform_document(document)
+        .replace_or_else(|| document.clone())
+}
+
+/// Names of the fragments spread by the operations of `document`, directly or through other
+/// fragments.
+pub(crate) fn used_fragments<'a, T: Text<'a>>(document: &Document<'a, T>) -> HashSet<String> {
+    fn visit<'a, T: Text<'a>>(
+        selection_set: &SelectionSet<'a, T>,
+        fragments: &HashMap<&str, &SelectionSet<'a, T>>,
+        used: &mut HashSet<String>,
+    ) {
+        for selection in &selection_set.items {
+            match selection {
+                Selection::Field(field) => visit(&field.selection_set, fragments, used),
+                Selection::InlineFragment(inline_fragment) => {
+                    visit(&inline_fragment.selection_set, fragments, used)
+                }
+                Selection::FragmentSpread(spread) => {
+                    let name = spread.fragment_name.as_ref();
+
+                    if used.insert(name.to_string()) {
+                        if let Some(fragment_selection_set) = fragments.get(name) {
+                            visit(fragment_selection_set, fragments, used);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    let fragments = document
+        .definitions
+        .iter()
+        .filter_map(|definition| match definition {
+            Definition::Fragment(fragment) => {
+                Some((fragment.name.as_ref(), &fragment.selection_set))
+            }
+            _ => None,
+        })
+        .collect::<HashMap<_, _>>();
+
+    let mut used = HashSet::new();
+
+    for definition in &document.definitions {
+        if let Definition::Operation(operation) = definition {
+            let selection_set = match operation {
+                OperationDefinition::SelectionSet(selection_set) => selection_set,
+                OperationDefinition::Query(query) => &query.selection_set,
+                OperationDefinition::Mutation(mutation) => &mutation.selection_set,
+                OperationDefinition::Subscription(subscription) => &subscription.selection_set,
+            };
+
+            visit(selection_set, &fragments, &mut used);
+        }
+    }
+
+    used
+}
+
+#[test]
+fn inlines_nested_fragments() {
+    let document = crate::parser::parse_query::<&str>(
+        "
+        query Users($withFriends: Boolean!) {
+          users { ...UserFields @include(if: $withFriends) ... on Admin { ...Level } }
+          unknown { ...Unknown }
+        }
+
+        fragment UserFields on User { id friends { ...Name } }
+        fragment Name on User { name }
+        fragment Level on Admin { level }
+        fragment Unused on User { id }
+        ",
+    )
+    .expect("Failed to parse query");
+
+    assert_eq!(
+        crate::ast::print_operation(
+            &inline_fragments(&document),
+            &crate::ast::PrintOperationOptions::new()
+        ),
+        "query Users($withFriends: Boolean!) {
+  users {
+    ... on User @include(if: $withFriends) {
+      id
+      friends {
+        ... on User {
+          name
+        }
+      }
+    }
+    ... on Admin {
+      ... on Admin {
+        level
+      }
+    }
+  }
+  unknown {
+    ...Unknown
+  }
+}"
+    );
+}
+
+#[test]
+fn inlines_cyclic_fragments_once() {
+    let document = crate::parser::parse_query::<String>(
+        "
+        { me { ...A } }
+        fragment A on User { friends { ...B } }
+        fragment B on User { id ...A }
+        ",
+    )
+    .expect("Failed to parse query")
+    .into_static();
+
+    let inlined = inline_fragments(&document);
+
+    assert_eq!(
+        crate::ast::print_operation(
+            &inlined,
+            &crate::ast::PrintOperationOptions {
+                minify: true,
+                ..crate::ast::PrintOperationOptions::new()
+            }
+        ),
+        "{me{...on User{friends{...on User{id...A}}}}}fragment A on User{friends{...on User{id...A}}}"
+    );
+
+    let known_fragments = document
+        .definitions
+        .iter()
+        .filter_map(|definition| match definition {
+            Definition::Fragment(fragment) => Some((fragment.name.as_str(), fragment)),
+            _ => None,
+        })
+        .collect();
+
+    assert_eq!(
+        FragmentInliner::from_known_fragments(&known_fragments)
+            .transform_document(&document)
+            .replace_or_else(|| document.clone()),
+        inlined
+    );
+}
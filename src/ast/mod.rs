@@ -1,12 +1,35 @@
 pub mod collect_fields;
 pub mod ext;
+pub mod indexed_schema;
+pub mod inline_fragments;
+pub mod normalize_operation;
 pub mod operation_transformer;
 pub mod operation_visitor;
+pub mod parallel_visitor;
+pub mod print_operation;
+pub mod print_schema;
+pub mod prune_skipped_selections;
+pub mod schema_coordinate;
+pub mod schema_coordinates_collector;
+pub mod schema_extensions;
 /// Utilities visiting GraphQL AST trees
 pub mod schema_visitor;
+pub(crate) mod source_positions;
+pub mod split_operations;
 
 pub use self::collect_fields::*;
 pub use self::ext::*;
+pub use self::indexed_schema::*;
+pub use self::inline_fragments::*;
+pub use self::normalize_operation::*;
 pub use self::operation_transformer::*;
 pub use self::operation_visitor::*;
+pub use self::parallel_visitor::*;
+pub use self::print_operation::*;
+pub use self::print_schema::*;
+pub use self::prune_skipped_selections::*;
+pub use self::schema_coordinate::*;
+pub use self::schema_coordinates_collector::*;
+pub use self::schema_extensions::*;
 pub use self::schema_visitor::*;
+pub use self::split_operations::*;
@@ -0,0 +1,328 @@
+use std::collections::BTreeMap;
+use std::marker::PhantomData;
+
+use crate::parser::query::{
+    Definition, Directive, Document, Field, Number, Selection, SelectionSet, Text, TypeCondition,
+    Value, VariableDefinition,
+};
+
+use super::inline_fragments::{inline_fragments, used_fragments};
+use super::print_operation::{print_operation, print_selection, PrintOperationOptions};
+use super::{OperationTransformer, Transformed, TransformedValue};
+
+/// Options of `normalize_operation` and `OperationNormalizer`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NormalizeOperationOptions {
+    /// Whether to replace literal values: strings become `""`, numbers `0`, lists `[]` and
+    /// objects `{}`. Booleans, enum values and nulls are kept, since they can change the shape
+    /// of the response. Defaults to `true`.
+    pub hide_literals: bool,
+    /// Whether to remove field aliases. Defaults to `false`.
+    pub remove_aliases: bool,
+    /// Whether to replace fragment spreads with inline fragments, see `FragmentInliner`. Defaults
+    /// to `false`.
+    pub inline_fragments: bool,
+}
+
+impl Default for NormalizeOperationOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl NormalizeOperationOptions {
+    pub fn new() -> Self {
+        Self {
+            hide_literals: true,
+            remove_aliases: false,
+            inline_fragments: false,
+        }
+    }
+}
+
+/// Transforms operations into a normalized form, so equivalent operations become the same
+/// document: literals are hidden, selections, arguments, directives and variable definitions
+/// are sorted, fragments are sorted by name and the unused ones are dropped.
+pub struct OperationNormalizer<'a, T: Text<'a>> {
+    options: NormalizeOperationOptions,
+    /// Ties the normalizer to the `Text` of the documents it transforms.
+    text: PhantomData<Document<'a, T>>,
+}
+
+impl<'a, T: Text<'a>> OperationNormalizer<'a, T> {
+    pub fn new(options: NormalizeOperationOptions) -> Self {
+        Self {
+            options,
+            text: PhantomData,
+        }
+    }
+}
+
+impl<'a, T: Text<'a> + Clone> OperationTransformer<'a, T> for OperationNormalizer<'a, T> {
+    fn transform_document(
+        &mut self,
+        document: &Document<'a, T>,
+    ) -> TransformedValue<Document<'a, T>> {
+        let document = if self.options.inline_fragments {
+            inline_fragments(document)
+        } else {
+            document.clone()
+        };
+        let document = self
+            .default_transform_document(&document)
+            .replace_or_else(|| document);
+        let used_fragments = used_fragments(&document);
+
+        let mut operations = vec![];
+        let mut fragments = vec![];
+
+        for definition in document.definitions {
+            match definition {
+                Definition::Operation(operation) => operations.push(operation),
+                Definition::Fragment(fragment) => {
+                    if used_fragments.contains(fragment.name.as_ref()) {
+                        fragments.push(fragment);
+                    }
+                }
+            }
+        }
+
+        fragments.sort_by(|a, b| a.name.as_ref().cmp(b.name.as_ref()));
+
+        TransformedValue::Replace(Document {
+            definitions: operations
+                .into_iter()
+                .map(Definition::Operation)
+                .chain(fragments.into_iter().map(Definition::Fragment))
+                .collect(),
+        })
+    }
+
+    fn transform_selection_set(
+        &mut self,
+        selections: &SelectionSet<'a, T>,
+    ) -> TransformedValue<Vec<Selection<'a, T>>> {
+        let mut items = self
+            .transform_list(&selections.items, Self::transform_selection)
+            .replace_or_else(|| selections.items.clone());
+
+        items.sort_by_cached_key(selection_sort_key);
+
+        TransformedValue::Replace(items)
+    }
+
+    fn transform_field(&mut self, field: &Field<'a, T>) -> Transformed<Selection<'a, T>> {
+        let transformed = self.default_transform_field(field);
+
+        if !self.options.remove_aliases {
+            return transformed;
+        }
+
+        let mut field = match transformed {
+            Transformed::Replace(Selection::Field(field)) => field,
+            Transformed::Replace(selection) => return Transformed::Replace(selection),
+            Transformed::Keep => field.clone(),
+        };
+        field.alias = None;
+
+        Transformed::Replace(Selection::Field(field))
+    }
+
+    fn transform_directives(
+        &mut self,
+        directives: &[Directive<'a, T>],
+    ) -> TransformedValue<Vec<Directive<'a, T>>> {
+        let mut directives = self
+            .transform_list(directives, Self::transform_directive)
+            .replace_or_else(|| directives.to_vec());
+
+        directives.sort_by(|a, b| a.name.as_ref().cmp(b.name.as_ref()));
+
+        TransformedValue::Replace(directives)
+    }
+
+    fn transform_arguments(
+        &mut self,
+        arguments: &[(T::Value, Value<'a, T>)],
+    ) -> TransformedValue<Vec<(T::Value, Value<'a, T>)>> {
+        let mut arguments = self
+            .transform_list(arguments, Self::transform_argument)
+            .replace_or_else(|| arguments.to_vec());
+
+        arguments.sort_by(|(a, _), (b, _)| a.as_ref().cmp(b.as_ref()));
+
+        TransformedValue::Replace(arguments)
+    }
+
+    fn transform_value(&mut self, value: &Value<'a, T>) -> TransformedValue<Value<'a, T>> {
+        if !self.options.hide_literals {
+            return TransformedValue::Keep;
+        }
+
+        match value {
+            Value::Int(_) => TransformedValue::Replace(Value::Int(Number::from(0))),
+            Value::Float(_) => TransformedValue::Replace(Value::Int(Number::from(0))),
+            Value::String(_) => TransformedValue::Replace(Value::String(String::new())),
+            Value::List(_) => TransformedValue::Replace(Value::List(vec![])),
+            Value::Object(_) => TransformedValue::Replace(Value::Object(BTreeMap::new())),
+            Value::Variable(_) | Value::Boolean(_) | Value::Null | Value::Enum(_) => {
+                TransformedValue::Keep
+            }
+        }
+    }
+
+    fn transform_variable_definitions(
+        &mut self,
+        variable_definitions: &Vec<VariableDefinition<'a, T>>,
+    ) -> TransformedValue<Vec<VariableDefinition<'a, T>>> {
+        let mut variable_definitions = self
+            .default_transform_variable_definitions(variable_definitions)
+            .replace_or_else(|| variable_definitions.clone());
+
+        variable_definitions.sort_by(|a, b| a.name.as_ref().cmp(b.name.as_ref()));
+
+        TransformedValue::Replace(variable_definitions)
+    }
+}
+
+/// An operation document in normal form, see `normalize_operation`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NormalizedOperation<'a, T: Text<'a>> {
+    pub document: Document<'a, T>,
+    /// `document`, printed with `print_operation` and `PrintOperationOptions::minify`.
+    pub operation: String,
+    /// A hash of `operation` (128 bits FNV-1a, in hexadecimal), stable across runs and
+    /// platforms. Equivalent operations get the same signature.
+    pub signature: String,
+}
+
+/// Normalizes an operation document with `OperationNormalizer`, prints it compactly and hashes
+/// it, for use as a cache key or in usage reports.
+pub fn normalize_operation<'a, T: Text<'a> + Clone>(
+    document: &Document<'a, T>,
+    options: &NormalizeOperationOptions,
+) -> NormalizedOperation<'a, T> {
+    let document = OperationNormalizer::new(options.clone())
+        .transform_document(document)
+        .replace_or_else(|| document.clone());
+
+    let operation = print_operation(&document, &minified_print_options());
+    let signature = format!("{:032x}", fnv1a_128(operation.as_bytes()));
+
+    NormalizedOperation {
+        document,
+        operation,
+        signature,
+    }
+}
+
+/// Fields come first, then fragment spreads and inline fragments, each sorted by name (or
+/// type condition). Selections with the same name are sorted by how they print.
+fn selection_sort_key<'a, T: Text<'a>>(selection: &Selection<'a, T>) -> (u8, String, String) {
+    let (rank, name) = match selection {
+        Selection::Field(field) => (0, field.name.as_ref().to_string()),
+        Selection::FragmentSpread(spread) => (1, spread.fragment_name.as_ref().to_string()),
+        Selection::InlineFragment(inline_fragment) => (
+            2,
+            match &inline_fragment.type_condition {
+                Some(TypeCondition::On(type_name)) => type_name.as_ref().to_string(),
+                None => String::new(),
+            },
+        ),
+    };
+
+    (
+        rank,
+        name,
+        print_selection(selection, &minified_print_options()),
+    )
+}
+
+fn minified_print_options() -> PrintOperationOptions {
+    PrintOperationOptions {
+        minify: true,
+        ..PrintOperationOptions::new()
+    }
+}
+
+fn fnv1a_128(bytes: &[u8]) -> u128 {
+    const OFFSET_BASIS: u128 = 0x6c62272e07bb014262b821756295c58d;
+    const PRIME: u128 = 0x0000000001000000000000000000013b;
+
+    bytes.iter().fold(OFFSET_BASIS, |hash, byte| {
+        (hash ^ *byte as u128).wrapping_mul(PRIME)
+    })
+}
+
+#[test]
+fn normalizes_equivalent_operations_to_the_same_signature() {
+    let options = NormalizeOperationOptions::new();
+    let a = crate::parser::parse_query::<String>(
+        "
+        query Users($role: Role, $first: Int = 10) {
+          users(role: $role, first: 5, filter: { name: \"john\" }) @include(if: true) {
+            name
+            id
+            ...Posts
+            ... on Admin { level }
+          }
+        }
+
+        fragment Unused on User { email }
+        fragment Posts on User { posts(tags: [\"a\", \"b\"]) { title } }
+        ",
+    )
+    .expect("Failed to parse query");
+    let b = crate::parser::parse_query::<String>(
+        "query Users($first:Int=20,$role:Role){users(filter:{},first:100,role:$role)@include(if:true){...on Admin{level} ...Posts id name}}
+        fragment Posts on User{posts(tags:[]){title}}",
+    )
+    .expect("Failed to parse query");
+
+    let a = normalize_operation(&a, &options);
+    let b = normalize_operation(&b, &options);
+
+    assert_eq!(
+        a.operation,
+        "query Users($first:Int=0$role:Role){users(filter:{}first:0 role:$role)@include(if:true){id name...Posts...on Admin{level}}}fragment Posts on User{posts(tags:[]){title}}"
+    );
+    assert_eq!(a.operation, b.operation);
+    assert_eq!(a.signature, b.signature);
+    assert_eq!(a.signature.len(), 32);
+
+    let c = crate::parser::parse_query::<String>("query Users { users { id } }")
+        .expect("Failed to parse query");
+
+    assert_ne!(a.signature, normalize_operation(&c, &options).signature);
+}
+
+#[test]
+fn normalizes_with_options() {
+    let document = crate::parser::parse_query::<String>(
+        "
+        query {
+          me { ...UserFields @skip(if: false) }
+          user: node(id: \"1\") { ...Node }
+        }
+
+        fragment UserFields on User { friend: friends(first: 2) { ...UserFields } }
+        fragment Node on Node { id }
+        ",
+    )
+    .expect("Failed to parse query");
+
+    let normalized = normalize_operation(
+        &document,
+        &NormalizeOperationOptions {
+            hide_literals: false,
+            remove_aliases: true,
+            inline_fragments: true,
+        },
+    );
+
+    // Cyclic spreads are left as they are, so their fragment is kept
+    assert_eq!(
+        normalized.operation,
+        "{me{...on User@skip(if:false){friends(first:2){...UserFields}}}node(id:\"1\"){...on Node{id}}}fragment UserFields on User{friends(first:2){...UserFields}}"
+    );
+}
@@ -1,15 +1,40 @@
+use std::borrow::Cow;
 use std::collections::{BTreeMap, HashMap};
+use std::ops::Deref;
 
+use crate::introspection::BUILT_INS;
 use crate::parser::query::TypeCondition;
+use crate::parser::Pos;
 
 use crate::static_graphql::{
     query::{self, *},
     schema::{self},
 };
 
+use super::source_positions::{SourcePositions, ValuePositions};
 use super::{
-    FieldByNameExtension, OperationDefinitionExtension, SchemaDocumentExtension, TypeExtension,
+    FieldByNameExtension, IntoSchema, OperationDefinitionExtension, Schema,
+    SchemaDocumentExtension, TypeExtension,
 };
+
+/// The `Schema` of an `OperationVisitorContext`: borrowed when it was prebuilt, or built from the
+/// schema `Document` for this context. Unlike `Cow`, this keeps the context covariant in `'a`.
+enum IndexedSchema<'a> {
+    Borrowed(&'a Schema<'a>),
+    Owned(Box<Schema<'a>>),
+}
+
+impl<'a> Deref for IndexedSchema<'a> {
+    type Target = Schema<'a>;
+
+    fn deref(&self) -> &Self::Target {
+        match self {
+            IndexedSchema::Borrowed(schema) => schema,
+            IndexedSchema::Owned(schema) => schema,
+        }
+    }
+}
+
 /// OperationVisitor
 pub struct OperationVisitorContext<'a> {
     pub schema: &'a schema::Document,
@@ -17,25 +42,41 @@ pub struct OperationVisitorContext<'a> {
     pub known_fragments: HashMap<&'a str, &'a FragmentDefinition>,
     pub directives: HashMap<String, schema::DirectiveDefinition>,
 
+    indexed_schema: IndexedSchema<'a>,
     type_stack: Vec<Option<&'a schema::TypeDefinition>>,
     parent_type_stack: Vec<Option<&'a schema::TypeDefinition>>,
     input_type_stack: Vec<Option<&'a schema::TypeDefinition>>,
     type_literal_stack: Vec<Option<Type>>,
     input_type_literal_stack: Vec<Option<&'a Type>>,
     field_stack: Vec<Option<&'a schema::Field>>,
+    position_stack: Vec<Pos>,
+    object_fields_stack: Vec<Vec<(&'a str, Pos)>>,
+    source_positions: Option<SourcePositions<'a>>,
 }
 
 impl<'a> OperationVisitorContext<'a> {
-    pub fn new(operation: &'a Document, schema: &'a schema::Document) -> Self {
+    /// Creates the context for visiting `operation`. The schema is either a schema `Document`,
+    /// or a prebuilt `Schema` to reuse across many operations.
+    pub fn new(operation: &'a Document, schema: impl IntoSchema<'a>) -> Self {
+        let indexed_schema = match schema.into_schema() {
+            Cow::Borrowed(schema) => IndexedSchema::Borrowed(schema),
+            Cow::Owned(schema) => IndexedSchema::Owned(Box::new(schema)),
+        };
+        let schema = indexed_schema.document();
+
         OperationVisitorContext {
             schema,
             operation,
+            indexed_schema,
             type_stack: vec![],
             parent_type_stack: vec![],
             input_type_stack: vec![],
             type_literal_stack: vec![],
             input_type_literal_stack: vec![],
             field_stack: vec![],
+            position_stack: vec![],
+            object_fields_stack: vec![],
+            source_positions: None,
             known_fragments: HashMap::from_iter(operation.definitions.iter().filter_map(|def| {
                 match def {
                     Definition::Fragment(fragment) => Some((fragment.name.as_str(), fragment)),
@@ -53,13 +94,33 @@ impl<'a> OperationVisitorContext<'a> {
         }
     }
 
+    /// Same as `new`, with the source `operation` was parsed from. The parsed AST has no
+    /// positions for arguments and values, they are read from the source instead; without it,
+    /// arguments and values get the position of the field or directive they belong to.
+    pub fn new_with_source(
+        operation: &'a Document,
+        schema: impl IntoSchema<'a>,
+        source: &'a str,
+    ) -> Self {
+        let mut context = Self::new(operation, schema);
+        context.source_positions = Some(SourcePositions::new(source));
+
+        context
+    }
+
+    /// The schema with its lookup tables. Prefer it over `schema` for looking up types and
+    /// directives, `SchemaDocumentExtension` scans the definitions of a `Document`.
+    pub fn indexed_schema(&self) -> &Schema<'a> {
+        &self.indexed_schema
+    }
+
     pub fn with_type<Func>(&mut self, t: Option<&Type>, func: Func)
     where
         Func: FnOnce(&mut OperationVisitorContext<'a>),
     {
         if let Some(t) = t {
             self.type_stack
-                .push(self.schema.type_by_name(t.inner_type()));
+                .push(self.indexed_schema.type_by_name(t.inner_type()));
         } else {
             self.type_stack.push(None);
         }
@@ -101,7 +162,7 @@ impl<'a> OperationVisitorContext<'a> {
     {
         if let Some(t) = t {
             self.input_type_stack
-                .push(self.schema.type_by_name(t.inner_type()));
+                .push(self.indexed_schema.type_by_name(t.inner_type()));
         } else {
             self.input_type_stack.push(None);
         }
@@ -112,6 +173,15 @@ impl<'a> OperationVisitorContext<'a> {
         self.input_type_stack.pop();
     }
 
+    pub fn with_position<Func>(&mut self, position: Pos, func: Func)
+    where
+        Func: FnOnce(&mut OperationVisitorContext<'a>),
+    {
+        self.position_stack.push(position);
+        func(self);
+        self.position_stack.pop();
+    }
+
     pub fn current_type(&self) -> Option<&schema::TypeDefinition> {
         self.type_stack.last().unwrap_or(&None).as_deref()
     }
@@ -135,6 +205,50 @@ impl<'a> OperationVisitorContext<'a> {
     pub fn current_field(&self) -> Option<&schema::Field> {
         self.field_stack.last().unwrap_or(&None).as_deref()
     }
+
+    /// The position of the node being visited, or of the closest one that has a position.
+    ///
+    /// Arguments and values don't carry a position in the parsed AST, use this in their
+    /// callbacks. Their positions are read from the source (see `new_with_source`), or are the
+    /// position of the field or directive they belong to.
+    pub fn current_position(&self) -> Pos {
+        self.position_stack.last().copied().unwrap_or_default()
+    }
+
+    /// Whether the context was created with the source of the operation, see `new_with_source`.
+    pub(crate) fn has_source(&self) -> bool {
+        self.source_positions.is_some()
+    }
+
+    /// Names and positions of the fields of the object value being visited, in source order.
+    /// Unlike the parsed value, this keeps duplicated fields. Empty without the source (see
+    /// `new_with_source`).
+    pub(crate) fn current_object_fields(&self) -> &[(&'a str, Pos)] {
+        self.object_fields_stack
+            .last()
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// Positions of `count` arguments of the field or directive being visited, see
+    /// `current_position`.
+    fn argument_positions(&self, count: usize) -> Vec<(Pos, Option<ValuePositions<'a>>)> {
+        let position = self.current_position();
+
+        if let Some(source_positions) = &self.source_positions {
+            let arguments = source_positions.arguments(position);
+
+            // Don't guess when the source doesn't match the AST
+            if arguments.len() == count {
+                return arguments
+                    .into_iter()
+                    .map(|(position, value)| (position, Some(value)))
+                    .collect();
+            }
+        }
+
+        vec![(position, None); count]
+    }
 }
 
 pub fn visit_document<'a, Visitor, UserContext>(
@@ -143,7 +257,7 @@ pub fn visit_document<'a, Visitor, UserContext>(
     context: &mut OperationVisitorContext<'a>,
     user_context: &mut UserContext,
 ) where
-    Visitor: OperationVisitor<'a, UserContext>,
+    Visitor: OperationVisitor<'a, UserContext> + ?Sized,
 {
     visitor.enter_document(context, user_context, document);
     visit_definitions(visitor, &document.definitions, context, user_context);
@@ -156,7 +270,7 @@ fn visit_definitions<'a, Visitor, UserContext>(
     context: &mut OperationVisitorContext<'a>,
     user_context: &mut UserContext,
 ) where
-    Visitor: OperationVisitor<'a, UserContext>,
+    Visitor: OperationVisitor<'a, UserContext> + ?Sized,
 {
     for definition in definitions {
         let schema_type_name = match definition {
@@ -165,23 +279,31 @@ fn visit_definitions<'a, Visitor, UserContext>(
                 Some(name)
             }
             Definition::Operation(operation) => match operation {
-                OperationDefinition::Query(_) => Some(&context.schema.query_type().name),
-                OperationDefinition::SelectionSet(_) => Some(&context.schema.query_type().name),
+                OperationDefinition::Query(_) => Some(&context.indexed_schema().query_type().name),
+                OperationDefinition::SelectionSet(_) => {
+                    Some(&context.indexed_schema().query_type().name)
+                }
                 OperationDefinition::Mutation(_) => {
-                    context.schema.mutation_type().map(|t| &t.name).or_else(|| {
-                        // Awkward hack but enables me to move forward
-                        // Somehow the `mutation_type()` gives None, even though `Mutation` type is defined in the schema.
-                        if let Some(type_definition) = context.schema.type_by_name("Mutation") {
-                            return match type_definition {
-                                crate::parser::schema::TypeDefinition::Object(object_type) => {
-                                    Some(&object_type.name)
-                                }
-                                _ => None,
-                            };
-                        }
+                    context
+                        .indexed_schema()
+                        .mutation_type()
+                        .map(|t| &t.name)
+                        .or_else(|| {
+                            // Awkward hack but enables me to move forward
+                            // Somehow the `mutation_type()` gives None, even though `Mutation` type is defined in the schema.
+                            if let Some(type_definition) =
+                                context.indexed_schema().type_by_name("Mutation")
+                            {
+                                return match type_definition {
+                                    crate::parser::schema::TypeDefinition::Object(object_type) => {
+                                        Some(&object_type.name)
+                                    }
+                                    _ => None,
+                                };
+                            }
 
-                        None
-                    })
+                            None
+                        })
                 }
                 OperationDefinition::Subscription(_) => {
                     context
@@ -192,7 +314,7 @@ fn visit_definitions<'a, Visitor, UserContext>(
                             // Awkward hack but enables me to move forward
                             // Somehow the `subscription_type()` gives None, even though `Subscription` type is defined in the schema.
                             if let Some(type_definition) =
-                                context.schema.type_by_name("Subscription")
+                                context.indexed_schema().type_by_name("Subscription")
                             {
                                 return match type_definition {
                                     crate::parser::schema::TypeDefinition::Object(object_type) => {
@@ -210,12 +332,13 @@ fn visit_definitions<'a, Visitor, UserContext>(
 
         let schema_type = schema_type_name.map(|v| Type::NamedType(v.clone()));
         context.with_type(schema_type.as_ref(), |context| match definition {
-            Definition::Fragment(fragment) => {
+            Definition::Fragment(fragment) => context.with_position(fragment.position, |context| {
                 visit_fragment_definition(visitor, fragment, context, user_context)
-            }
-            Definition::Operation(operation) => {
-                visit_operation_definition(visitor, operation, context, user_context)
-            }
+            }),
+            Definition::Operation(operation) => context
+                .with_position(operation.position(), |context| {
+                    visit_operation_definition(visitor, operation, context, user_context)
+                }),
         });
     }
 }
@@ -226,23 +349,26 @@ fn visit_directives<'a, Visitor, UserContext>(
     context: &mut OperationVisitorContext<'a>,
     user_context: &mut UserContext,
 ) where
-    Visitor: OperationVisitor<'a, UserContext>,
+    Visitor: OperationVisitor<'a, UserContext> + ?Sized,
 {
     for directive in directives {
         let directive_def_args = context
-            .schema
+            .indexed_schema()
             .directive_by_name(&directive.name)
+            .or_else(|| BUILT_INS.directive_by_name(&directive.name))
             .map(|def| &def.arguments);
 
-        visitor.enter_directive(context, user_context, directive);
-        visit_arguments(
-            visitor,
-            directive_def_args,
-            &directive.arguments,
-            context,
-            user_context,
-        );
-        visitor.leave_directive(context, user_context, directive);
+        context.with_position(directive.position, |context| {
+            visitor.enter_directive(context, user_context, directive);
+            visit_arguments(
+                visitor,
+                directive_def_args,
+                &directive.arguments,
+                context,
+                user_context,
+            );
+            visitor.leave_directive(context, user_context, directive);
+        });
     }
 }
 
@@ -253,17 +379,27 @@ fn visit_arguments<'a, Visitor, UserContext>(
     context: &mut OperationVisitorContext<'a>,
     user_context: &mut UserContext,
 ) where
-    Visitor: OperationVisitor<'a, UserContext>,
+    Visitor: OperationVisitor<'a, UserContext> + ?Sized,
 {
-    for argument in arguments {
+    let positions = context.argument_positions(arguments.len());
+
+    for (argument, (position, value_positions)) in arguments.iter().zip(positions) {
         let arg_type = arguments_definition
             .and_then(|argument_defs| argument_defs.iter().find(|a| a.name.eq(&argument.0)))
             .map(|a| &a.value_type);
 
         context.with_input_type(arg_type, |context| {
-            visitor.enter_argument(context, user_context, argument);
-            visit_input_value(visitor, &argument.1, context, user_context);
-            visitor.leave_argument(context, user_context, argument);
+            context.with_position(position, |context| {
+                visitor.enter_argument(context, user_context, argument);
+                visit_input_value(
+                    visitor,
+                    &argument.1,
+                    value_positions.as_ref(),
+                    context,
+                    user_context,
+                );
+                visitor.leave_argument(context, user_context, argument);
+            })
         })
     }
 }
@@ -271,12 +407,15 @@ fn visit_arguments<'a, Visitor, UserContext>(
 fn visit_input_value<'a, Visitor, UserContext>(
     visitor: &mut Visitor,
     input_value: &'a Value,
+    positions: Option<&ValuePositions<'a>>,
     context: &mut OperationVisitorContext<'a>,
     user_context: &mut UserContext,
 ) where
-    Visitor: OperationVisitor<'a, UserContext>,
+    Visitor: OperationVisitor<'a, UserContext> + ?Sized,
 {
-    match input_value {
+    let position = positions.map_or_else(|| context.current_position(), |p| p.position);
+
+    context.with_position(position, |context| match input_value {
         Value::Boolean(_) | Value::Float(_) | Value::Int(_) | Value::String(_) => {
             visitor.enter_scalar_value(context, user_context, input_value);
             visitor.leave_scalar_value(context, user_context, input_value);
@@ -296,40 +435,70 @@ fn visit_input_value<'a, Visitor, UserContext>(
                 Type::ListType(inner_type) => Some(inner_type.as_ref()),
                 _ => None,
             });
+            let item_positions = positions
+                .map(|p| &p.items)
+                .filter(|items| items.len() == v.len());
 
             context.with_input_type(input_type, |context| {
-                for item in v {
-                    visit_input_value(visitor, item, context, user_context)
+                for (index, item) in v.iter().enumerate() {
+                    let item_positions = item_positions.map(|items| &items[index]);
+                    visit_input_value(visitor, item, item_positions, context, user_context)
                 }
             });
 
             visitor.leave_list_value(context, user_context, v);
         }
         Value::Object(v) => {
+            let fields = positions
+                .map(|p| {
+                    p.fields
+                        .iter()
+                        .map(|(name, position, _)| (*name, *position))
+                })
+                .into_iter()
+                .flatten()
+                .collect();
+            context.object_fields_stack.push(fields);
             visitor.enter_object_value(context, user_context, v);
 
             for (sub_key, sub_value) in v.iter() {
                 let input_type = context
                     .current_input_type_literal()
-                    .and_then(|v| context.schema.type_by_name(v.inner_type()))
+                    .and_then(|v| context.indexed_schema().type_by_name(v.inner_type()))
                     .and_then(|v| v.input_field_by_name(sub_key))
                     .map(|v| &v.value_type);
+                let (field_position, value_positions) =
+                    match positions.and_then(|p| p.field(sub_key)) {
+                        Some((field_position, value_positions)) => {
+                            (field_position, Some(value_positions))
+                        }
+                        None => (position, None),
+                    };
 
                 context.with_input_type(input_type, |context| {
-                    let param = &(sub_key.clone(), sub_value.clone());
-                    visitor.enter_object_field(context, user_context, param);
-                    visit_input_value(visitor, sub_value, context, user_context);
-                    visitor.leave_object_field(context, user_context, param);
+                    context.with_position(field_position, |context| {
+                        let param = &(sub_key.clone(), sub_value.clone());
+                        visitor.enter_object_field(context, user_context, param);
+                        visit_input_value(
+                            visitor,
+                            sub_value,
+                            value_positions,
+                            context,
+                            user_context,
+                        );
+                        visitor.leave_object_field(context, user_context, param);
+                    })
                 });
             }
 
             visitor.leave_object_value(context, user_context, v);
+            context.object_fields_stack.pop();
         }
         Value::Variable(v) => {
             visitor.enter_variable_value(context, user_context, v);
             visitor.leave_variable_value(context, user_context, v);
         }
-    }
+    })
 }
 
 fn visit_variable_definitions<'a, Visitor, UserContext>(
@@ -338,19 +507,35 @@ fn visit_variable_definitions<'a, Visitor, UserContext>(
     context: &mut OperationVisitorContext<'a>,
     user_context: &mut UserContext,
 ) where
-    Visitor: OperationVisitor<'a, UserContext>,
+    Visitor: OperationVisitor<'a, UserContext> + ?Sized,
 {
     for variable in variables {
         context.with_input_type(Some(&variable.var_type), |context| {
-            visitor.enter_variable_definition(context, user_context, variable);
-
-            if let Some(default_value) = &variable.default_value {
-                visit_input_value(visitor, default_value, context, user_context);
-            }
+            context.with_position(variable.position, |context| {
+                visitor.enter_variable_definition(context, user_context, variable);
+
+                if let Some(default_value) = &variable.default_value {
+                    let positions =
+                        context
+                            .source_positions
+                            .as_ref()
+                            .and_then(|source_positions| {
+                                source_positions.default_value(variable.position)
+                            });
+
+                    visit_input_value(
+                        visitor,
+                        default_value,
+                        positions.as_ref(),
+                        context,
+                        user_context,
+                    );
+                }
 
-            // DOTAN: We should visit the directives as well here, but it's extracted in graphql_parser.
+                // DOTAN: We should visit the directives as well here, but it's extracted in graphql_parser.
 
-            visitor.leave_variable_definition(context, user_context, variable);
+                visitor.leave_variable_definition(context, user_context, variable);
+            })
         })
     }
 }
@@ -361,7 +546,26 @@ fn visit_selection<'a, Visitor, UserContext>(
     context: &mut OperationVisitorContext<'a>,
     user_context: &mut UserContext,
 ) where
-    Visitor: OperationVisitor<'a, UserContext>,
+    Visitor: OperationVisitor<'a, UserContext> + ?Sized,
+{
+    let position = match selection {
+        Selection::Field(field) => field.position,
+        Selection::FragmentSpread(fragment_spread) => fragment_spread.position,
+        Selection::InlineFragment(inline_fragment) => inline_fragment.position,
+    };
+
+    context.with_position(position, |context| {
+        visit_selection_node(visitor, selection, context, user_context)
+    });
+}
+
+fn visit_selection_node<'a, Visitor, UserContext>(
+    visitor: &mut Visitor,
+    selection: &'a Selection,
+    context: &mut OperationVisitorContext<'a>,
+    user_context: &mut UserContext,
+) where
+    Visitor: OperationVisitor<'a, UserContext> + ?Sized,
 {
     match selection {
         Selection::Field(field) => {
@@ -440,7 +644,7 @@ fn visit_selection_set<'a, Visitor, UserContext>(
     context: &mut OperationVisitorContext<'a>,
     user_context: &mut UserContext,
 ) where
-    Visitor: OperationVisitor<'a, UserContext>,
+    Visitor: OperationVisitor<'a, UserContext> + ?Sized,
 {
     context.with_parent_type(|context| {
         visitor.enter_selection_set(context, user_context, selection_set);
@@ -459,7 +663,7 @@ fn visit_fragment_definition<'a, Visitor, UserContext>(
     context: &mut OperationVisitorContext<'a>,
     user_context: &mut UserContext,
 ) where
-    Visitor: OperationVisitor<'a, UserContext>,
+    Visitor: OperationVisitor<'a, UserContext> + ?Sized,
 {
     visitor.enter_fragment_definition(context, user_context, fragment);
     visit_directives(visitor, &fragment.directives, context, user_context);
@@ -473,7 +677,7 @@ fn visit_operation_definition<'a, Visitor, UserContext>(
     context: &mut OperationVisitorContext<'a>,
     user_context: &mut UserContext,
 ) where
-    Visitor: OperationVisitor<'a, UserContext>,
+    Visitor: OperationVisitor<'a, UserContext> + ?Sized,
 {
     visitor.enter_operation_definition(context, user_context, operation);
     visit_directives(visitor, operation.directives(), context, user_context);
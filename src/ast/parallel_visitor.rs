@@ -0,0 +1,211 @@
+use std::collections::BTreeMap;
+
+use crate::static_graphql::query::*;
+
+use super::{OperationVisitor, OperationVisitorContext};
+
+/// Runs many `OperationVisitor`s in a single traversal, like `visitInParallel` in graphql-js.
+///
+/// Every callback is forwarded to all visitors, in the order they were added.
+pub struct ParallelVisitor<'a, UserContext> {
+    visitors: Vec<Box<dyn OperationVisitor<'a, UserContext> + 'a>>,
+}
+
+impl<'a, UserContext> Default for ParallelVisitor<'a, UserContext> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a, UserContext> ParallelVisitor<'a, UserContext> {
+    pub fn new() -> Self {
+        Self { visitors: vec![] }
+    }
+
+    pub fn add_visitor(&mut self, visitor: Box<dyn OperationVisitor<'a, UserContext> + 'a>) {
+        self.visitors.push(visitor);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.visitors.is_empty()
+    }
+
+    fn for_each_visitor(
+        &mut self,
+        user_context: &mut UserContext,
+        mut f: impl FnMut(&mut (dyn OperationVisitor<'a, UserContext> + 'a), &mut UserContext),
+    ) {
+        for visitor in &mut self.visitors {
+            f(visitor.as_mut(), user_context);
+        }
+    }
+}
+
+/// Same as `ParallelVisitor`, but every visitor has its own user context, returned by
+/// `into_contexts` after the traversal.
+pub(crate) struct ParallelVisitorWithContexts<'a, UserContext> {
+    visitors: Vec<(Box<dyn OperationVisitor<'a, UserContext> + 'a>, UserContext)>,
+}
+
+impl<'a, UserContext> ParallelVisitorWithContexts<'a, UserContext> {
+    pub fn new() -> Self {
+        Self { visitors: vec![] }
+    }
+
+    pub fn add_visitor(
+        &mut self,
+        visitor: Box<dyn OperationVisitor<'a, UserContext> + 'a>,
+        user_context: UserContext,
+    ) {
+        self.visitors.push((visitor, user_context));
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.visitors.is_empty()
+    }
+
+    /// The user contexts of the visitors, in the order they were added.
+    pub fn into_contexts(self) -> Vec<UserContext> {
+        self.visitors
+            .into_iter()
+            .map(|(_, user_context)| user_context)
+            .collect()
+    }
+
+    fn for_each_visitor(
+        &mut self,
+        _: &mut (),
+        mut f: impl FnMut(&mut (dyn OperationVisitor<'a, UserContext> + 'a), &mut UserContext),
+    ) {
+        for (visitor, user_context) in &mut self.visitors {
+            f(visitor.as_mut(), user_context);
+        }
+    }
+}
+
+macro_rules! forward_to_visitors {
+    ($user_context:ty) => {
+        forward_to_visitors!(
+            $user_context;
+            enter_document: &'a Document,
+            leave_document: &Document,
+            enter_operation_definition: &'a OperationDefinition,
+            leave_operation_definition: &OperationDefinition,
+            enter_fragment_definition: &'a FragmentDefinition,
+            leave_fragment_definition: &FragmentDefinition,
+            enter_variable_definition: &'a VariableDefinition,
+            leave_variable_definition: &VariableDefinition,
+            enter_directive: &Directive,
+            leave_directive: &Directive,
+            enter_selection_set: &'a SelectionSet,
+            leave_selection_set: &SelectionSet,
+            enter_field: &Field,
+            leave_field: &Field,
+            enter_fragment_spread: &'a FragmentSpread,
+            leave_fragment_spread: &FragmentSpread,
+            enter_inline_fragment: &InlineFragment,
+            leave_inline_fragment: &InlineFragment,
+            enter_argument: &'a (String, Value),
+            leave_argument: &(String, Value),
+            enter_null_value: (),
+            leave_null_value: (),
+            enter_scalar_value: &Value,
+            leave_scalar_value: &Value,
+            enter_enum_value: &String,
+            leave_enum_value: &String,
+            enter_variable_value: &'a str,
+            leave_variable_value: &String,
+            enter_list_value: &Vec<Value>,
+            leave_list_value: &Vec<Value>,
+            enter_object_value: &BTreeMap<String, Value>,
+            leave_object_value: &BTreeMap<String, Value>,
+            enter_object_field: &(String, Value),
+            leave_object_field: &(String, Value),
+        );
+    };
+    ($user_context:ty; $($name:ident: $node:ty,)*) => {
+        $(
+            fn $name(
+                &mut self,
+                visitor_context: &mut OperationVisitorContext<'a>,
+                user_context: &mut $user_context,
+                node: $node,
+            ) {
+                self.for_each_visitor(user_context, |visitor, user_context| {
+                    visitor.$name(visitor_context, user_context, node)
+                });
+            }
+        )*
+    };
+}
+
+impl<'a, UserContext> OperationVisitor<'a, UserContext> for ParallelVisitor<'a, UserContext> {
+    forward_to_visitors!(UserContext);
+}
+
+impl<'a, UserContext> OperationVisitor<'a, ()> for ParallelVisitorWithContexts<'a, UserContext> {
+    forward_to_visitors!(());
+}
+
+#[test]
+fn forwards_callbacks_to_all_visitors_in_order() {
+    use super::{visit_document, Schema};
+
+    struct FieldNames(&'static str);
+
+    impl<'a> OperationVisitor<'a, Vec<String>> for FieldNames {
+        fn enter_field(
+            &mut self,
+            _: &mut OperationVisitorContext<'a>,
+            visited: &mut Vec<String>,
+            field: &Field,
+        ) {
+            visited.push(format!("{}: enter {}", self.0, field.name));
+        }
+
+        fn leave_field(
+            &mut self,
+            _: &mut OperationVisitorContext<'a>,
+            visited: &mut Vec<String>,
+            field: &Field,
+        ) {
+            visited.push(format!("{}: leave {}", self.0, field.name));
+        }
+    }
+
+    let schema_document =
+        crate::parser::parse_schema::<String>("type Query { a: A } type A { b: Int }")
+            .unwrap()
+            .into_static();
+    let schema = Schema::new(&schema_document);
+    let operation = crate::parser::parse_query::<String>("{ a { b } }")
+        .unwrap()
+        .into_static();
+    let mut context = OperationVisitorContext::new(&operation, &schema);
+
+    let mut parallel_visitor = ParallelVisitor::new();
+    parallel_visitor.add_visitor(Box::new(FieldNames("first")));
+    parallel_visitor.add_visitor(Box::new(FieldNames("second")));
+
+    let mut visited = vec![];
+    visit_document(
+        &mut parallel_visitor,
+        &operation,
+        &mut context,
+        &mut visited,
+    );
+
+    assert_eq!(
+        visited,
+        vec![
+            "first: enter a",
+            "second: enter a",
+            "first: enter b",
+            "second: enter b",
+            "first: leave b",
+            "second: leave b",
+            "first: leave a",
+            "second: leave a",
+        ]
+    );
+}
@@ -0,0 +1,424 @@
+use crate::parser::query::{
+    Definition, Directive, Document, FragmentDefinition, OperationDefinition, Selection,
+    SelectionSet, Text, TypeCondition, Value, VariableDefinition,
+};
+
+use super::print_schema::print_string;
+
+/// Options of `print_operation`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PrintOperationOptions {
+    /// The indentation of each nested selection set. Defaults to two spaces.
+    pub indent: String,
+    /// Whether to print the document on a single line without ignored characters, like
+    /// `stripIgnoredCharacters` in graphql-js. `indent` is ignored then. Defaults to `false`.
+    pub minify: bool,
+    /// Whether to print field and directive arguments sorted by name, instead of in the order of
+    /// the document. Defaults to `false`.
+    pub sort_arguments: bool,
+}
+
+impl Default for PrintOperationOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PrintOperationOptions {
+    pub fn new() -> Self {
+        Self {
+            indent: "  ".to_string(),
+            minify: false,
+            sort_arguments: false,
+        }
+    }
+}
+
+/// Prints an operation document, like `print` in graphql-js. Anonymous queries are printed with
+/// the shorthand `{ ... }` form.
+pub fn print_operation<'a, T: Text<'a>>(
+    document: &Document<'a, T>,
+    options: &PrintOperationOptions,
+) -> String {
+    let mut printer = Printer::new(options);
+
+    for (index, definition) in document.definitions.iter().enumerate() {
+        if index > 0 && !options.minify {
+            printer.output.push_str("\n\n");
+        }
+
+        match definition {
+            Definition::Operation(operation) => printer.operation(operation),
+            Definition::Fragment(fragment) => printer.fragment(fragment),
+        }
+    }
+
+    printer.output
+}
+
+/// Prints a single selection (and its selection set), see `print_operation`.
+pub(crate) fn print_selection<'a, T: Text<'a>>(
+    selection: &Selection<'a, T>,
+    options: &PrintOperationOptions,
+) -> String {
+    let mut printer = Printer::new(options);
+    printer.selection(selection);
+
+    printer.output
+}
+
+struct Printer<'o> {
+    options: &'o PrintOperationOptions,
+    output: String,
+    depth: usize,
+}
+
+impl<'o> Printer<'o> {
+    fn new(options: &'o PrintOperationOptions) -> Self {
+        Self {
+            options,
+            output: String::new(),
+            depth: 0,
+        }
+    }
+
+    /// Writes a token. When minified, a space is only kept between tokens that would otherwise
+    /// merge, like names and numbers (including negative ones).
+    fn token(&mut self, token: &str) {
+        let is_name_char = |c: char| c == '_' || c.is_ascii_alphanumeric();
+
+        if self.options.minify
+            && self.output.ends_with(is_name_char)
+            && token.starts_with(|c: char| is_name_char(c) || c == '-')
+        {
+            self.output.push(' ');
+        }
+
+        self.output.push_str(token);
+    }
+
+    /// Writes whitespace that is only needed to make the output readable.
+    fn space(&mut self) {
+        if !self.options.minify {
+            self.output.push(' ');
+        }
+    }
+
+    fn separator(&mut self) {
+        if !self.options.minify {
+            self.output.push_str(", ");
+        }
+    }
+
+    fn new_line(&mut self) {
+        if !self.options.minify {
+            self.output.push('\n');
+            for _ in 0..self.depth {
+                self.output.push_str(&self.options.indent);
+            }
+        }
+    }
+
+    fn operation<'a, T: Text<'a>>(&mut self, operation: &OperationDefinition<'a, T>) {
+        let (kind, name, variable_definitions, directives, selection_set) = match operation {
+            OperationDefinition::SelectionSet(selection_set) => {
+                return self.selection_set(selection_set)
+            }
+            OperationDefinition::Query(query) => (
+                "query",
+                &query.name,
+                &query.variable_definitions,
+                &query.directives,
+                &query.selection_set,
+            ),
+            OperationDefinition::Mutation(mutation) => (
+                "mutation",
+                &mutation.name,
+                &mutation.variable_definitions,
+                &mutation.directives,
+                &mutation.selection_set,
+            ),
+            OperationDefinition::Subscription(subscription) => (
+                "subscription",
+                &subscription.name,
+                &subscription.variable_definitions,
+                &subscription.directives,
+                &subscription.selection_set,
+            ),
+        };
+
+        if kind == "query"
+            && name.is_none()
+            && variable_definitions.is_empty()
+            && directives.is_empty()
+        {
+            return self.selection_set(selection_set);
+        }
+
+        self.token(kind);
+        if let Some(name) = name {
+            self.output.push(' ');
+            self.token(name.as_ref());
+        }
+        self.variable_definitions(variable_definitions);
+        self.directives(directives);
+        self.space();
+        self.selection_set(selection_set);
+    }
+
+    fn fragment<'a, T: Text<'a>>(&mut self, fragment: &FragmentDefinition<'a, T>) {
+        let TypeCondition::On(type_name) = &fragment.type_condition;
+
+        self.token("fragment");
+        self.output.push(' ');
+        self.token(fragment.name.as_ref());
+        self.output.push(' ');
+        self.token("on");
+        self.output.push(' ');
+        self.token(type_name.as_ref());
+        self.directives(&fragment.directives);
+        self.space();
+        self.selection_set(&fragment.selection_set);
+    }
+
+    fn variable_definitions<'a, T: Text<'a>>(
+        &mut self,
+        variable_definitions: &[VariableDefinition<'a, T>],
+    ) {
+        if variable_definitions.is_empty() {
+            return;
+        }
+
+        self.token("(");
+        for (index, variable_definition) in variable_definitions.iter().enumerate() {
+            if index > 0 {
+                self.separator();
+            }
+            self.token(&format!("${}", variable_definition.name.as_ref()));
+            self.token(":");
+            self.space();
+            self.token(&variable_definition.var_type.to_string());
+            if let Some(default_value) = &variable_definition.default_value {
+                self.space();
+                self.token("=");
+                self.space();
+                self.value(default_value);
+            }
+        }
+        self.token(")");
+    }
+
+    fn selection_set<'a, T: Text<'a>>(&mut self, selection_set: &SelectionSet<'a, T>) {
+        self.token("{");
+        self.depth += 1;
+        for selection in &selection_set.items {
+            self.new_line();
+            self.selection(selection);
+        }
+        self.depth -= 1;
+        self.new_line();
+        self.token("}");
+    }
+
+    fn selection<'a, T: Text<'a>>(&mut self, selection: &Selection<'a, T>) {
+        match selection {
+            Selection::Field(field) => {
+                if let Some(alias) = &field.alias {
+                    self.token(alias.as_ref());
+                    self.token(":");
+                    self.space();
+                }
+                self.token(field.name.as_ref());
+                self.arguments(&field.arguments);
+                self.directives(&field.directives);
+                if !field.selection_set.items.is_empty() {
+                    self.space();
+                    self.selection_set(&field.selection_set);
+                }
+            }
+            Selection::FragmentSpread(spread) => {
+                self.token("...");
+                self.token(spread.fragment_name.as_ref());
+                self.directives(&spread.directives);
+            }
+            Selection::InlineFragment(inline_fragment) => {
+                self.token("...");
+                if let Some(TypeCondition::On(type_name)) = &inline_fragment.type_condition {
+                    self.space();
+                    self.token("on");
+                    self.output.push(' ');
+                    self.token(type_name.as_ref());
+                }
+                self.directives(&inline_fragment.directives);
+                self.space();
+                self.selection_set(&inline_fragment.selection_set);
+            }
+        }
+    }
+
+    fn directives<'a, T: Text<'a>>(&mut self, directives: &[Directive<'a, T>]) {
+        for directive in directives {
+            self.space();
+            self.token(&format!("@{}", directive.name.as_ref()));
+            self.arguments(&directive.arguments);
+        }
+    }
+
+    fn arguments<'a, T: Text<'a>>(&mut self, arguments: &[(T::Value, Value<'a, T>)]) {
+        if arguments.is_empty() {
+            return;
+        }
+
+        let mut arguments = arguments.iter().collect::<Vec<_>>();
+        if self.options.sort_arguments {
+            arguments.sort_by(|(a, _), (b, _)| a.as_ref().cmp(b.as_ref()));
+        }
+
+        self.token("(");
+        for (index, (name, value)) in arguments.into_iter().enumerate() {
+            if index > 0 {
+                self.separator();
+            }
+            self.token(name.as_ref());
+            self.token(":");
+            self.space();
+            self.value(value);
+        }
+        self.token(")");
+    }
+
+    fn value<'a, T: Text<'a>>(&mut self, value: &Value<'a, T>) {
+        match value {
+            Value::Variable(name) => self.token(&format!("${}", name.as_ref())),
+            Value::Int(number) => self.token(&number.as_i64().unwrap_or_default().to_string()),
+            // `Debug` keeps the decimal point of round floats
+            Value::Float(number) => self.token(&format!("{:?}", number)),
+            Value::String(string) => self.token(&print_string(string)),
+            Value::Boolean(boolean) => self.token(if *boolean { "true" } else { "false" }),
+            Value::Null => self.token("null"),
+            Value::Enum(name) => self.token(name.as_ref()),
+            Value::List(items) => {
+                self.token("[");
+                for (index, item) in items.iter().enumerate() {
+                    if index > 0 {
+                        self.separator();
+                    }
+                    self.value(item);
+                }
+                self.token("]");
+            }
+            Value::Object(fields) => {
+                self.token("{");
+                for (index, (name, value)) in fields.iter().enumerate() {
+                    if index > 0 {
+                        self.separator();
+                    }
+                    self.token(name.as_ref());
+                    self.token(":");
+                    self.space();
+                    self.value(value);
+                }
+                self.token("}");
+            }
+        }
+    }
+}
+
+#[test]
+fn prints_operations() {
+    let document = crate::parser::parse_query::<&str>(
+        "
+        query Users($first: Int = 10, $filter: Filter) @live {
+          users(first: $first, filter: $filter, order: { by: NAME, desc: true }) {
+            id
+            fullName: name @include(if: true)
+            ...UserFields
+            ... on Admin { level(scale: 1.0, tags: [\"a\", \"b\"]) }
+            ... @skip(if: false) { email }
+          }
+        }
+
+        { me { id } }
+
+        fragment UserFields on User @cached { avatar(size: 64, round: true) }
+        ",
+    )
+    .expect("Failed to parse query");
+
+    assert_eq!(
+        print_operation(&document, &PrintOperationOptions::new()),
+        r#"query Users($first: Int = 10, $filter: Filter) @live {
+  users(first: $first, filter: $filter, order: {by: NAME, desc: true}) {
+    id
+    fullName: name @include(if: true)
+    ...UserFields
+    ... on Admin {
+      level(scale: 1.0, tags: ["a", "b"])
+    }
+    ... @skip(if: false) {
+      email
+    }
+  }
+}
+
+{
+  me {
+    id
+  }
+}
+
+fragment UserFields on User @cached {
+  avatar(size: 64, round: true)
+}"#
+    );
+
+    assert_eq!(
+        print_operation(
+            &document,
+            &PrintOperationOptions {
+                indent: "\t".to_string(),
+                minify: false,
+                sort_arguments: true,
+            }
+        )
+        .lines()
+        .nth(1),
+        Some("\tusers(filter: $filter, first: $first, order: {by: NAME, desc: true}) {")
+    );
+
+    assert_eq!(
+        print_operation(
+            &document,
+            &PrintOperationOptions {
+                minify: true,
+                ..PrintOperationOptions::new()
+            }
+        ),
+        r#"query Users($first:Int=10$filter:Filter)@live{users(first:$first filter:$filter order:{by:NAME desc:true}){id fullName:name@include(if:true)...UserFields...on Admin{level(scale:1.0 tags:["a""b"])}...@skip(if:false){email}}}{me{id}}fragment UserFields on User@cached{avatar(size:64 round:true)}"#
+    );
+}
+
+#[test]
+fn printed_operation_can_be_parsed_again() {
+    let document = crate::parser::parse_query::<String>(
+        "
+        mutation Update($input: UpdateInput! = { name: \"a \\\"b\\\"\", ids: [1, -2] }) {
+          update(input: $input) { __typename id ... on Node @include(if: true) { id } }
+        }
+        subscription { events(after: -1.5e3) { type } }
+        ",
+    )
+    .expect("Failed to parse query");
+
+    for minify in [false, true] {
+        let options = PrintOperationOptions {
+            minify,
+            ..PrintOperationOptions::new()
+        };
+        let printed = print_operation(&document, &options);
+        let reparsed =
+            crate::parser::parse_query::<String>(&printed).expect("Failed to parse printed query");
+
+        assert_eq!(print_operation(&reparsed, &options), printed);
+    }
+}
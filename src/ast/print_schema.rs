@@ -0,0 +1,752 @@
+use crate::ast::{TypeDefinitionExtension, TypeExtensionHelpers};
+use crate::introspection::{is_built_in_directive, is_built_in_scalar, is_introspection_type};
+use crate::static_graphql::schema::{
+    Definition, Directive, DirectiveDefinition, Document, EnumValue, Field, InputValue,
+    SchemaDefinition, TypeDefinition, TypeExtension, Value,
+};
+
+/// Options of `print_schema`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PrintSchemaOptions {
+    /// Whether to leave out the built-in scalars, the built-in directives and the introspection
+    /// types, like `printSchema` in graphql-js does. Defaults to `false`.
+    pub omit_built_ins: bool,
+    /// Whether to sort definitions, fields, arguments, enum values, interfaces, union members and
+    /// directive locations by name, so the output doesn't depend on the order of the document.
+    /// Definitions are printed schema first, then directives, types and type extensions.
+    /// Defaults to `false`.
+    pub sort: bool,
+}
+
+impl Default for PrintSchemaOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PrintSchemaOptions {
+    pub fn new() -> Self {
+        Self {
+            omit_built_ins: false,
+            sort: false,
+        }
+    }
+}
+
+/// Prints a schema document as SDL, in the format of `printSchema` in graphql-js: descriptions
+/// are printed as block strings (when they can be), and directives and default values are kept.
+///
+/// Works with documents built from introspection (`build_client_schema`) as well.
+pub fn print_schema(document: &Document, options: &PrintSchemaOptions) -> String {
+    let mut definitions = document
+        .definitions
+        .iter()
+        .filter(|definition| !(options.omit_built_ins && is_built_in(definition)))
+        .collect::<Vec<_>>();
+
+    if options.sort {
+        definitions.sort_by_key(|definition| definition_sort_key(definition));
+    }
+
+    definitions
+        .into_iter()
+        .map(|definition| print_definition(definition, options.sort))
+        .collect::<Vec<_>>()
+        .join("\n\n")
+        + "\n"
+}
+
+fn is_built_in(definition: &Definition) -> bool {
+    match definition {
+        Definition::TypeDefinition(TypeDefinition::Scalar(scalar)) => {
+            is_built_in_scalar(&scalar.name)
+        }
+        Definition::TypeDefinition(type_definition) => {
+            is_introspection_type(type_definition.name())
+        }
+        Definition::DirectiveDefinition(directive) => is_built_in_directive(&directive.name),
+        _ => false,
+    }
+}
+
+fn definition_sort_key(definition: &Definition) -> (u8, &str) {
+    match definition {
+        Definition::SchemaDefinition(_) => (0, ""),
+        Definition::DirectiveDefinition(directive) => (1, &directive.name),
+        Definition::TypeDefinition(type_definition) => (2, type_definition.name()),
+        Definition::TypeExtension(type_extension) => (3, type_extension.name()),
+    }
+}
+
+fn print_definition(definition: &Definition, sort: bool) -> String {
+    match definition {
+        Definition::SchemaDefinition(schema_definition) => {
+            print_schema_definition(schema_definition)
+        }
+        Definition::TypeDefinition(type_definition) => print_type_definition(type_definition, sort),
+        Definition::TypeExtension(type_extension) => print_type_extension(type_extension, sort),
+        Definition::DirectiveDefinition(directive) => print_directive_definition(directive, sort),
+    }
+}
+
+fn print_schema_definition(schema_definition: &SchemaDefinition) -> String {
+    let operation_types = [
+        ("query", &schema_definition.query),
+        ("mutation", &schema_definition.mutation),
+        ("subscription", &schema_definition.subscription),
+    ]
+    .iter()
+    .filter_map(|(operation, type_name)| {
+        type_name
+            .as_ref()
+            .map(|type_name| format!("  {}: {}", operation, type_name))
+    })
+    .collect::<Vec<_>>();
+
+    format!(
+        "schema{}{}",
+        print_directives(&schema_definition.directives),
+        print_block(operation_types)
+    )
+}
+
+fn print_type_definition(type_definition: &TypeDefinition, sort: bool) -> String {
+    match type_definition {
+        TypeDefinition::Scalar(scalar) => format!(
+            "{}scalar {}{}",
+            print_description(&scalar.description, "", true),
+            scalar.name,
+            print_directives(&scalar.directives)
+        ),
+        TypeDefinition::Object(object) => format!(
+            "{}type {}{}{}{}",
+            print_description(&object.description, "", true),
+            object.name,
+            print_implements(&object.implements_interfaces, sort),
+            print_directives(&object.directives),
+            print_fields(&object.fields, sort)
+        ),
+        TypeDefinition::Interface(interface) => format!(
+            "{}interface {}{}{}{}",
+            print_description(&interface.description, "", true),
+            interface.name,
+            print_implements(&interface.implements_interfaces, sort),
+            print_directives(&interface.directives),
+            print_fields(&interface.fields, sort)
+        ),
+        TypeDefinition::Union(union) => format!(
+            "{}union {}{}{}",
+            print_description(&union.description, "", true),
+            union.name,
+            print_directives(&union.directives),
+            print_union_members(&union.types, sort)
+        ),
+        TypeDefinition::Enum(enum_type) => format!(
+            "{}enum {}{}{}",
+            print_description(&enum_type.description, "", true),
+            enum_type.name,
+            print_directives(&enum_type.directives),
+            print_enum_values(&enum_type.values, sort)
+        ),
+        TypeDefinition::InputObject(input_object) => format!(
+            "{}input {}{}{}",
+            print_description(&input_object.description, "", true),
+            input_object.name,
+            print_directives(&input_object.directives),
+            print_input_fields(&input_object.fields, sort)
+        ),
+    }
+}
+
+fn print_type_extension(type_extension: &TypeExtension, sort: bool) -> String {
+    match type_extension {
+        TypeExtension::Scalar(scalar) => format!(
+            "extend scalar {}{}",
+            scalar.name,
+            print_directives(&scalar.directives)
+        ),
+        TypeExtension::Object(object) => format!(
+            "extend type {}{}{}{}",
+            object.name,
+            print_implements(&object.implements_interfaces, sort),
+            print_directives(&object.directives),
+            print_fields(&object.fields, sort)
+        ),
+        TypeExtension::Interface(interface) => format!(
+            "extend interface {}{}{}{}",
+            interface.name,
+            print_implements(&interface.implements_interfaces, sort),
+            print_directives(&interface.directives),
+            print_fields(&interface.fields, sort)
+        ),
+        TypeExtension::Union(union) => format!(
+            "extend union {}{}{}",
+            union.name,
+            print_directives(&union.directives),
+            print_union_members(&union.types, sort)
+        ),
+        TypeExtension::Enum(enum_type) => format!(
+            "extend enum {}{}{}",
+            enum_type.name,
+            print_directives(&enum_type.directives),
+            print_enum_values(&enum_type.values, sort)
+        ),
+        TypeExtension::InputObject(input_object) => format!(
+            "extend input {}{}{}",
+            input_object.name,
+            print_directives(&input_object.directives),
+            print_input_fields(&input_object.fields, sort)
+        ),
+    }
+}
+
+fn print_directive_definition(directive: &DirectiveDefinition, sort: bool) -> String {
+    let mut locations = directive
+        .locations
+        .iter()
+        .map(|location| location.as_str())
+        .collect::<Vec<_>>();
+
+    if sort {
+        locations.sort_unstable();
+    }
+
+    format!(
+        "{}directive @{}{}{} on {}",
+        print_description(&directive.description, "", true),
+        directive.name,
+        print_args(&directive.arguments, "", sort),
+        if directive.repeatable {
+            " repeatable"
+        } else {
+            ""
+        },
+        locations.join(" | ")
+    )
+}
+
+fn print_implements(interfaces: &[String], sort: bool) -> String {
+    if interfaces.is_empty() {
+        return String::new();
+    }
+
+    let interfaces = sorted(interfaces, sort, |interface| interface);
+
+    format!(
+        " implements {}",
+        interfaces
+            .iter()
+            .map(|interface| interface.as_str())
+            .collect::<Vec<_>>()
+            .join(" & ")
+    )
+}
+
+fn print_union_members(types: &[String], sort: bool) -> String {
+    if types.is_empty() {
+        return String::new();
+    }
+
+    let types = sorted(types, sort, |member| member);
+
+    format!(
+        " = {}",
+        types
+            .iter()
+            .map(|member| member.as_str())
+            .collect::<Vec<_>>()
+            .join(" | ")
+    )
+}
+
+fn print_fields(fields: &[Field], sort: bool) -> String {
+    print_block(
+        sorted(fields, sort, |field| &field.name)
+            .into_iter()
+            .enumerate()
+            .map(|(index, field)| {
+                format!(
+                    "{}  {}{}: {}{}",
+                    print_description(&field.description, "  ", index == 0),
+                    field.name,
+                    print_args(&field.arguments, "  ", sort),
+                    field.field_type,
+                    print_directives(&field.directives)
+                )
+            })
+            .collect(),
+    )
+}
+
+fn print_input_fields(fields: &[InputValue], sort: bool) -> String {
+    print_block(
+        sorted(fields, sort, |field| &field.name)
+            .into_iter()
+            .enumerate()
+            .map(|(index, field)| {
+                format!(
+                    "{}  {}",
+                    print_description(&field.description, "  ", index == 0),
+                    print_input_value(field)
+                )
+            })
+            .collect(),
+    )
+}
+
+fn print_enum_values(values: &[EnumValue], sort: bool) -> String {
+    print_block(
+        sorted(values, sort, |value| &value.name)
+            .into_iter()
+            .enumerate()
+            .map(|(index, value)| {
+                format!(
+                    "{}  {}{}",
+                    print_description(&value.description, "  ", index == 0),
+                    value.name,
+                    print_directives(&value.directives)
+                )
+            })
+            .collect(),
+    )
+}
+
+/// Arguments are printed on one line, unless one of them has a description.
+fn print_args(args: &[InputValue], indentation: &str, sort: bool) -> String {
+    if args.is_empty() {
+        return String::new();
+    }
+
+    let args = sorted(args, sort, |arg| &arg.name);
+
+    if args.iter().all(|arg| arg.description.is_none()) {
+        return format!(
+            "({})",
+            args.into_iter()
+                .map(print_input_value)
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+    }
+
+    let args = args
+        .into_iter()
+        .enumerate()
+        .map(|(index, arg)| {
+            format!(
+                "{}  {}{}",
+                print_description(&arg.description, &format!("  {}", indentation), index == 0),
+                indentation,
+                print_input_value(arg)
+            )
+        })
+        .collect::<Vec<_>>();
+
+    format!("(\n{}\n{})", args.join("\n"), indentation)
+}
+
+fn print_input_value(input_value: &InputValue) -> String {
+    let default_value = match &input_value.default_value {
+        Some(value) => format!(" = {}", print_value(value)),
+        None => String::new(),
+    };
+
+    format!(
+        "{}: {}{}{}",
+        input_value.name,
+        input_value.value_type,
+        default_value,
+        print_directives(&input_value.directives)
+    )
+}
+
+fn print_directives(directives: &[Directive]) -> String {
+    directives
+        .iter()
+        .map(|directive| {
+            let arguments = if directive.arguments.is_empty() {
+                String::new()
+            } else {
+                format!(
+                    "({})",
+                    directive
+                        .arguments
+                        .iter()
+                        .map(|(name, value)| format!("{}: {}", name, print_value(value)))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                )
+            };
+
+            format!(" @{}{}", directive.name, arguments)
+        })
+        .collect()
+}
+
+/// Same as the `Display` of `Value`, except strings are always quoted: the parser prints the ones
+/// with line breaks as block strings.
+pub(crate) fn print_value(value: &Value) -> String {
+    match value {
+        Value::String(string) => print_string(string),
+        Value::List(items) => format!(
+            "[{}]",
+            items.iter().map(print_value).collect::<Vec<_>>().join(", ")
+        ),
+        Value::Object(fields) => format!(
+            "{{{}}}",
+            fields
+                .iter()
+                .map(|(name, value)| format!("{}: {}", name, print_value(value)))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        _ => value.to_string(),
+    }
+}
+
+fn print_block(lines: Vec<String>) -> String {
+    if lines.is_empty() {
+        return String::new();
+    }
+
+    format!(" {{\n{}\n}}", lines.join("\n"))
+}
+
+fn print_description(
+    description: &Option<String>,
+    indentation: &str,
+    first_in_block: bool,
+) -> String {
+    let description = match description {
+        // graphql-parser keeps the line break that ends a block string
+        Some(description) => description.trim_end_matches('\n'),
+        None => return String::new(),
+    };
+
+    // graphql-parser also trims the first line of block strings, which is the whole description
+    // when it's printed on one line
+    let is_trimmed = description.contains('\n') || description.trim() == description;
+
+    let printed = if is_trimmed && is_printable_as_block_string(description) {
+        print_block_string(description)
+    } else {
+        print_string(description)
+    };
+
+    // Descriptions in a block are separated from the previous item by an empty line
+    let prefix = if !indentation.is_empty() && !first_in_block {
+        format!("\n{}", indentation)
+    } else {
+        indentation.to_string()
+    };
+
+    format!(
+        "{}{}\n",
+        prefix,
+        printed.replace('\n', &format!("\n{}", indentation))
+    )
+}
+
+pub(crate) fn print_string(string: &str) -> String {
+    let mut printed = String::with_capacity(string.len() + 2);
+    printed.push('"');
+
+    for c in string.chars() {
+        match c {
+            '"' => printed.push_str("\\\""),
+            '\\' => printed.push_str("\\\\"),
+            '\n' => printed.push_str("\\n"),
+            '\r' => printed.push_str("\\r"),
+            '\t' => printed.push_str("\\t"),
+            '\u{0008}' => printed.push_str("\\b"),
+            '\u{000C}' => printed.push_str("\\f"),
+            c if c < '\u{0020}' || c == '\u{007F}' => {
+                printed.push_str(&format!("\\u{:04X}", c as u32))
+            }
+            c => printed.push(c),
+        }
+    }
+
+    printed.push('"');
+    printed
+}
+
+/// Whether the block string of `value` parses back to `value`: it can't have leading or
+/// trailing empty lines, indentation common to all its lines, or non-printable characters.
+fn is_printable_as_block_string(value: &str) -> bool {
+    if value.is_empty() {
+        return true;
+    }
+
+    let mut is_empty_line = true;
+    let mut has_indent = false;
+    let mut has_common_indent = true;
+    let mut seen_non_empty_line = false;
+
+    for c in value.chars() {
+        match c {
+            '\n' => {
+                if is_empty_line && !seen_non_empty_line {
+                    return false;
+                }
+                seen_non_empty_line = true;
+                is_empty_line = true;
+                has_indent = false;
+            }
+            '\t' | ' ' => has_indent = has_indent || is_empty_line,
+            '\r' => return false,
+            c if c < '\u{0020}' => return false,
+            _ => {
+                has_common_indent = has_common_indent && has_indent;
+                is_empty_line = false;
+            }
+        }
+    }
+
+    !(is_empty_line || has_common_indent && seen_non_empty_line)
+}
+
+fn print_block_string(value: &str) -> String {
+    let escaped = value.replace("\"\"\"", "\\\"\"\"");
+    let lines = escaped.lines().collect::<Vec<_>>();
+    let is_single_line = lines.len() == 1;
+    let force_leading_new_line = lines.len() > 1
+        && lines[1..]
+            .iter()
+            .all(|line| line.is_empty() || line.starts_with([' ', '\t']));
+    let has_trailing_triple_quotes = escaped.ends_with("\\\"\"\"");
+    let force_trailing_new_line =
+        (value.ends_with('"') && !has_trailing_triple_quotes) || value.ends_with('\\');
+    let print_as_multiple_lines = !is_single_line
+        || value.len() > 70
+        || force_trailing_new_line
+        || force_leading_new_line
+        || has_trailing_triple_quotes;
+    let skip_leading_new_line = is_single_line && value.starts_with([' ', '\t']);
+
+    let mut printed = String::from("\"\"\"");
+    if (print_as_multiple_lines && !skip_leading_new_line) || force_leading_new_line {
+        printed.push('\n');
+    }
+    printed.push_str(&escaped);
+    if print_as_multiple_lines || force_trailing_new_line {
+        printed.push('\n');
+    }
+    printed.push_str("\"\"\"");
+    printed
+}
+
+fn sorted<T>(items: &[T], sort: bool, name: impl Fn(&T) -> &String) -> Vec<&T> {
+    let mut items = items.iter().collect::<Vec<_>>();
+
+    if sort {
+        items.sort_by(|a, b| name(a).cmp(name(b)));
+    }
+
+    items
+}
+
+#[cfg(test)]
+fn parse_test_schema(sdl: &str) -> Document {
+    crate::parser::parse_schema::<String>(sdl)
+        .expect("Failed to parse schema")
+        .into_static()
+}
+
+#[test]
+fn prints_schema_document() {
+    let document = parse_test_schema(
+        r#"
+        schema { query: Query }
+        "Root of queries"
+        type Query implements Node @key(fields: "id") {
+          id: ID!
+          "Search\nwith a filter"
+          search(
+            "What to look for"
+            filter: Filter = {name: "a", tags: ["x"]}
+            first: Int = 10
+          ): [Result!] @deprecated(reason: "Use \"find\"")
+        }
+        interface Node { id: ID! }
+        union Result = Query | User
+        type User { name(format: Format = SHORT): String }
+        enum Format { "The short one" SHORT LONG @deprecated }
+        input Filter { name: String tags: [String!] }
+        scalar Date @specifiedBy(url: "https://tools.ietf.org/html/rfc3339")
+        directive @key(fields: String!) repeatable on OBJECT | INTERFACE
+        extend type User @key(fields: "name")
+        "#,
+    );
+
+    assert_eq!(
+        print_schema(&document, &PrintSchemaOptions::default()),
+        r#"schema {
+  query: Query
+}
+
+"""Root of queries"""
+type Query implements Node @key(fields: "id") {
+  id: ID!
+
+  """
+  Search
+  with a filter
+  """
+  search(
+    """What to look for"""
+    filter: Filter = {name: "a", tags: ["x"]}
+    first: Int = 10
+  ): [Result!] @deprecated(reason: "Use \"find\"")
+}
+
+interface Node {
+  id: ID!
+}
+
+union Result = Query | User
+
+type User {
+  name(format: Format = SHORT): String
+}
+
+enum Format {
+  """The short one"""
+  SHORT
+  LONG @deprecated
+}
+
+input Filter {
+  name: String
+  tags: [String!]
+}
+
+scalar Date @specifiedBy(url: "https://tools.ietf.org/html/rfc3339")
+
+directive @key(fields: String!) repeatable on OBJECT | INTERFACE
+
+extend type User @key(fields: "name")
+"#
+    );
+}
+
+#[test]
+fn sorts_schema_document() {
+    let options = PrintSchemaOptions {
+        sort: true,
+        ..PrintSchemaOptions::default()
+    };
+    let document = parse_test_schema(
+        "
+        type Query { b(y: Int, x: Int): B a: String }
+        union B = Z | Y
+        type Z implements J & I { id: ID }
+        type Y { id: ID }
+        directive @d on UNION | OBJECT
+        enum E { B A }
+        schema { query: Query }
+        ",
+    );
+    let shuffled = parse_test_schema(
+        "
+        enum E { A B }
+        schema { query: Query }
+        directive @d on OBJECT | UNION
+        type Y { id: ID }
+        type Z implements I & J { id: ID }
+        type Query { a: String b(x: Int, y: Int): B }
+        union B = Y | Z
+        ",
+    );
+
+    let printed = print_schema(&document, &options);
+
+    assert_eq!(printed, print_schema(&shuffled, &options));
+    assert_eq!(
+        printed,
+        "schema {
+  query: Query
+}
+
+directive @d on OBJECT | UNION
+
+union B = Y | Z
+
+enum E {
+  A
+  B
+}
+
+type Query {
+  a: String
+  b(x: Int, y: Int): B
+}
+
+type Y {
+  id: ID
+}
+
+type Z implements I & J {
+  id: ID
+}
+"
+    );
+}
+
+#[test]
+fn prints_introspection_schema_without_built_ins() {
+    use crate::introspection::{build_client_schema, parse_introspection};
+
+    let options = PrintSchemaOptions {
+        omit_built_ins: true,
+        sort: true,
+    };
+
+    for file in [
+        "github_introspection.json",
+        "shopify_introspection.json",
+        "product_introspection.json",
+    ] {
+        let json_file = std::fs::File::open(format!("./src/introspection/test_files/{}", file))
+            .expect("failed to open json file");
+        let introspection = parse_introspection(json_file).expect("failed to parse json");
+        let schema = build_client_schema(&introspection).expect("failed to build schema");
+
+        let printed = print_schema(&schema, &options);
+
+        assert!(!printed.contains("type __Schema"));
+        assert!(!printed.contains("scalar String"));
+        assert!(!printed.contains("directive @skip"));
+        // Printing is stable when the printed schema is parsed back
+        assert_eq!(
+            print_schema(&parse_test_schema(&printed), &options),
+            printed
+        );
+    }
+}
+
+#[test]
+fn prints_strings_that_cannot_be_block_strings() {
+    let mut document = parse_test_schema("scalar A scalar B scalar C");
+
+    for (definition, description) in document.definitions.iter_mut().zip([
+        "  indented\n  lines",
+        "ends with a quote\"",
+        "contains \"\"\" and \u{0007}",
+    ]) {
+        if let Definition::TypeDefinition(TypeDefinition::Scalar(scalar)) = definition {
+            scalar.description = Some(description.to_string());
+        }
+    }
+
+    let printed = print_schema(&document, &PrintSchemaOptions::default());
+
+    assert_eq!(
+        printed,
+        "\"  indented\\n  lines\"\nscalar A\n\n\"\"\"\nends with a quote\"\n\"\"\"\nscalar B\n\n\"contains \\\"\\\"\\\" and \\u0007\"\nscalar C\n"
+    );
+    assert_eq!(
+        print_schema(&parse_test_schema(&printed), &PrintSchemaOptions::default()),
+        printed
+    );
+}
@@ -0,0 +1,108 @@
+use std::marker::PhantomData;
+
+use serde_json::{Map, Value as JsonValue};
+
+use crate::parser::query::{Document, Selection, SelectionSet, Text};
+
+use super::{should_include_selection, OperationTransformer, TransformedValue};
+
+/// Removes the fields, fragment spreads and inline fragments excluded by `@skip` or `@include`
+/// with the given variable values (see `should_include_selection`). The directives of the
+/// remaining selections are kept.
+///
+/// A field whose selections are all skipped is left with an empty selection set, which is not
+/// valid in an operation.
+pub struct SkippedSelectionsPruner<'v, 'a, T: Text<'a>> {
+    variables: &'v Map<String, JsonValue>,
+    /// Ties the pruner to the `Text` of the documents it transforms.
+    text: PhantomData<Document<'a, T>>,
+}
+
+impl<'v, 'a, T: Text<'a>> SkippedSelectionsPruner<'v, 'a, T> {
+    pub fn new(variables: &'v Map<String, JsonValue>) -> Self {
+        Self {
+            variables,
+            text: PhantomData,
+        }
+    }
+}
+
+impl<'v, 'a, T: Text<'a> + Clone> OperationTransformer<'a, T>
+    for SkippedSelectionsPruner<'v, 'a, T>
+{
+    fn transform_selection_set(
+        &mut self,
+        selections: &SelectionSet<'a, T>,
+    ) -> TransformedValue<Vec<Selection<'a, T>>> {
+        let included = selections
+            .items
+            .iter()
+            .filter(|selection| {
+                let directives = match selection {
+                    Selection::Field(field) => &field.directives,
+                    Selection::FragmentSpread(spread) => &spread.directives,
+                    Selection::InlineFragment(inline_fragment) => &inline_fragment.directives,
+                };
+
+                should_include_selection(directives, self.variables)
+            })
+            .cloned()
+            .collect::<Vec<_>>();
+
+        if included.len() == selections.items.len() {
+            return self.transform_list(&selections.items, Self::transform_selection);
+        }
+
+        let transformed = self.transform_list(&included, Self::transform_selection);
+
+        TransformedValue::Replace(transformed.replace_or_else(|| included))
+    }
+}
+
+/// Returns `document` without the selections skipped with `variables`, see
+/// `SkippedSelectionsPruner`.
+pub fn prune_skipped_selections<'a, T: Text<'a> + Clone>(
+    document: &Document<'a, T>,
+    variables: &Map<String, JsonValue>,
+) -> Document<'a, T> {
+    SkippedSelectionsPruner::new(variables)
+        .transform_document(document)
+        .replace_or_else(|| document.clone())
+}
+
+#[test]
+fn prunes_skipped_selections() {
+    let document = crate::parser::parse_query::<String>(
+        "
+        query($skipName: Boolean!, $withPosts: Boolean!) {
+          me {
+            id
+            name @skip(if: $skipName)
+            ...Posts @include(if: $withPosts)
+            ... @include(if: false) { email }
+          }
+          other @skip(if: true) { id }
+        }
+
+        fragment Posts on User {
+          posts { title @include(if: $withPosts) body @skip(if: $withPosts) }
+        }
+        ",
+    )
+    .expect("Failed to parse query");
+    let variables = serde_json::json!({ "skipName": true, "withPosts": true })
+        .as_object()
+        .cloned()
+        .unwrap();
+
+    assert_eq!(
+        crate::ast::print_operation(
+            &prune_skipped_selections(&document, &variables),
+            &crate::ast::PrintOperationOptions {
+                minify: true,
+                ..crate::ast::PrintOperationOptions::new()
+            }
+        ),
+        "query($skipName:Boolean!$withPosts:Boolean!){me{id...Posts@include(if:$withPosts)}}fragment Posts on User{posts{title@include(if:$withPosts)}}"
+    );
+}
@@ -0,0 +1,580 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::ast::{SchemaDocumentExtension, TypeDefinitionExtension};
+use crate::introspection::BUILT_INS;
+use crate::static_graphql::schema::{
+    DirectiveDefinition, Document, EnumValue, Field, InputValue, TypeDefinition,
+};
+
+/// A schema coordinate, identifying a type, a field, an input field, an enum value, an argument
+/// or a directive of a schema (https://spec.graphql.org/draft/#sec-Schema-Coordinates).
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum SchemaCoordinate {
+    /// `Type`
+    Type { name: String },
+    /// `Type.field`, `Input.field` or `Enum.VALUE`
+    Member {
+        type_name: String,
+        member_name: String,
+    },
+    /// `Type.field(argument:)`
+    Argument {
+        type_name: String,
+        field_name: String,
+        argument_name: String,
+    },
+    /// `@directive`
+    Directive { directive_name: String },
+    /// `@directive(argument:)`
+    DirectiveArgument {
+        directive_name: String,
+        argument_name: String,
+    },
+}
+
+/// The definition a `SchemaCoordinate` refers to in a schema.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SchemaCoordinateTarget<'a> {
+    Type(&'a TypeDefinition),
+    Field(&'a Field),
+    InputField(&'a InputValue),
+    EnumValue(&'a EnumValue),
+    Argument(&'a InputValue),
+    Directive(&'a DirectiveDefinition),
+    DirectiveArgument(&'a InputValue),
+}
+
+/// A schema coordinate that can't be parsed, or that doesn't exist in a schema.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SchemaCoordinateError {
+    pub message: String,
+}
+
+impl SchemaCoordinateError {
+    fn new(message: String) -> Self {
+        Self { message }
+    }
+}
+
+impl fmt::Display for SchemaCoordinateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl std::error::Error for SchemaCoordinateError {}
+
+impl SchemaCoordinate {
+    /// Parses a schema coordinate. Like the spec requires, it can't contain whitespace or
+    /// comments.
+    pub fn parse(source: &str) -> Result<Self, SchemaCoordinateError> {
+        let mut parser = CoordinateParser {
+            source,
+            position: 0,
+        };
+
+        let coordinate = if parser.eat('@') {
+            let directive_name = parser.name()?;
+
+            match parser.argument()? {
+                Some(argument_name) => SchemaCoordinate::DirectiveArgument {
+                    directive_name,
+                    argument_name,
+                },
+                None => SchemaCoordinate::Directive { directive_name },
+            }
+        } else {
+            let type_name = parser.name()?;
+
+            if parser.eat('.') {
+                let member_name = parser.name()?;
+
+                match parser.argument()? {
+                    Some(argument_name) => SchemaCoordinate::Argument {
+                        type_name,
+                        field_name: member_name,
+                        argument_name,
+                    },
+                    None => SchemaCoordinate::Member {
+                        type_name,
+                        member_name,
+                    },
+                }
+            } else {
+                SchemaCoordinate::Type { name: type_name }
+            }
+        };
+
+        if parser.position < source.len() {
+            return Err(parser.error("the end of the coordinate"));
+        }
+
+        Ok(coordinate)
+    }
+
+    /// Returns the definition this coordinate refers to in `schema`, or an error when the type,
+    /// member, argument or directive doesn't exist.
+    ///
+    /// Member coordinates refer to fields of object and interface types, input fields of input
+    /// object types and values of enum types. Members added by type extensions are only found
+    /// once the extensions are merged (see `merge_type_extensions`).
+    ///
+    /// Built-in scalars, directives and introspection types resolve to their built-in
+    /// definition when `schema` doesn't define them.
+    pub fn resolve<'a>(
+        &self,
+        schema: &'a Document,
+    ) -> Result<SchemaCoordinateTarget<'a>, SchemaCoordinateError> {
+        match self {
+            SchemaCoordinate::Type { name } => {
+                resolve_type(schema, name).map(SchemaCoordinateTarget::Type)
+            }
+            SchemaCoordinate::Member {
+                type_name,
+                member_name,
+            } => {
+                let type_def = resolve_type(schema, type_name)?;
+
+                match type_def {
+                    TypeDefinition::Object(_) | TypeDefinition::Interface(_) => {
+                        resolve_field(type_def, member_name).map(SchemaCoordinateTarget::Field)
+                    }
+                    TypeDefinition::InputObject(input_object) => input_object
+                        .fields
+                        .iter()
+                        .find(|field| &field.name == member_name)
+                        .map(SchemaCoordinateTarget::InputField)
+                        .ok_or_else(|| {
+                            SchemaCoordinateError::new(format!(
+                                "Input object \"{}\" has no field \"{}\".",
+                                type_name, member_name
+                            ))
+                        }),
+                    TypeDefinition::Enum(enum_type) => enum_type
+                        .values
+                        .iter()
+                        .find(|value| &value.name == member_name)
+                        .map(SchemaCoordinateTarget::EnumValue)
+                        .ok_or_else(|| {
+                            SchemaCoordinateError::new(format!(
+                                "Enum \"{}\" has no value \"{}\".",
+                                type_name, member_name
+                            ))
+                        }),
+                    _ => Err(SchemaCoordinateError::new(format!(
+                        "Expected \"{}\" to be an enum, input object, object or interface type.",
+                        type_name
+                    ))),
+                }
+            }
+            SchemaCoordinate::Argument {
+                type_name,
+                field_name,
+                argument_name,
+            } => {
+                let type_def = resolve_type(schema, type_name)?;
+
+                if !matches!(
+                    type_def,
+                    TypeDefinition::Object(_) | TypeDefinition::Interface(_)
+                ) {
+                    return Err(SchemaCoordinateError::new(format!(
+                        "Expected \"{}\" to be an object or interface type.",
+                        type_name
+                    )));
+                }
+
+                resolve_field(type_def, field_name)?
+                    .arguments
+                    .iter()
+                    .find(|argument| &argument.name == argument_name)
+                    .map(SchemaCoordinateTarget::Argument)
+                    .ok_or_else(|| {
+                        SchemaCoordinateError::new(format!(
+                            "Field \"{}.{}\" has no argument \"{}\".",
+                            type_name, field_name, argument_name
+                        ))
+                    })
+            }
+            SchemaCoordinate::Directive { directive_name } => {
+                resolve_directive(schema, directive_name).map(SchemaCoordinateTarget::Directive)
+            }
+            SchemaCoordinate::DirectiveArgument {
+                directive_name,
+                argument_name,
+            } => resolve_directive(schema, directive_name)?
+                .arguments
+                .iter()
+                .find(|argument| &argument.name == argument_name)
+                .map(SchemaCoordinateTarget::DirectiveArgument)
+                .ok_or_else(|| {
+                    SchemaCoordinateError::new(format!(
+                        "Directive \"@{}\" has no argument \"{}\".",
+                        directive_name, argument_name
+                    ))
+                }),
+        }
+    }
+}
+
+impl fmt::Display for SchemaCoordinate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SchemaCoordinate::Type { name } => write!(f, "{}", name),
+            SchemaCoordinate::Member {
+                type_name,
+                member_name,
+            } => write!(f, "{}.{}", type_name, member_name),
+            SchemaCoordinate::Argument {
+                type_name,
+                field_name,
+                argument_name,
+            } => write!(f, "{}.{}({}:)", type_name, field_name, argument_name),
+            SchemaCoordinate::Directive { directive_name } => write!(f, "@{}", directive_name),
+            SchemaCoordinate::DirectiveArgument {
+                directive_name,
+                argument_name,
+            } => write!(f, "@{}({}:)", directive_name, argument_name),
+        }
+    }
+}
+
+impl FromStr for SchemaCoordinate {
+    type Err = SchemaCoordinateError;
+
+    fn from_str(source: &str) -> Result<Self, Self::Err> {
+        Self::parse(source)
+    }
+}
+
+fn resolve_type<'a>(
+    schema: &'a Document,
+    name: &str,
+) -> Result<&'a TypeDefinition, SchemaCoordinateError> {
+    schema
+        .type_by_name(name)
+        .or_else(|| BUILT_INS.type_by_name(name))
+        .ok_or_else(|| SchemaCoordinateError::new(format!("Unknown type \"{}\".", name)))
+}
+
+fn resolve_field<'a>(
+    type_def: &'a TypeDefinition,
+    field_name: &str,
+) -> Result<&'a Field, SchemaCoordinateError> {
+    let fields = match type_def {
+        TypeDefinition::Object(object) => &object.fields,
+        TypeDefinition::Interface(interface) => &interface.fields,
+        _ => unreachable!("only object and interface types have fields"),
+    };
+
+    fields
+        .iter()
+        .find(|field| field.name == field_name)
+        .ok_or_else(|| {
+            SchemaCoordinateError::new(format!(
+                "Type \"{}\" has no field \"{}\".",
+                type_def.name(),
+                field_name
+            ))
+        })
+}
+
+fn resolve_directive<'a>(
+    schema: &'a Document,
+    directive_name: &str,
+) -> Result<&'a DirectiveDefinition, SchemaCoordinateError> {
+    schema
+        .directive_by_name(directive_name)
+        .or_else(|| BUILT_INS.directive_by_name(directive_name))
+        .ok_or_else(|| {
+            SchemaCoordinateError::new(format!("Unknown directive \"@{}\".", directive_name))
+        })
+}
+
+struct CoordinateParser<'a> {
+    source: &'a str,
+    position: usize,
+}
+
+impl<'a> CoordinateParser<'a> {
+    fn peek(&self) -> Option<char> {
+        self.source[self.position..].chars().next()
+    }
+
+    fn eat(&mut self, expected: char) -> bool {
+        if self.peek() == Some(expected) {
+            self.position += expected.len_utf8();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), SchemaCoordinateError> {
+        if self.eat(expected) {
+            Ok(())
+        } else {
+            Err(self.error(&format!("\"{}\"", expected)))
+        }
+    }
+
+    /// `Name :: NameStart NameContinue*`
+    fn name(&mut self) -> Result<String, SchemaCoordinateError> {
+        let start = self.position;
+
+        match self.peek() {
+            Some(c) if c == '_' || c.is_ascii_alphabetic() => self.position += 1,
+            _ => return Err(self.error("a name")),
+        }
+
+        while let Some(c) = self.peek() {
+            if c == '_' || c.is_ascii_alphanumeric() {
+                self.position += 1;
+            } else {
+                break;
+            }
+        }
+
+        Ok(self.source[start..self.position].to_string())
+    }
+
+    /// `( Name : )`, when there is one
+    fn argument(&mut self) -> Result<Option<String>, SchemaCoordinateError> {
+        if !self.eat('(') {
+            return Ok(None);
+        }
+
+        let argument_name = self.name()?;
+        self.expect(':')?;
+        self.expect(')')?;
+
+        Ok(Some(argument_name))
+    }
+
+    fn error(&self, expected: &str) -> SchemaCoordinateError {
+        let found = match self.peek() {
+            Some(c) => format!("\"{}\"", c),
+            None => "the end of the coordinate".to_string(),
+        };
+
+        SchemaCoordinateError::new(format!(
+            "Invalid schema coordinate \"{}\": expected {} at position {}, found {}.",
+            self.source, expected, self.position, found
+        ))
+    }
+}
+
+#[test]
+fn parses_and_prints_schema_coordinates() {
+    let coordinates = [
+        (
+            "Query",
+            SchemaCoordinate::Type {
+                name: "Query".to_string(),
+            },
+        ),
+        (
+            "Query.user",
+            SchemaCoordinate::Member {
+                type_name: "Query".to_string(),
+                member_name: "user".to_string(),
+            },
+        ),
+        (
+            "Query.user(id:)",
+            SchemaCoordinate::Argument {
+                type_name: "Query".to_string(),
+                field_name: "user".to_string(),
+                argument_name: "id".to_string(),
+            },
+        ),
+        (
+            "@include",
+            SchemaCoordinate::Directive {
+                directive_name: "include".to_string(),
+            },
+        ),
+        (
+            "@include(if:)",
+            SchemaCoordinate::DirectiveArgument {
+                directive_name: "include".to_string(),
+                argument_name: "if".to_string(),
+            },
+        ),
+        (
+            "__Type._field_1",
+            SchemaCoordinate::Member {
+                type_name: "__Type".to_string(),
+                member_name: "_field_1".to_string(),
+            },
+        ),
+    ];
+
+    for (source, coordinate) in coordinates {
+        assert_eq!(source.parse::<SchemaCoordinate>(), Ok(coordinate.clone()));
+        assert_eq!(coordinate.to_string(), source);
+    }
+
+    for (source, message) in [
+        (
+            "",
+            "expected a name at position 0, found the end of the coordinate",
+        ),
+        (
+            "Query.",
+            "expected a name at position 6, found the end of the coordinate",
+        ),
+        ("Query. user", "expected a name at position 6, found \" \""),
+        (
+            "Query.user(id)",
+            "expected \":\" at position 13, found \")\"",
+        ),
+        (
+            "Query.user(id:",
+            "expected \")\" at position 14, found the end of the coordinate",
+        ),
+        (
+            "Query(id:)",
+            "expected the end of the coordinate at position 5, found \"(\"",
+        ),
+        (
+            "@include.if",
+            "expected the end of the coordinate at position 8, found \".\"",
+        ),
+        ("1Query", "expected a name at position 0, found \"1\""),
+    ] {
+        assert_eq!(
+            SchemaCoordinate::parse(source),
+            Err(SchemaCoordinateError::new(format!(
+                "Invalid schema coordinate \"{}\": {}.",
+                source, message
+            )))
+        );
+    }
+}
+
+#[test]
+fn resolves_schema_coordinates() {
+    let schema = crate::parser::parse_schema::<String>(
+        "
+        type Query { user(id: ID!): User search(filter: Filter): [Result] }
+        interface Node { id: ID! }
+        type User implements Node { id: ID! role: Role }
+        union Result = User
+        enum Role { ADMIN USER }
+        input Filter { role: Role }
+        directive @auth(role: Role) on FIELD_DEFINITION
+        ",
+    )
+    .expect("Failed to parse schema")
+    .into_static();
+
+    let resolve = |source: &str| {
+        SchemaCoordinate::parse(source)
+            .unwrap()
+            .resolve(&schema)
+            .map_err(|error| error.message)
+    };
+
+    assert!(matches!(
+        resolve("Result"),
+        Ok(SchemaCoordinateTarget::Type(TypeDefinition::Union(_)))
+    ));
+    assert!(
+        matches!(resolve("Node.id"), Ok(SchemaCoordinateTarget::Field(field)) if field.name == "id")
+    );
+    assert!(
+        matches!(resolve("Filter.role"), Ok(SchemaCoordinateTarget::InputField(field)) if field.name == "role")
+    );
+    assert!(
+        matches!(resolve("Role.ADMIN"), Ok(SchemaCoordinateTarget::EnumValue(value)) if value.name == "ADMIN")
+    );
+    assert!(
+        matches!(resolve("Query.user(id:)"), Ok(SchemaCoordinateTarget::Argument(argument)) if argument.name == "id")
+    );
+    assert!(
+        matches!(resolve("@auth"), Ok(SchemaCoordinateTarget::Directive(directive)) if directive.name == "auth")
+    );
+    assert!(
+        matches!(resolve("@auth(role:)"), Ok(SchemaCoordinateTarget::DirectiveArgument(argument)) if argument.name == "role")
+    );
+
+    assert_eq!(
+        resolve("Missing"),
+        Err("Unknown type \"Missing\".".to_string())
+    );
+    assert_eq!(
+        resolve("User.name"),
+        Err("Type \"User\" has no field \"name\".".to_string())
+    );
+    assert_eq!(
+        resolve("Filter.name"),
+        Err("Input object \"Filter\" has no field \"name\".".to_string())
+    );
+    assert_eq!(
+        resolve("Role.GUEST"),
+        Err("Enum \"Role\" has no value \"GUEST\".".to_string())
+    );
+    assert_eq!(
+        resolve("Result.id"),
+        Err(
+            "Expected \"Result\" to be an enum, input object, object or interface type."
+                .to_string()
+        )
+    );
+    assert_eq!(
+        resolve("Role.ADMIN(id:)"),
+        Err("Expected \"Role\" to be an object or interface type.".to_string())
+    );
+    assert_eq!(
+        resolve("Query.user(name:)"),
+        Err("Field \"Query.user\" has no argument \"name\".".to_string())
+    );
+    assert_eq!(
+        resolve("@cached"),
+        Err("Unknown directive \"@cached\".".to_string())
+    );
+    assert_eq!(
+        resolve("@auth(scope:)"),
+        Err("Directive \"@auth\" has no argument \"scope\".".to_string())
+    );
+}
+
+#[test]
+fn resolves_built_in_schema_coordinates() {
+    let schema = crate::parser::parse_schema::<String>(
+        "
+        type Query { name: String }
+        scalar ID @specifiedBy(url: \"https://example.com/id\")
+        ",
+    )
+    .expect("Failed to parse schema")
+    .into_static();
+
+    let resolve = |source: &str| {
+        SchemaCoordinate::parse(source)
+            .unwrap()
+            .resolve(&schema)
+            .map_err(|error| error.message)
+    };
+
+    assert!(
+        matches!(resolve("String"), Ok(SchemaCoordinateTarget::Type(TypeDefinition::Scalar(scalar))) if scalar.name == "String")
+    );
+    assert!(
+        matches!(resolve("ID"), Ok(SchemaCoordinateTarget::Type(TypeDefinition::Scalar(scalar))) if !scalar.directives.is_empty())
+    );
+    assert!(
+        matches!(resolve("__Type.fields(includeDeprecated:)"), Ok(SchemaCoordinateTarget::Argument(argument)) if argument.name == "includeDeprecated")
+    );
+    assert!(
+        matches!(resolve("__TypeKind.OBJECT"), Ok(SchemaCoordinateTarget::EnumValue(value)) if value.name == "OBJECT")
+    );
+    assert!(
+        matches!(resolve("@skip"), Ok(SchemaCoordinateTarget::Directive(directive)) if directive.name == "skip")
+    );
+    assert!(
+        matches!(resolve("@deprecated(reason:)"), Ok(SchemaCoordinateTarget::DirectiveArgument(argument)) if argument.name == "reason")
+    );
+}
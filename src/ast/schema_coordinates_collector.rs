@@ -0,0 +1,320 @@
+use std::collections::{BTreeMap, HashSet};
+
+use crate::ast::{
+    visit_document, FieldByNameExtension, IntoSchema, OperationVisitor, OperationVisitorContext,
+    Schema, SchemaCoordinate, SchemaDocumentExtension, TypeDefinitionExtension, TypeExtension,
+};
+use crate::introspection::BUILT_INS;
+use crate::static_graphql::query::{Directive, Document, Field, Value, VariableDefinition};
+use crate::static_graphql::schema::TypeDefinition;
+
+/// Collects the schema coordinates used by an operation, for usage reporting:
+///
+/// - fields (`Type.field`), on the type they are selected on, including the ones selected in
+///   fragments and on abstract types
+/// - arguments (`Type.field(arg:)`), directives (`@directive`) and directive arguments
+///   (`@directive(arg:)`)
+/// - input object fields (`Input.field`) set in literals, and all the input object fields a
+///   variable can set, following its declared type
+/// - enum values (`Enum.VALUE`) used in literals
+///
+/// Elements that don't exist in the schema are ignored, but the operation is expected to be
+/// valid. The coordinates are collected in the `HashSet` given as user context.
+pub struct SchemaCoordinatesCollector {
+    /// Names of the directives being visited, to tell their arguments from field arguments.
+    directives: Vec<String>,
+}
+
+impl Default for SchemaCoordinatesCollector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SchemaCoordinatesCollector {
+    pub fn new() -> Self {
+        SchemaCoordinatesCollector { directives: vec![] }
+    }
+}
+
+impl<'a> OperationVisitor<'a, HashSet<SchemaCoordinate>> for SchemaCoordinatesCollector {
+    fn enter_field(
+        &mut self,
+        visitor_context: &mut OperationVisitorContext<'a>,
+        coordinates: &mut HashSet<SchemaCoordinate>,
+        field: &Field,
+    ) {
+        if let Some(parent_type) = visitor_context.current_parent_type() {
+            if parent_type.field_by_name(&field.name).is_some() {
+                coordinates.insert(SchemaCoordinate::Member {
+                    type_name: parent_type.name().to_string(),
+                    member_name: field.name.clone(),
+                });
+            }
+        }
+    }
+
+    fn enter_directive(
+        &mut self,
+        visitor_context: &mut OperationVisitorContext<'a>,
+        coordinates: &mut HashSet<SchemaCoordinate>,
+        directive: &Directive,
+    ) {
+        self.directives.push(directive.name.clone());
+
+        if visitor_context
+            .indexed_schema()
+            .directive_by_name(&directive.name)
+            .or_else(|| BUILT_INS.directive_by_name(&directive.name))
+            .is_some()
+        {
+            coordinates.insert(SchemaCoordinate::Directive {
+                directive_name: directive.name.clone(),
+            });
+        }
+    }
+
+    fn leave_directive(
+        &mut self,
+        _: &mut OperationVisitorContext<'a>,
+        _: &mut HashSet<SchemaCoordinate>,
+        _: &Directive,
+    ) {
+        self.directives.pop();
+    }
+
+    fn enter_argument(
+        &mut self,
+        visitor_context: &mut OperationVisitorContext<'a>,
+        coordinates: &mut HashSet<SchemaCoordinate>,
+        (argument_name, _): &(String, Value),
+    ) {
+        // The type of the argument is only known when it's defined
+        if visitor_context.current_input_type_literal().is_none() {
+            return;
+        }
+
+        if let Some(directive_name) = self.directives.last() {
+            coordinates.insert(SchemaCoordinate::DirectiveArgument {
+                directive_name: directive_name.clone(),
+                argument_name: argument_name.clone(),
+            });
+        } else if let (Some(parent_type), Some(field)) = (
+            visitor_context.current_parent_type(),
+            visitor_context.current_field(),
+        ) {
+            coordinates.insert(SchemaCoordinate::Argument {
+                type_name: parent_type.name().to_string(),
+                field_name: field.name.clone(),
+                argument_name: argument_name.clone(),
+            });
+        }
+    }
+
+    fn enter_object_value(
+        &mut self,
+        visitor_context: &mut OperationVisitorContext<'a>,
+        coordinates: &mut HashSet<SchemaCoordinate>,
+        fields: &BTreeMap<String, Value>,
+    ) {
+        if let Some(TypeDefinition::InputObject(input_object)) =
+            visitor_context.current_input_type()
+        {
+            for field_name in fields.keys() {
+                if input_object.fields.iter().any(|f| &f.name == field_name) {
+                    coordinates.insert(SchemaCoordinate::Member {
+                        type_name: input_object.name.clone(),
+                        member_name: field_name.clone(),
+                    });
+                }
+            }
+        }
+    }
+
+    fn enter_enum_value(
+        &mut self,
+        visitor_context: &mut OperationVisitorContext<'a>,
+        coordinates: &mut HashSet<SchemaCoordinate>,
+        value: &String,
+    ) {
+        if let Some(TypeDefinition::Enum(enum_type)) = visitor_context.current_input_type() {
+            if enum_type.values.iter().any(|v| &v.name == value) {
+                coordinates.insert(SchemaCoordinate::Member {
+                    type_name: enum_type.name.clone(),
+                    member_name: value.clone(),
+                });
+            }
+        }
+    }
+
+    fn enter_variable_definition(
+        &mut self,
+        visitor_context: &mut OperationVisitorContext<'a>,
+        coordinates: &mut HashSet<SchemaCoordinate>,
+        variable: &VariableDefinition,
+    ) {
+        collect_input_fields(
+            visitor_context.indexed_schema(),
+            variable.var_type.inner_type(),
+            coordinates,
+            &mut HashSet::new(),
+        );
+    }
+}
+
+/// Any field of an input object (and of the input objects it contains) can be set through a
+/// variable.
+fn collect_input_fields<'a>(
+    schema: &'a Schema<'a>,
+    type_name: &str,
+    coordinates: &mut HashSet<SchemaCoordinate>,
+    visited_types: &mut HashSet<&'a str>,
+) {
+    if let Some(TypeDefinition::InputObject(input_object)) = schema.type_by_name(type_name) {
+        if !visited_types.insert(&input_object.name) {
+            return;
+        }
+
+        for field in &input_object.fields {
+            coordinates.insert(SchemaCoordinate::Member {
+                type_name: input_object.name.clone(),
+                member_name: field.name.clone(),
+            });
+            collect_input_fields(
+                schema,
+                field.value_type.inner_type(),
+                coordinates,
+                visited_types,
+            );
+        }
+    }
+}
+
+/// Returns the schema coordinates used by the operations of `document`, see
+/// `SchemaCoordinatesCollector`.
+pub fn collect_schema_coordinates<'a>(
+    document: &'a Document,
+    schema: impl IntoSchema<'a>,
+) -> HashSet<SchemaCoordinate> {
+    let mut visitor_context = OperationVisitorContext::new(document, schema);
+    let mut coordinates = HashSet::new();
+
+    visit_document(
+        &mut SchemaCoordinatesCollector::new(),
+        document,
+        &mut visitor_context,
+        &mut coordinates,
+    );
+
+    coordinates
+}
+
+#[test]
+fn collects_schema_coordinates_of_operation() {
+    let schema = crate::parser::parse_schema::<String>(
+        "
+        type Query {
+          node(id: ID!): Node
+          search(filter: Filter, first: Int): [Result]
+          users(where: UserWhere): [User]
+        }
+        interface Node { id: ID! }
+        type User implements Node { id: ID! name(format: Format): String role: Role }
+        type Post implements Node { id: ID! title: String }
+        union Result = User | Post
+        enum Role { ADMIN USER }
+        enum Format { SHORT LONG }
+        input Filter { role: Role text: String tags: [Tag!] }
+        input Tag { name: String }
+        input UserWhere { name: String and: [UserWhere!] role: Role }
+        directive @auth(scope: String) on FIELD
+        ",
+    )
+    .expect("Failed to parse schema")
+    .into_static();
+
+    let document = crate::parser::parse_query::<String>(
+        "
+        query Search($where: UserWhere, $skip: Boolean!) {
+          node(id: \"1\") {
+            __typename
+            id
+            ...UserFields
+          }
+          search(filter: { role: ADMIN, tags: [{ name: \"a\" }] }) {
+            ... on Post { title @skip(if: $skip) }
+          }
+          users(where: $where) { name(format: LONG) @auth(scope: \"users\") }
+        }
+
+        fragment UserFields on User { role }
+        ",
+    )
+    .expect("Failed to parse query")
+    .into_static();
+
+    let mut coordinates = collect_schema_coordinates(&document, &schema)
+        .iter()
+        .map(|coordinate| coordinate.to_string())
+        .collect::<Vec<_>>();
+    coordinates.sort();
+
+    assert_eq!(
+        coordinates,
+        vec![
+            "@auth",
+            "@auth(scope:)",
+            "@skip",
+            "@skip(if:)",
+            "Filter.role",
+            "Filter.tags",
+            "Format.LONG",
+            "Node.id",
+            "Post.title",
+            "Query.node",
+            "Query.node(id:)",
+            "Query.search",
+            "Query.search(filter:)",
+            "Query.users",
+            "Query.users(where:)",
+            "Role.ADMIN",
+            "Tag.name",
+            "User.name",
+            "User.name(format:)",
+            "User.role",
+            "UserWhere.and",
+            "UserWhere.name",
+            "UserWhere.role",
+        ]
+    );
+}
+
+#[test]
+fn collects_built_in_directives() {
+    let schema = crate::parser::parse_schema::<String>("type Query { a(x: Int): Int }")
+        .expect("Failed to parse schema")
+        .into_static();
+    let document = crate::parser::parse_query::<String>(
+        "query($v: Boolean!) { a(x: 1) @include(if: $v) @skip(if: false) }",
+    )
+    .expect("Failed to parse query")
+    .into_static();
+
+    let mut coordinates = collect_schema_coordinates(&document, &schema)
+        .iter()
+        .map(|coordinate| coordinate.to_string())
+        .collect::<Vec<_>>();
+    coordinates.sort();
+
+    assert_eq!(
+        coordinates,
+        vec![
+            "@include",
+            "@include(if:)",
+            "@skip",
+            "@skip(if:)",
+            "Query.a",
+            "Query.a(x:)"
+        ]
+    );
+}
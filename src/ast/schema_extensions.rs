@@ -0,0 +1,199 @@
+use std::collections::HashMap;
+
+use crate::static_graphql::schema::{self, Definition, TypeDefinition, TypeExtension};
+
+use super::{TypeDefinitionExtension, TypeExtensionHelpers};
+
+/// Applies every type extension (`extend type`, `extend interface`, `extend union`, `extend enum`,
+/// `extend input` and `extend scalar`) of `document` to the type it extends, and returns the
+/// resulting document.
+///
+/// `SchemaDocumentExtension` and `Schema` only look at type definitions, so a schema split into
+/// a definition and extensions needs to be merged before it's used for validation.
+///
+/// Extensions of types that aren't defined in the document, or that are defined with a different
+/// kind, are kept as they are (see the `PossibleTypeExtensions` schema rule).
+///
+/// Note that `extend schema` can't be represented: the parser doesn't support it.
+pub fn merge_type_extensions(document: &schema::Document) -> schema::Document {
+    let mut definitions: Vec<Definition> = document
+        .definitions
+        .iter()
+        .filter(|definition| !matches!(definition, Definition::TypeExtension(_)))
+        .cloned()
+        .collect();
+
+    let mut type_indexes: HashMap<String, usize> = HashMap::new();
+
+    for (index, definition) in definitions.iter().enumerate() {
+        if let Definition::TypeDefinition(type_def) = definition {
+            type_indexes
+                .entry(type_def.name().to_string())
+                .or_insert(index);
+        }
+    }
+
+    for definition in &document.definitions {
+        if let Definition::TypeExtension(extension) = definition {
+            let extended_type = type_indexes.get(extension.name()).and_then(|index| {
+                match &mut definitions[*index] {
+                    Definition::TypeDefinition(type_def) => Some(type_def),
+                    _ => None,
+                }
+            });
+
+            let applied = match extended_type {
+                Some(type_def) => apply_type_extension(type_def, extension),
+                None => false,
+            };
+
+            if !applied {
+                definitions.push(definition.clone());
+            }
+        }
+    }
+
+    schema::Document { definitions }
+}
+
+/// Returns `false` when `extension` is not of the same kind as `type_def`.
+fn apply_type_extension(type_def: &mut TypeDefinition, extension: &TypeExtension) -> bool {
+    match (type_def, extension) {
+        (TypeDefinition::Scalar(scalar), TypeExtension::Scalar(extension)) => {
+            scalar
+                .directives
+                .extend(extension.directives.iter().cloned());
+        }
+        (TypeDefinition::Object(object), TypeExtension::Object(extension)) => {
+            object
+                .implements_interfaces
+                .extend(extension.implements_interfaces.iter().cloned());
+            object
+                .directives
+                .extend(extension.directives.iter().cloned());
+            object.fields.extend(extension.fields.iter().cloned());
+        }
+        (TypeDefinition::Interface(interface), TypeExtension::Interface(extension)) => {
+            interface
+                .implements_interfaces
+                .extend(extension.implements_interfaces.iter().cloned());
+            interface
+                .directives
+                .extend(extension.directives.iter().cloned());
+            interface.fields.extend(extension.fields.iter().cloned());
+        }
+        (TypeDefinition::Union(union), TypeExtension::Union(extension)) => {
+            union
+                .directives
+                .extend(extension.directives.iter().cloned());
+            union.types.extend(extension.types.iter().cloned());
+        }
+        (TypeDefinition::Enum(enum_), TypeExtension::Enum(extension)) => {
+            enum_
+                .directives
+                .extend(extension.directives.iter().cloned());
+            enum_.values.extend(extension.values.iter().cloned());
+        }
+        (TypeDefinition::InputObject(input_object), TypeExtension::InputObject(extension)) => {
+            input_object
+                .directives
+                .extend(extension.directives.iter().cloned());
+            input_object.fields.extend(extension.fields.iter().cloned());
+        }
+        _ => return false,
+    }
+
+    true
+}
+
+#[test]
+fn merges_extensions_of_all_kinds() {
+    use crate::ast::SchemaDocumentExtension;
+
+    let document = crate::parser::parse_schema::<String>(
+        "
+extend type Query { b: Int }
+type Query { a: Int }
+extend type Query implements Node @tag { id: ID! }
+interface Node { id: ID! }
+extend interface Node @tag
+union Result = Query
+extend union Result = Other
+type Other { c: Int }
+enum Color { RED }
+extend enum Color { GREEN }
+input Filter { a: Int }
+extend input Filter { b: Int }
+scalar Date
+extend scalar Date @tag
+directive @tag on OBJECT | INTERFACE | UNION | SCALAR
+",
+    )
+    .expect("Failed to parse schema")
+    .into_static();
+
+    let merged = merge_type_extensions(&document);
+
+    assert!(!merged
+        .definitions
+        .iter()
+        .any(|definition| matches!(definition, Definition::TypeExtension(_))));
+
+    match merged.type_by_name("Query") {
+        Some(TypeDefinition::Object(query)) => {
+            let fields: Vec<&str> = query.fields.iter().map(|f| f.name.as_str()).collect();
+            assert_eq!(fields, vec!["a", "b", "id"]);
+            assert_eq!(query.implements_interfaces, vec!["Node"]);
+            assert_eq!(query.directives.len(), 1);
+        }
+        other => panic!("unexpected Query type: {:?}", other),
+    }
+    match merged.type_by_name("Node") {
+        Some(TypeDefinition::Interface(node)) => assert_eq!(node.directives.len(), 1),
+        other => panic!("unexpected Node type: {:?}", other),
+    }
+    match merged.type_by_name("Result") {
+        Some(TypeDefinition::Union(result)) => assert_eq!(result.types, vec!["Query", "Other"]),
+        other => panic!("unexpected Result type: {:?}", other),
+    }
+    match merged.type_by_name("Color") {
+        Some(TypeDefinition::Enum(color)) => {
+            let values: Vec<&str> = color.values.iter().map(|v| v.name.as_str()).collect();
+            assert_eq!(values, vec!["RED", "GREEN"]);
+        }
+        other => panic!("unexpected Color type: {:?}", other),
+    }
+    match merged.type_by_name("Filter") {
+        Some(TypeDefinition::InputObject(filter)) => assert_eq!(filter.fields.len(), 2),
+        other => panic!("unexpected Filter type: {:?}", other),
+    }
+    match merged.type_by_name("Date") {
+        Some(TypeDefinition::Scalar(date)) => assert_eq!(date.directives.len(), 1),
+        other => panic!("unexpected Date type: {:?}", other),
+    }
+}
+
+#[test]
+fn keeps_extensions_that_cannot_be_applied() {
+    let document = crate::parser::parse_schema::<String>(
+        "
+type Query { a: Int }
+extend type Unknown { b: Int }
+extend enum Query { A }
+",
+    )
+    .expect("Failed to parse schema")
+    .into_static();
+
+    let merged = merge_type_extensions(&document);
+    let extensions: Vec<&str> = merged
+        .definitions
+        .iter()
+        .filter_map(|definition| match definition {
+            Definition::TypeExtension(extension) => Some(extension.name()),
+            _ => None,
+        })
+        .collect();
+
+    assert_eq!(extensions, vec!["Unknown", "Query"]);
+}
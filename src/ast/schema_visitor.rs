@@ -1,6 +1,8 @@
 use crate::static_graphql::schema::{
-    Definition, DirectiveDefinition, Document, EnumType, EnumValue, Field, InputObjectType,
-    InputValue, InterfaceType, ObjectType, ScalarType, SchemaDefinition, TypeDefinition, UnionType,
+    Definition, DirectiveDefinition, Document, EnumType, EnumTypeExtension, EnumValue, Field,
+    InputObjectType, InputObjectTypeExtension, InputValue, InterfaceType, InterfaceTypeExtension,
+    ObjectType, ObjectTypeExtension, ScalarType, ScalarTypeExtension, SchemaDefinition,
+    TypeDefinition, TypeExtension, UnionType, UnionTypeExtension,
 };
 
 /// A trait for implenenting a visitor for GraphQL schema definition.
@@ -83,9 +85,37 @@ pub trait SchemaVisitor<T = ()> {
                     self.enter_directive_definition(directive_definition, _visitor_context);
                     self.leave_directive_definition(directive_definition, _visitor_context);
                 }
-                Definition::TypeExtension(_type_extension) => {
-                    // TODO: implement this
-                    panic!("TypeExtension not supported at the moment");
+                Definition::TypeExtension(type_extension) => {
+                    self.enter_type_extension(type_extension, _visitor_context);
+
+                    match type_extension {
+                        TypeExtension::Object(object) => {
+                            self.enter_object_type_extension(object, _visitor_context);
+                            self.leave_object_type_extension(object, _visitor_context);
+                        }
+                        TypeExtension::Scalar(scalar) => {
+                            self.enter_scalar_type_extension(scalar, _visitor_context);
+                            self.leave_scalar_type_extension(scalar, _visitor_context);
+                        }
+                        TypeExtension::Enum(enum_) => {
+                            self.enter_enum_type_extension(enum_, _visitor_context);
+                            self.leave_enum_type_extension(enum_, _visitor_context);
+                        }
+                        TypeExtension::Union(union) => {
+                            self.enter_union_type_extension(union, _visitor_context);
+                            self.leave_union_type_extension(union, _visitor_context);
+                        }
+                        TypeExtension::InputObject(input_object) => {
+                            self.enter_input_object_type_extension(input_object, _visitor_context);
+                            self.leave_input_object_type_extension(input_object, _visitor_context);
+                        }
+                        TypeExtension::Interface(interface) => {
+                            self.enter_interface_type_extension(interface, _visitor_context);
+                            self.leave_interface_type_extension(interface, _visitor_context);
+                        }
+                    }
+
+                    self.leave_type_extension(type_extension, _visitor_context);
                 }
             }
         }
@@ -170,6 +200,47 @@ pub trait SchemaVisitor<T = ()> {
 
     fn enter_enum_value(&self, _node: &EnumValue, _enum: &EnumType, _visitor_context: &mut T) {}
     fn leave_enum_value(&self, _node: &EnumValue, _enum: &EnumType, _visitor_context: &mut T) {}
+
+    fn enter_type_extension(&self, _node: &TypeExtension, _visitor_context: &mut T) {}
+    fn leave_type_extension(&self, _node: &TypeExtension, _visitor_context: &mut T) {}
+
+    fn enter_object_type_extension(&self, _node: &ObjectTypeExtension, _visitor_context: &mut T) {}
+    fn leave_object_type_extension(&self, _node: &ObjectTypeExtension, _visitor_context: &mut T) {}
+
+    fn enter_interface_type_extension(
+        &self,
+        _node: &InterfaceTypeExtension,
+        _visitor_context: &mut T,
+    ) {
+    }
+    fn leave_interface_type_extension(
+        &self,
+        _node: &InterfaceTypeExtension,
+        _visitor_context: &mut T,
+    ) {
+    }
+
+    fn enter_input_object_type_extension(
+        &self,
+        _node: &InputObjectTypeExtension,
+        _visitor_context: &mut T,
+    ) {
+    }
+    fn leave_input_object_type_extension(
+        &self,
+        _node: &InputObjectTypeExtension,
+        _visitor_context: &mut T,
+    ) {
+    }
+
+    fn enter_union_type_extension(&self, _node: &UnionTypeExtension, _visitor_context: &mut T) {}
+    fn leave_union_type_extension(&self, _node: &UnionTypeExtension, _visitor_context: &mut T) {}
+
+    fn enter_scalar_type_extension(&self, _node: &ScalarTypeExtension, _visitor_context: &mut T) {}
+    fn leave_scalar_type_extension(&self, _node: &ScalarTypeExtension, _visitor_context: &mut T) {}
+
+    fn enter_enum_type_extension(&self, _node: &EnumTypeExtension, _visitor_context: &mut T) {}
+    fn leave_enum_type_extension(&self, _node: &EnumTypeExtension, _visitor_context: &mut T) {}
 }
 
 #[test]
@@ -377,3 +448,78 @@ fn visit_schema() {
         vec!["UsersFilter.name"]
     );
 }
+
+#[test]
+fn visit_schema_extensions() {
+    use crate::ast::TypeExtensionHelpers;
+    use crate::parser::schema::parse_schema;
+
+    let schema_ast = parse_schema(
+        r#"
+    extend type Query { b: Int }
+    extend interface Node { b: Int }
+    extend union Result = A
+    extend enum Role { GUEST }
+    extend input Filter { b: Int }
+    extend scalar Date @foo
+    "#,
+    )
+    .expect("Failed to parse schema")
+    .into_static();
+
+    struct TestVisitor;
+
+    impl SchemaVisitor<Vec<String>> for TestVisitor {
+        fn enter_type_extension(&self, node: &TypeExtension, visited: &mut Vec<String>) {
+            visited.push(format!("enter {} {}", node.kind(), node.name()));
+        }
+
+        fn leave_type_extension(&self, node: &TypeExtension, visited: &mut Vec<String>) {
+            visited.push(format!("leave {}", node.name()));
+        }
+
+        fn enter_object_type_extension(
+            &self,
+            node: &ObjectTypeExtension,
+            visited: &mut Vec<String>,
+        ) {
+            visited.push(format!("fields {}", node.fields.len()));
+        }
+
+        fn enter_union_type_extension(&self, node: &UnionTypeExtension, visited: &mut Vec<String>) {
+            visited.push(format!("members {}", node.types.join(", ")));
+        }
+
+        fn enter_scalar_type_extension(
+            &self,
+            node: &ScalarTypeExtension,
+            visited: &mut Vec<String>,
+        ) {
+            visited.push(format!("directives {}", node.directives.len()));
+        }
+    }
+
+    let mut visited = vec![];
+    TestVisitor.visit_schema_document(&schema_ast, &mut visited);
+
+    assert_eq!(
+        visited,
+        vec![
+            "enter object Query",
+            "fields 1",
+            "leave Query",
+            "enter interface Node",
+            "leave Node",
+            "enter union Result",
+            "members A",
+            "leave Result",
+            "enter enum Role",
+            "leave Role",
+            "enter input object Filter",
+            "leave Filter",
+            "enter scalar Date",
+            "directives 1",
+            "leave Date",
+        ]
+    );
+}
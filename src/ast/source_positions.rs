@@ -0,0 +1,320 @@
+use crate::parser::Pos;
+
+/// A token of an operation source, see `tokenize`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Token<'s> {
+    /// One of `! $ & ( ) : = @ [ ] { | }`, or `...` (as `.`).
+    Punctuator(char),
+    Name(&'s str),
+    /// A number or a string.
+    Literal,
+}
+
+/// Splits the source of an operation into tokens, with the positions `graphql-parser` gives to
+/// the nodes starting with them (tabs count as 8 columns, like in the parser).
+///
+/// The source is expected to be a document that was parsed successfully: tokenizing stops at
+/// the first character that can't start a token.
+pub(crate) fn tokenize(source: &str) -> Vec<(Token<'_>, Pos)> {
+    let mut tokens = Vec::new();
+    let mut position = Pos { line: 1, column: 1 };
+    let mut offset = 0;
+
+    loop {
+        let mut chars = source[offset..].char_indices();
+
+        // Whitespace, commas and comments
+        let start = loop {
+            match chars.next() {
+                None => return tokens,
+                Some((_, '\u{feff}' | '\r')) => {}
+                Some((_, '\t')) => position.column += 8,
+                Some((_, '\n')) => {
+                    position.line += 1;
+                    position.column = 1;
+                }
+                Some((_, ' ' | ',')) => position.column += 1,
+                Some((_, '#')) => {
+                    for (_, c) in chars.by_ref() {
+                        if c == '\r' || c == '\n' {
+                            position.line += 1;
+                            position.column = 1;
+                            break;
+                        }
+                    }
+                }
+                Some((index, _)) => break index,
+            }
+        };
+
+        offset += start;
+        let rest = &source[offset..];
+        let (token, len) = match rest.chars().next() {
+            Some(
+                c @ ('!' | '$' | '&' | '(' | ')' | ':' | '=' | '@' | '[' | ']' | '{' | '|' | '}'),
+            ) => (Token::Punctuator(c), 1),
+            Some('.') if rest.starts_with("...") => (Token::Punctuator('.'), 3),
+            Some('_' | 'a'..='z' | 'A'..='Z') => {
+                let len = rest
+                    .find(|c: char| c != '_' && !c.is_ascii_alphanumeric())
+                    .unwrap_or(rest.len());
+
+                (Token::Name(&rest[..len]), len)
+            }
+            Some('-' | '0'..='9') => {
+                let len = rest
+                    .find(|c: char| " \n\r\t,#!$:=@|&()[]{}".contains(c))
+                    .unwrap_or(rest.len());
+
+                (Token::Literal, len)
+            }
+            Some('"') => match string_len(rest) {
+                Some(len) => (Token::Literal, len),
+                None => return tokens,
+            },
+            _ => return tokens,
+        };
+
+        tokens.push((token, position));
+
+        let text = &rest[..len];
+        match text.rfind('\n') {
+            Some(last_line) => {
+                position.line += text.matches('\n').count();
+                position.column = text[last_line + 1..].chars().count() + 1;
+            }
+            None => position.column += text.chars().count(),
+        }
+        offset += len;
+    }
+}
+
+/// Length of the string (or block string) starting `source`.
+fn string_len(source: &str) -> Option<usize> {
+    if let Some(block) = source.strip_prefix("\"\"\"") {
+        return block
+            .match_indices("\"\"\"")
+            .find(|(end, _)| !block[..*end].ends_with('\\'))
+            .map(|(end, _)| end + 6);
+    }
+
+    let mut escaped = false;
+
+    for (index, c) in source.char_indices().skip(1) {
+        match c {
+            '"' if !escaped => return Some(index + 1),
+            '\n' => return None,
+            _ => {}
+        }
+
+        escaped = !escaped && c == '\\';
+    }
+
+    None
+}
+
+/// The position of a value in the source, with the positions of its list items or object
+/// fields.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct ValuePositions<'s> {
+    pub position: Pos,
+    pub items: Vec<ValuePositions<'s>>,
+    /// Fields of an object value, in source order: name, position of the field and positions
+    /// of its value. Duplicated names are kept (the parsed value only has the last one).
+    pub fields: Vec<(&'s str, Pos, ValuePositions<'s>)>,
+}
+
+impl<'s> ValuePositions<'s> {
+    /// The field `name` of an object value. When the name is duplicated, this is the last field,
+    /// the one kept by the parser.
+    pub fn field(&self, name: &str) -> Option<(Pos, &ValuePositions<'s>)> {
+        self.fields
+            .iter()
+            .rev()
+            .find(|(field_name, _, _)| *field_name == name)
+            .map(|(_, position, value)| (*position, value))
+    }
+}
+
+/// Positions of the arguments and values of an operation, read from its source: the AST of
+/// `graphql-parser` only has positions for definitions, selections, directives and variable
+/// definitions.
+pub(crate) struct SourcePositions<'s> {
+    tokens: Vec<(Token<'s>, Pos)>,
+}
+
+impl<'s> SourcePositions<'s> {
+    pub fn new(source: &'s str) -> Self {
+        Self {
+            tokens: tokenize(source),
+        }
+    }
+
+    /// Positions of the arguments (name and value) of the field or directive starting at
+    /// `position`, in source order.
+    pub fn arguments(&self, position: Pos) -> Vec<(Pos, ValuePositions<'s>)> {
+        let mut arguments = Vec::new();
+        let mut index = match self.token_index(position) {
+            Some(index) => index,
+            None => return arguments,
+        };
+
+        // `@name` for a directive, `alias: name` or `name` for a field
+        if self.is_punctuator(index, '@') {
+            index += 1;
+        }
+        index += 1;
+        if self.is_punctuator(index, ':') {
+            index += 2;
+        }
+        if !self.is_punctuator(index, '(') {
+            return arguments;
+        }
+        index += 1;
+
+        while let Some((Token::Name(_), name_position)) = self.tokens.get(index) {
+            match self.value(index + 2) {
+                Some((value, next_index)) => {
+                    arguments.push((*name_position, value));
+                    index = next_index;
+                }
+                None => break,
+            }
+        }
+
+        arguments
+    }
+
+    /// Positions of the default value of the variable definition starting at `position`.
+    pub fn default_value(&self, position: Pos) -> Option<ValuePositions<'s>> {
+        // `$name: Type = value`
+        let mut index = self.token_index(position)? + 3;
+
+        while let Some((Token::Name(_) | Token::Punctuator('[' | ']' | '!'), _)) =
+            self.tokens.get(index)
+        {
+            index += 1;
+        }
+
+        if !self.is_punctuator(index, '=') {
+            return None;
+        }
+
+        self.value(index + 1).map(|(value, _)| value)
+    }
+
+    /// Positions of the value starting at the token `index`, and the index of the token
+    /// following it.
+    fn value(&self, index: usize) -> Option<(ValuePositions<'s>, usize)> {
+        let (token, position) = *self.tokens.get(index)?;
+        let mut value = ValuePositions {
+            position,
+            ..ValuePositions::default()
+        };
+
+        match token {
+            Token::Name(_) | Token::Literal => Some((value, index + 1)),
+            Token::Punctuator('$') => Some((value, index + 2)),
+            Token::Punctuator('[') => {
+                let mut index = index + 1;
+
+                while !self.is_punctuator(index, ']') {
+                    let (item, next_index) = self.value(index)?;
+                    value.items.push(item);
+                    index = next_index;
+                }
+
+                Some((value, index + 1))
+            }
+            Token::Punctuator('{') => {
+                let mut index = index + 1;
+
+                while let Some((Token::Name(name), field_position)) = self.tokens.get(index) {
+                    let (field_value, next_index) = self.value(index + 2)?;
+                    value.fields.push((*name, *field_position, field_value));
+                    index = next_index;
+                }
+
+                if !self.is_punctuator(index, '}') {
+                    return None;
+                }
+
+                Some((value, index + 1))
+            }
+            Token::Punctuator(_) => None,
+        }
+    }
+
+    fn token_index(&self, position: Pos) -> Option<usize> {
+        self.tokens
+            .binary_search_by_key(&position, |(_, token_position)| *token_position)
+            .ok()
+    }
+
+    fn is_punctuator(&self, index: usize, punctuator: char) -> bool {
+        matches!(self.tokens.get(index), Some((Token::Punctuator(c), _)) if *c == punctuator)
+    }
+}
+
+#[test]
+fn finds_argument_and_value_positions() {
+    let source = "
+query Q($a: [Int!]! = [1, 2], $b: In = { x: \"\"\"
+  block\"\"\" y: $a }) {
+  alias: field(first: -1.5e3, second: { z: [ENUM] z: null }) @skip(if: $b) { id }
+}";
+    let positions = SourcePositions::new(source);
+    let pos = |line, column| Pos { line, column };
+
+    assert_eq!(
+        positions.default_value(pos(2, 9)),
+        Some(ValuePositions {
+            position: pos(2, 23),
+            items: vec![
+                ValuePositions {
+                    position: pos(2, 24),
+                    ..ValuePositions::default()
+                },
+                ValuePositions {
+                    position: pos(2, 27),
+                    ..ValuePositions::default()
+                },
+            ],
+            fields: vec![],
+        })
+    );
+    assert_eq!(
+        positions.default_value(pos(2, 31)).map(|value| value
+            .fields
+            .iter()
+            .map(|f| f.1)
+            .collect::<Vec<_>>()),
+        Some(vec![pos(2, 42), pos(3, 12)])
+    );
+
+    let arguments = positions.arguments(pos(4, 3));
+    assert_eq!(
+        arguments
+            .iter()
+            .map(|(name, value)| (*name, value.position))
+            .collect::<Vec<_>>(),
+        vec![(pos(4, 16), pos(4, 23)), (pos(4, 31), pos(4, 39))]
+    );
+    assert_eq!(
+        arguments[1]
+            .1
+            .field("z")
+            .map(|(position, value)| (position, value.position)),
+        Some((pos(4, 51), pos(4, 54)))
+    );
+    assert_eq!(arguments[1].1.fields[0].2.items[0].position, pos(4, 45));
+
+    let directive_arguments = positions.arguments(pos(4, 62));
+    assert_eq!(
+        directive_arguments
+            .iter()
+            .map(|(name, value)| (*name, value.position))
+            .collect::<Vec<_>>(),
+        vec![(pos(4, 68), pos(4, 72))]
+    );
+}
@@ -0,0 +1,193 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::static_graphql::query::{
+    Definition, Document, FragmentDefinition, OperationDefinition, SelectionSet,
+};
+
+use super::{AstNodeWithName, FragmentSpreadExtraction, OperationDefinitionExtension};
+
+/// Returns the operation named `operation_name` in `document`, or its only operation when no
+/// name is given, like `getOperationAST` in graphql-js. Returns `None` when the named operation
+/// doesn't exist, or when no name is given and the document has zero or several operations.
+pub fn get_operation<'d>(
+    document: &'d Document,
+    operation_name: Option<&str>,
+) -> Option<&'d OperationDefinition> {
+    let mut selected = None;
+
+    for definition in &document.definitions {
+        if let Definition::Operation(operation) = definition {
+            match operation_name {
+                Some(operation_name) => {
+                    if operation.node_name() == Some(operation_name) {
+                        return Some(operation);
+                    }
+                }
+                None => {
+                    if selected.is_some() {
+                        return None;
+                    }
+
+                    selected = Some(operation);
+                }
+            }
+        }
+    }
+
+    selected
+}
+
+/// Names of the fragments spread in `selection_set`, directly or through the fragment
+/// definitions of `fragments`, in the order they are first spread. Spreads of unknown fragments
+/// are included, and cycles are only followed once.
+pub fn transitive_fragment_names<'d>(
+    selection_set: &'d SelectionSet,
+    fragments: &HashMap<&str, &'d FragmentDefinition>,
+) -> Vec<&'d str> {
+    fn visit<'d>(
+        selection_set: &'d SelectionSet,
+        fragments: &HashMap<&str, &'d FragmentDefinition>,
+        names: &mut Vec<&'d str>,
+        visited: &mut HashSet<&'d str>,
+    ) {
+        for spread in selection_set.get_recursive_fragment_spreads() {
+            let name = spread.fragment_name.as_str();
+
+            if visited.insert(name) {
+                names.push(name);
+
+                if let Some(fragment) = fragments.get(name) {
+                    visit(&fragment.selection_set, fragments, names, visited);
+                }
+            }
+        }
+    }
+
+    let mut names = vec![];
+    visit(selection_set, fragments, &mut names, &mut HashSet::new());
+
+    names
+}
+
+/// Splits a document with several operations into one standalone document per operation (in
+/// document order), containing the operation and only the fragment definitions it references,
+/// directly or through other fragments. Fragment definitions keep their order in `document`.
+pub fn split_operations(document: &Document) -> Vec<Document> {
+    let fragments = document
+        .definitions
+        .iter()
+        .filter_map(|definition| match definition {
+            Definition::Fragment(fragment) => Some((fragment.name.as_str(), fragment)),
+            _ => None,
+        })
+        .collect::<HashMap<_, _>>();
+
+    document
+        .definitions
+        .iter()
+        .filter_map(|definition| match definition {
+            Definition::Operation(operation) => Some(operation),
+            _ => None,
+        })
+        .map(|operation| {
+            let fragment_names = transitive_fragment_names(operation.selection_set(), &fragments)
+                .into_iter()
+                .collect::<HashSet<_>>();
+
+            let definitions = document
+                .definitions
+                .iter()
+                .filter(|definition| match definition {
+                    Definition::Operation(other) => std::ptr::eq(other, operation),
+                    Definition::Fragment(fragment) => {
+                        fragment_names.contains(fragment.name.as_str())
+                    }
+                })
+                .cloned()
+                .collect();
+
+            Document { definitions }
+        })
+        .collect()
+}
+
+#[test]
+fn gets_operations_like_graphql_js() {
+    let parse = |source: &str| {
+        crate::parser::parse_query::<String>(source)
+            .expect("Failed to parse query")
+            .into_static()
+    };
+
+    let single = parse("fragment F on Query { a } query A { ...F }");
+    let multiple = parse("query A { a } mutation B { b } { c }");
+
+    assert_eq!(get_operation(&single, None).unwrap().node_name(), Some("A"));
+    assert_eq!(
+        get_operation(&single, Some("A")).unwrap().node_name(),
+        Some("A")
+    );
+    assert!(get_operation(&single, Some("B")).is_none());
+    assert!(get_operation(&multiple, None).is_none());
+    assert!(matches!(
+        get_operation(&multiple, Some("B")),
+        Some(OperationDefinition::Mutation(_))
+    ));
+    assert!(get_operation(&parse("fragment F on Query { a }"), None).is_none());
+}
+
+#[test]
+fn splits_operations_with_transitive_fragments() {
+    let document = crate::parser::parse_query::<String>(
+        "
+        query A { me { ...User } }
+        fragment Unused on User { id }
+        query B { node { ... on User { friends { ...Name } } } }
+        fragment User on User { ...Name friends { ...Avatar } }
+        fragment Name on User { name ...Name }
+        fragment Avatar on User { avatar }
+        { __typename }
+        ",
+    )
+    .expect("Failed to parse query")
+    .into_static();
+
+    let documents = split_operations(&document)
+        .iter()
+        .map(|document| {
+            crate::ast::print_operation(
+                document,
+                &crate::ast::PrintOperationOptions {
+                    minify: true,
+                    ..crate::ast::PrintOperationOptions::new()
+                },
+            )
+        })
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        documents,
+        vec![
+            "query A{me{...User}}fragment User on User{...Name friends{...Avatar}}fragment Name on User{name...Name}fragment Avatar on User{avatar}",
+            "query B{node{...on User{friends{...Name}}}}fragment Name on User{name...Name}",
+            "{__typename}",
+        ]
+    );
+
+    let fragments = document
+        .definitions
+        .iter()
+        .filter_map(|definition| match definition {
+            Definition::Fragment(fragment) => Some((fragment.name.as_str(), fragment)),
+            _ => None,
+        })
+        .collect::<HashMap<_, _>>();
+
+    assert_eq!(
+        transitive_fragment_names(
+            get_operation(&document, Some("A")).unwrap().selection_set(),
+            &fragments
+        ),
+        vec!["User", "Name", "Avatar"]
+    );
+}
@@ -0,0 +1,880 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::ast::{merge_type_extensions, InputValueHelpers, TypeDefinitionExtension};
+use crate::static_graphql::schema::{
+    Definition, DirectiveDefinition, Document, EnumValue, Field, InputValue, Type, TypeDefinition,
+    Value,
+};
+
+/// How a change affects the clients of a schema.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Criticality {
+    /// Operations that were valid may become invalid, or get errors at runtime.
+    Breaking,
+    /// Operations stay valid, but may behave differently (e.g. a new enum value clients don't
+    /// handle).
+    Dangerous,
+    /// Nothing changes for existing operations.
+    Safe,
+}
+
+impl fmt::Display for Criticality {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Criticality::Breaking => write!(f, "BREAKING"),
+            Criticality::Dangerous => write!(f, "DANGEROUS"),
+            Criticality::Safe => write!(f, "SAFE"),
+        }
+    }
+}
+
+/// What changed, in the terms of graphql-inspector.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SchemaChangeType {
+    TypeAdded,
+    TypeRemoved,
+    TypeKindChanged,
+    FieldAdded,
+    FieldRemoved,
+    FieldTypeChanged,
+    FieldArgumentAdded,
+    FieldArgumentRemoved,
+    FieldArgumentTypeChanged,
+    FieldArgumentDefaultValueChanged,
+    InputFieldAdded,
+    InputFieldRemoved,
+    InputFieldTypeChanged,
+    InputFieldDefaultValueChanged,
+    EnumValueAdded,
+    EnumValueRemoved,
+    UnionMemberAdded,
+    UnionMemberRemoved,
+    InterfaceAdded,
+    InterfaceRemoved,
+    DirectiveAdded,
+    DirectiveRemoved,
+    DirectiveRepeatableRemoved,
+    DirectiveLocationAdded,
+    DirectiveLocationRemoved,
+    DirectiveArgumentAdded,
+    DirectiveArgumentRemoved,
+    DirectiveArgumentTypeChanged,
+    DirectiveArgumentDefaultValueChanged,
+}
+
+/// A difference between two schemas, found by `diff_schemas`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SchemaChange {
+    pub change_type: SchemaChangeType,
+    pub criticality: Criticality,
+    /// The schema coordinate of the changed element, like `User`, `User.name`,
+    /// `Query.user(id:)`, `Role.ADMIN` or `@include(if:)`.
+    pub path: String,
+    pub message: String,
+}
+
+impl fmt::Display for SchemaChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{}] {}: {}", self.criticality, self.path, self.message)
+    }
+}
+
+/// Compares two schema documents, and returns the changes made to go from `old_schema` to
+/// `new_schema`: types, fields, arguments, input fields, enum values, union members, interfaces
+/// and directives that were added, removed or changed, along with their `Criticality`.
+///
+/// The criticality rules are the ones of `findBreakingChanges` in graphql-js and of
+/// graphql-inspector. Type extensions are merged before the schemas are compared.
+pub fn diff_schemas(old_schema: &Document, new_schema: &Document) -> Vec<SchemaChange> {
+    let old_schema = merge_type_extensions(old_schema);
+    let new_schema = merge_type_extensions(new_schema);
+    let mut changes = Vec::new();
+
+    let old_types = type_definitions(&old_schema);
+    let new_types = type_definitions(&new_schema);
+    let old_types_by_name = types_by_name(&old_types);
+    let new_types_by_name = types_by_name(&new_types);
+
+    for old_type in &old_types {
+        match new_types_by_name.get(old_type.name()) {
+            Some(new_type) => diff_type(old_type, new_type, &mut changes),
+            None => changes.push(change(
+                SchemaChangeType::TypeRemoved,
+                Criticality::Breaking,
+                old_type.name(),
+                format!("Type '{}' was removed", old_type.name()),
+            )),
+        }
+    }
+
+    for new_type in &new_types {
+        if !old_types_by_name.contains_key(new_type.name()) {
+            changes.push(change(
+                SchemaChangeType::TypeAdded,
+                Criticality::Safe,
+                new_type.name(),
+                format!("Type '{}' was added", new_type.name()),
+            ));
+        }
+    }
+
+    let old_directives = directive_definitions(&old_schema);
+    let new_directives = directive_definitions(&new_schema);
+
+    for old_directive in &old_directives {
+        match new_directives.iter().find(|d| d.name == old_directive.name) {
+            Some(new_directive) => diff_directive(old_directive, new_directive, &mut changes),
+            None => changes.push(change(
+                SchemaChangeType::DirectiveRemoved,
+                Criticality::Breaking,
+                format!("@{}", old_directive.name),
+                format!("Directive '{}' was removed", old_directive.name),
+            )),
+        }
+    }
+
+    for new_directive in &new_directives {
+        if !old_directives.iter().any(|d| d.name == new_directive.name) {
+            changes.push(change(
+                SchemaChangeType::DirectiveAdded,
+                Criticality::Safe,
+                format!("@{}", new_directive.name),
+                format!("Directive '{}' was added", new_directive.name),
+            ));
+        }
+    }
+
+    changes
+}
+
+fn change(
+    change_type: SchemaChangeType,
+    criticality: Criticality,
+    path: impl Into<String>,
+    message: String,
+) -> SchemaChange {
+    SchemaChange {
+        change_type,
+        criticality,
+        path: path.into(),
+        message,
+    }
+}
+
+fn type_definitions(document: &Document) -> Vec<&TypeDefinition> {
+    document
+        .definitions
+        .iter()
+        .filter_map(|definition| match definition {
+            Definition::TypeDefinition(type_def) => Some(type_def),
+            _ => None,
+        })
+        .collect()
+}
+
+fn types_by_name<'a>(types: &[&'a TypeDefinition]) -> HashMap<&'a str, &'a TypeDefinition> {
+    types
+        .iter()
+        .map(|type_def| (type_def.name(), *type_def))
+        .collect()
+}
+
+fn directive_definitions(document: &Document) -> Vec<&DirectiveDefinition> {
+    document
+        .definitions
+        .iter()
+        .filter_map(|definition| match definition {
+            Definition::DirectiveDefinition(directive) => Some(directive),
+            _ => None,
+        })
+        .collect()
+}
+
+fn type_kind(type_def: &TypeDefinition) -> &'static str {
+    match type_def {
+        TypeDefinition::Scalar(_) => "SCALAR",
+        TypeDefinition::Object(_) => "OBJECT",
+        TypeDefinition::Interface(_) => "INTERFACE",
+        TypeDefinition::Union(_) => "UNION",
+        TypeDefinition::Enum(_) => "ENUM",
+        TypeDefinition::InputObject(_) => "INPUT_OBJECT",
+    }
+}
+
+fn diff_type(
+    old_type: &TypeDefinition,
+    new_type: &TypeDefinition,
+    changes: &mut Vec<SchemaChange>,
+) {
+    match (old_type, new_type) {
+        (TypeDefinition::Object(old_object), TypeDefinition::Object(new_object)) => {
+            diff_interfaces(
+                &old_object.name,
+                "object type",
+                &old_object.implements_interfaces,
+                &new_object.implements_interfaces,
+                changes,
+            );
+            diff_fields(
+                &old_object.name,
+                "object type",
+                &old_object.fields,
+                &new_object.fields,
+                changes,
+            );
+        }
+        (TypeDefinition::Interface(old_interface), TypeDefinition::Interface(new_interface)) => {
+            diff_interfaces(
+                &old_interface.name,
+                "interface type",
+                &old_interface.implements_interfaces,
+                &new_interface.implements_interfaces,
+                changes,
+            );
+            diff_fields(
+                &old_interface.name,
+                "interface type",
+                &old_interface.fields,
+                &new_interface.fields,
+                changes,
+            );
+        }
+        (TypeDefinition::Union(old_union), TypeDefinition::Union(new_union)) => {
+            for member in &old_union.types {
+                if !new_union.types.contains(member) {
+                    changes.push(change(
+                        SchemaChangeType::UnionMemberRemoved,
+                        Criticality::Breaking,
+                        old_union.name.as_str(),
+                        format!(
+                            "Member '{}' was removed from union type '{}'",
+                            member, old_union.name
+                        ),
+                    ));
+                }
+            }
+
+            for member in &new_union.types {
+                if !old_union.types.contains(member) {
+                    changes.push(change(
+                        SchemaChangeType::UnionMemberAdded,
+                        Criticality::Dangerous,
+                        new_union.name.as_str(),
+                        format!(
+                            "Member '{}' was added to union type '{}'",
+                            member, new_union.name
+                        ),
+                    ));
+                }
+            }
+        }
+        (TypeDefinition::Enum(old_enum), TypeDefinition::Enum(new_enum)) => {
+            diff_enum_values(&old_enum.name, &old_enum.values, &new_enum.values, changes);
+        }
+        (TypeDefinition::InputObject(old_input), TypeDefinition::InputObject(new_input)) => {
+            diff_input_fields(
+                &old_input.name,
+                &old_input.fields,
+                &new_input.fields,
+                changes,
+            );
+        }
+        (TypeDefinition::Scalar(_), TypeDefinition::Scalar(_)) => {}
+        _ => changes.push(change(
+            SchemaChangeType::TypeKindChanged,
+            Criticality::Breaking,
+            old_type.name(),
+            format!(
+                "'{}' kind changed from '{}' to '{}'",
+                old_type.name(),
+                type_kind(old_type),
+                type_kind(new_type)
+            ),
+        )),
+    }
+}
+
+fn diff_interfaces(
+    type_name: &str,
+    kind: &str,
+    old_interfaces: &[String],
+    new_interfaces: &[String],
+    changes: &mut Vec<SchemaChange>,
+) {
+    for interface in old_interfaces {
+        if !new_interfaces.contains(interface) {
+            changes.push(change(
+                SchemaChangeType::InterfaceRemoved,
+                Criticality::Breaking,
+                type_name,
+                format!(
+                    "'{}' {} no longer implements '{}' interface",
+                    type_name, kind, interface
+                ),
+            ));
+        }
+    }
+
+    for interface in new_interfaces {
+        if !old_interfaces.contains(interface) {
+            changes.push(change(
+                SchemaChangeType::InterfaceAdded,
+                Criticality::Dangerous,
+                type_name,
+                format!(
+                    "'{}' {} implements '{}' interface",
+                    type_name, kind, interface
+                ),
+            ));
+        }
+    }
+}
+
+fn diff_fields(
+    type_name: &str,
+    kind: &str,
+    old_fields: &[Field],
+    new_fields: &[Field],
+    changes: &mut Vec<SchemaChange>,
+) {
+    for old_field in old_fields {
+        let path = format!("{}.{}", type_name, old_field.name);
+
+        let new_field = match new_fields.iter().find(|f| f.name == old_field.name) {
+            Some(new_field) => new_field,
+            None => {
+                changes.push(change(
+                    SchemaChangeType::FieldRemoved,
+                    Criticality::Breaking,
+                    path,
+                    format!(
+                        "Field '{}' was removed from {} '{}'",
+                        old_field.name, kind, type_name
+                    ),
+                ));
+                continue;
+            }
+        };
+
+        if old_field.field_type != new_field.field_type {
+            let criticality =
+                if is_safe_output_type_change(&old_field.field_type, &new_field.field_type) {
+                    Criticality::Safe
+                } else {
+                    Criticality::Breaking
+                };
+
+            changes.push(change(
+                SchemaChangeType::FieldTypeChanged,
+                criticality,
+                path.as_str(),
+                format!(
+                    "Field '{}' changed type from '{}' to '{}'",
+                    path, old_field.field_type, new_field.field_type
+                ),
+            ));
+        }
+
+        diff_arguments(
+            &ArgumentsOwner::Field(&path),
+            &old_field.arguments,
+            &new_field.arguments,
+            changes,
+        );
+    }
+
+    for new_field in new_fields {
+        if !old_fields.iter().any(|f| f.name == new_field.name) {
+            changes.push(change(
+                SchemaChangeType::FieldAdded,
+                Criticality::Safe,
+                format!("{}.{}", type_name, new_field.name),
+                format!(
+                    "Field '{}' was added to {} '{}'",
+                    new_field.name, kind, type_name
+                ),
+            ));
+        }
+    }
+}
+
+/// Field arguments and directive arguments are compared the same way, but are reported
+/// differently.
+enum ArgumentsOwner<'a> {
+    /// The coordinate of the field, like `Query.user`.
+    Field(&'a str),
+    /// The name of the directive.
+    Directive(&'a str),
+}
+
+impl<'a> ArgumentsOwner<'a> {
+    fn path(&self, argument: &str) -> String {
+        match self {
+            ArgumentsOwner::Field(field) => format!("{}({}:)", field, argument),
+            ArgumentsOwner::Directive(directive) => format!("@{}({}:)", directive, argument),
+        }
+    }
+
+    fn describe(&self) -> String {
+        match self {
+            ArgumentsOwner::Field(field) => format!("field '{}'", field),
+            ArgumentsOwner::Directive(directive) => format!("directive '{}'", directive),
+        }
+    }
+}
+
+fn diff_arguments(
+    owner: &ArgumentsOwner,
+    old_arguments: &[InputValue],
+    new_arguments: &[InputValue],
+    changes: &mut Vec<SchemaChange>,
+) {
+    let is_directive = matches!(owner, ArgumentsOwner::Directive(_));
+
+    for old_argument in old_arguments {
+        let path = owner.path(&old_argument.name);
+
+        let new_argument = match new_arguments.iter().find(|a| a.name == old_argument.name) {
+            Some(new_argument) => new_argument,
+            None => {
+                changes.push(change(
+                    if is_directive {
+                        SchemaChangeType::DirectiveArgumentRemoved
+                    } else {
+                        SchemaChangeType::FieldArgumentRemoved
+                    },
+                    Criticality::Breaking,
+                    path,
+                    format!(
+                        "Argument '{}' was removed from {}",
+                        old_argument.name,
+                        owner.describe()
+                    ),
+                ));
+                continue;
+            }
+        };
+
+        if old_argument.value_type != new_argument.value_type {
+            let criticality =
+                if is_safe_input_type_change(&old_argument.value_type, &new_argument.value_type) {
+                    Criticality::Safe
+                } else {
+                    Criticality::Breaking
+                };
+
+            changes.push(change(
+                if is_directive {
+                    SchemaChangeType::DirectiveArgumentTypeChanged
+                } else {
+                    SchemaChangeType::FieldArgumentTypeChanged
+                },
+                criticality,
+                path.as_str(),
+                format!(
+                    "Type for argument '{}' on {} changed from '{}' to '{}'",
+                    old_argument.name,
+                    owner.describe(),
+                    old_argument.value_type,
+                    new_argument.value_type
+                ),
+            ));
+        }
+
+        if old_argument.default_value != new_argument.default_value {
+            changes.push(change(
+                if is_directive {
+                    SchemaChangeType::DirectiveArgumentDefaultValueChanged
+                } else {
+                    SchemaChangeType::FieldArgumentDefaultValueChanged
+                },
+                Criticality::Dangerous,
+                path,
+                format!(
+                    "Default value for argument '{}' on {} changed from '{}' to '{}'",
+                    old_argument.name,
+                    owner.describe(),
+                    print_default_value(&old_argument.default_value),
+                    print_default_value(&new_argument.default_value)
+                ),
+            ));
+        }
+    }
+
+    for new_argument in new_arguments {
+        if old_arguments.iter().any(|a| a.name == new_argument.name) {
+            continue;
+        }
+
+        // Adding an optional argument to a field is dangerous: it may change what the field
+        // resolves to
+        let criticality = match (new_argument.is_required(), is_directive) {
+            (true, _) => Criticality::Breaking,
+            (false, true) => Criticality::Safe,
+            (false, false) => Criticality::Dangerous,
+        };
+
+        changes.push(change(
+            if is_directive {
+                SchemaChangeType::DirectiveArgumentAdded
+            } else {
+                SchemaChangeType::FieldArgumentAdded
+            },
+            criticality,
+            owner.path(&new_argument.name),
+            format!(
+                "Argument '{}: {}' was added to {}",
+                new_argument.name,
+                new_argument.value_type,
+                owner.describe()
+            ),
+        ));
+    }
+}
+
+fn diff_input_fields(
+    type_name: &str,
+    old_fields: &[InputValue],
+    new_fields: &[InputValue],
+    changes: &mut Vec<SchemaChange>,
+) {
+    for old_field in old_fields {
+        let path = format!("{}.{}", type_name, old_field.name);
+
+        let new_field = match new_fields.iter().find(|f| f.name == old_field.name) {
+            Some(new_field) => new_field,
+            None => {
+                changes.push(change(
+                    SchemaChangeType::InputFieldRemoved,
+                    Criticality::Breaking,
+                    path,
+                    format!(
+                        "Input field '{}' was removed from input object type '{}'",
+                        old_field.name, type_name
+                    ),
+                ));
+                continue;
+            }
+        };
+
+        if old_field.value_type != new_field.value_type {
+            let criticality =
+                if is_safe_input_type_change(&old_field.value_type, &new_field.value_type) {
+                    Criticality::Safe
+                } else {
+                    Criticality::Breaking
+                };
+
+            changes.push(change(
+                SchemaChangeType::InputFieldTypeChanged,
+                criticality,
+                path.as_str(),
+                format!(
+                    "Input field '{}' changed type from '{}' to '{}'",
+                    path, old_field.value_type, new_field.value_type
+                ),
+            ));
+        }
+
+        if old_field.default_value != new_field.default_value {
+            changes.push(change(
+                SchemaChangeType::InputFieldDefaultValueChanged,
+                Criticality::Dangerous,
+                path.as_str(),
+                format!(
+                    "Input field '{}' default value changed from '{}' to '{}'",
+                    path,
+                    print_default_value(&old_field.default_value),
+                    print_default_value(&new_field.default_value)
+                ),
+            ));
+        }
+    }
+
+    for new_field in new_fields {
+        if old_fields.iter().any(|f| f.name == new_field.name) {
+            continue;
+        }
+
+        let criticality = if new_field.is_required() {
+            Criticality::Breaking
+        } else {
+            Criticality::Dangerous
+        };
+
+        changes.push(change(
+            SchemaChangeType::InputFieldAdded,
+            criticality,
+            format!("{}.{}", type_name, new_field.name),
+            format!(
+                "Input field '{}: {}' was added to input object type '{}'",
+                new_field.name, new_field.value_type, type_name
+            ),
+        ));
+    }
+}
+
+fn diff_enum_values(
+    type_name: &str,
+    old_values: &[EnumValue],
+    new_values: &[EnumValue],
+    changes: &mut Vec<SchemaChange>,
+) {
+    for old_value in old_values {
+        if !new_values.iter().any(|v| v.name == old_value.name) {
+            changes.push(change(
+                SchemaChangeType::EnumValueRemoved,
+                Criticality::Breaking,
+                format!("{}.{}", type_name, old_value.name),
+                format!(
+                    "Enum value '{}' was removed from enum '{}'",
+                    old_value.name, type_name
+                ),
+            ));
+        }
+    }
+
+    for new_value in new_values {
+        if !old_values.iter().any(|v| v.name == new_value.name) {
+            changes.push(change(
+                SchemaChangeType::EnumValueAdded,
+                Criticality::Dangerous,
+                format!("{}.{}", type_name, new_value.name),
+                format!(
+                    "Enum value '{}' was added to enum '{}'",
+                    new_value.name, type_name
+                ),
+            ));
+        }
+    }
+}
+
+fn diff_directive(
+    old_directive: &DirectiveDefinition,
+    new_directive: &DirectiveDefinition,
+    changes: &mut Vec<SchemaChange>,
+) {
+    let path = format!("@{}", old_directive.name);
+
+    if old_directive.repeatable && !new_directive.repeatable {
+        changes.push(change(
+            SchemaChangeType::DirectiveRepeatableRemoved,
+            Criticality::Breaking,
+            path.as_str(),
+            format!(
+                "Repeatable flag was removed from directive '{}'",
+                old_directive.name
+            ),
+        ));
+    }
+
+    for location in &old_directive.locations {
+        if !new_directive.locations.contains(location) {
+            changes.push(change(
+                SchemaChangeType::DirectiveLocationRemoved,
+                Criticality::Breaking,
+                path.as_str(),
+                format!(
+                    "Location '{}' was removed from directive '{}'",
+                    location.as_str(),
+                    old_directive.name
+                ),
+            ));
+        }
+    }
+
+    for location in &new_directive.locations {
+        if !old_directive.locations.contains(location) {
+            changes.push(change(
+                SchemaChangeType::DirectiveLocationAdded,
+                Criticality::Safe,
+                path.as_str(),
+                format!(
+                    "Location '{}' was added to directive '{}'",
+                    location.as_str(),
+                    old_directive.name
+                ),
+            ));
+        }
+    }
+
+    diff_arguments(
+        &ArgumentsOwner::Directive(&old_directive.name),
+        &old_directive.arguments,
+        &new_directive.arguments,
+        changes,
+    );
+}
+
+fn print_default_value(default_value: &Option<Value>) -> String {
+    match default_value {
+        Some(value) => value.to_string(),
+        None => "undefined".to_string(),
+    }
+}
+
+/// Whether every value of `old_type` can still be returned as `new_type`: the new type can only
+/// be more strict about nulls.
+fn is_safe_output_type_change(old_type: &Type, new_type: &Type) -> bool {
+    match (old_type, new_type) {
+        (Type::NamedType(old_name), Type::NamedType(new_name)) => old_name == new_name,
+        (Type::ListType(old_of_type), Type::ListType(new_of_type)) => {
+            is_safe_output_type_change(old_of_type, new_of_type)
+        }
+        (Type::NonNullType(old_of_type), Type::NonNullType(new_of_type)) => {
+            is_safe_output_type_change(old_of_type, new_of_type)
+        }
+        (Type::NonNullType(_), _) => false,
+        (_, Type::NonNullType(new_of_type)) => is_safe_output_type_change(old_type, new_of_type),
+        _ => false,
+    }
+}
+
+/// Whether every value accepted by `old_type` is still accepted by `new_type`: the new type can
+/// only be less strict about nulls.
+fn is_safe_input_type_change(old_type: &Type, new_type: &Type) -> bool {
+    match (old_type, new_type) {
+        (Type::NamedType(old_name), Type::NamedType(new_name)) => old_name == new_name,
+        (Type::ListType(old_of_type), Type::ListType(new_of_type)) => {
+            is_safe_input_type_change(old_of_type, new_of_type)
+        }
+        (Type::NonNullType(old_of_type), Type::NonNullType(new_of_type)) => {
+            is_safe_input_type_change(old_of_type, new_of_type)
+        }
+        (Type::NonNullType(old_of_type), _) => is_safe_input_type_change(old_of_type, new_type),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+fn diff_test_schemas(old_schema: &str, new_schema: &str) -> Vec<String> {
+    let parse = |sdl: &str| {
+        crate::parser::parse_schema::<String>(sdl)
+            .expect("Failed to parse schema")
+            .into_static()
+    };
+
+    diff_schemas(&parse(old_schema), &parse(new_schema))
+        .iter()
+        .map(|change| change.to_string())
+        .collect()
+}
+
+#[test]
+fn reports_type_field_and_argument_changes() {
+    let changes = diff_test_schemas(
+        "
+        type Query {
+          user(id: ID!, name: String): User
+          users(first: Int = 10): [User]
+          node: Node
+        }
+        type User implements Node { id: ID! name: String email: String }
+        interface Node { id: ID! }
+        type Removed { id: ID }
+        scalar Changed
+        ",
+        "
+        type Query {
+          user(id: ID, region: String!): User!
+          users(first: Int = 20, after: String): [User!]
+          node: String
+        }
+        type User { id: ID! name: String! age: Int }
+        interface Node { id: ID! }
+        type Added { id: ID }
+        enum Changed { A }
+        ",
+    );
+
+    assert_eq!(
+        changes,
+        vec![
+            "[SAFE] Query.user: Field 'Query.user' changed type from 'User' to 'User!'",
+            "[SAFE] Query.user(id:): Type for argument 'id' on field 'Query.user' changed from 'ID!' to 'ID'",
+            "[BREAKING] Query.user(name:): Argument 'name' was removed from field 'Query.user'",
+            "[BREAKING] Query.user(region:): Argument 'region: String!' was added to field 'Query.user'",
+            "[SAFE] Query.users: Field 'Query.users' changed type from '[User]' to '[User!]'",
+            "[DANGEROUS] Query.users(first:): Default value for argument 'first' on field 'Query.users' changed from '10' to '20'",
+            "[DANGEROUS] Query.users(after:): Argument 'after: String' was added to field 'Query.users'",
+            "[BREAKING] Query.node: Field 'Query.node' changed type from 'Node' to 'String'",
+            "[BREAKING] User: 'User' object type no longer implements 'Node' interface",
+            "[SAFE] User.name: Field 'User.name' changed type from 'String' to 'String!'",
+            "[BREAKING] User.email: Field 'email' was removed from object type 'User'",
+            "[SAFE] User.age: Field 'age' was added to object type 'User'",
+            "[BREAKING] Removed: Type 'Removed' was removed",
+            "[BREAKING] Changed: 'Changed' kind changed from 'SCALAR' to 'ENUM'",
+            "[SAFE] Added: Type 'Added' was added",
+        ]
+    );
+}
+
+#[test]
+fn reports_input_enum_union_and_directive_changes() {
+    let changes = diff_test_schemas(
+        "
+        type Query { search(filter: Filter): Result }
+        input Filter { name: String! tags: [String] limit: Int = 5 }
+        enum Role { ADMIN USER }
+        union Result = Query | Other
+        type Other { id: ID }
+        type Third { id: ID }
+        directive @auth(role: Role) repeatable on FIELD_DEFINITION | OBJECT
+        directive @old on FIELD
+        ",
+        "
+        type Query { search(filter: Filter): Result }
+        input Filter { name: String tags: [String!] limit: Int required: Boolean! optional: Int }
+        enum Role { ADMIN GUEST }
+        union Result = Query | Third
+        type Other { id: ID }
+        type Third { id: ID }
+        directive @auth(role: Role!, scope: String) on FIELD_DEFINITION | INTERFACE
+        directive @new on FIELD
+        ",
+    );
+
+    assert_eq!(
+        changes,
+        vec![
+            "[SAFE] Filter.name: Input field 'Filter.name' changed type from 'String!' to 'String'",
+            "[BREAKING] Filter.tags: Input field 'Filter.tags' changed type from '[String]' to '[String!]'",
+            "[DANGEROUS] Filter.limit: Input field 'Filter.limit' default value changed from '5' to 'undefined'",
+            "[BREAKING] Filter.required: Input field 'required: Boolean!' was added to input object type 'Filter'",
+            "[DANGEROUS] Filter.optional: Input field 'optional: Int' was added to input object type 'Filter'",
+            "[BREAKING] Role.USER: Enum value 'USER' was removed from enum 'Role'",
+            "[DANGEROUS] Role.GUEST: Enum value 'GUEST' was added to enum 'Role'",
+            "[BREAKING] Result: Member 'Other' was removed from union type 'Result'",
+            "[DANGEROUS] Result: Member 'Third' was added to union type 'Result'",
+            "[BREAKING] @auth: Repeatable flag was removed from directive 'auth'",
+            "[BREAKING] @auth: Location 'OBJECT' was removed from directive 'auth'",
+            "[SAFE] @auth: Location 'INTERFACE' was added to directive 'auth'",
+            "[BREAKING] @auth(role:): Type for argument 'role' on directive 'auth' changed from 'Role' to 'Role!'",
+            "[SAFE] @auth(scope:): Argument 'scope: String' was added to directive 'auth'",
+            "[BREAKING] @old: Directive 'old' was removed",
+            "[SAFE] @new: Directive 'new' was added",
+        ]
+    );
+}
+
+#[test]
+fn finds_no_changes_between_identical_schemas() {
+    use crate::introspection::{build_client_schema, parse_introspection};
+
+    let json_file = std::fs::File::open("./src/introspection/test_files/github_introspection.json")
+        .expect("failed to open json file");
+    let introspection = parse_introspection(json_file).expect("failed to parse json");
+    let schema = build_client_schema(&introspection).expect("failed to build schema");
+
+    assert_eq!(diff_schemas(&schema, &schema), vec![]);
+
+    // Extensions are merged before comparing
+    assert_eq!(
+        diff_test_schemas(
+            "type Query { a: String b: Int }",
+            "type Query { a: String } extend type Query { b: Int }"
+        ),
+        Vec::<String>::new()
+    );
+}
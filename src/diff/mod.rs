@@ -0,0 +1,3 @@
+mod diff_schemas;
+
+pub use self::diff_schemas::*;
@@ -0,0 +1,674 @@
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::fmt;
+
+use serde::ser::{Serialize, Serializer};
+use serde_json::Value as JsonValue;
+
+use crate::ast::{
+    collect_fields_by_response_key, get_operation, FieldByNameExtension, GroupedFieldSet,
+    IntoSchema, OperationDefinitionExtension, OperationVisitorContext, Schema,
+    TypeDefinitionExtension, TypeExtension,
+};
+use crate::introspection::{introspection_from_schema, IntrospectionFromSchemaError};
+use crate::static_graphql::query::{self, Definition, OperationDefinition, SelectionSet};
+use crate::static_graphql::schema::{self, TypeDefinition};
+
+use super::response_validation::INTROSPECTION_SCHEMA;
+use super::{literal_to_json, CoercedVariableValues};
+
+/// An error preventing an introspection query from being executed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct IntrospectionExecutionError {
+    pub message: String,
+}
+
+impl IntrospectionExecutionError {
+    fn new(message: &str) -> Self {
+        Self {
+            message: message.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for IntrospectionExecutionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for IntrospectionExecutionError {}
+
+impl From<IntrospectionFromSchemaError> for IntrospectionExecutionError {
+    fn from(error: IntrospectionFromSchemaError) -> Self {
+        Self {
+            message: error.message,
+        }
+    }
+}
+
+/// A value of the `data` returned by `IntrospectionExecutor::execute`.
+///
+/// Objects keep their fields in the order of the selections, and are serialized in that order.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ResponseValue {
+    /// A scalar or enum value (or a list of them), or `null`.
+    Leaf(JsonValue),
+    List(Vec<ResponseValue>),
+    /// Fields by response key.
+    Object(Vec<(String, ResponseValue)>),
+}
+
+impl Serialize for ResponseValue {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self {
+            ResponseValue::Leaf(value) => value.serialize(serializer),
+            ResponseValue::List(items) => serializer.collect_seq(items),
+            ResponseValue::Object(fields) => {
+                serializer.collect_map(fields.iter().map(|(key, value)| (key, value)))
+            }
+        }
+    }
+}
+
+/// Serializes the value as JSON, with the fields of objects in order.
+impl fmt::Display for ResponseValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let json = serde_json::to_string(self).map_err(|_| fmt::Error)?;
+        f.write_str(&json)
+    }
+}
+
+/// Converts to a `serde_json` value. Unless `serde_json` is built with `preserve_order`, the
+/// fields of objects are sorted by name.
+impl From<ResponseValue> for JsonValue {
+    fn from(value: ResponseValue) -> Self {
+        match value {
+            ResponseValue::Leaf(value) => value,
+            ResponseValue::List(items) => {
+                JsonValue::Array(items.into_iter().map(JsonValue::from).collect())
+            }
+            ResponseValue::Object(fields) => JsonValue::Object(
+                fields
+                    .into_iter()
+                    .map(|(key, value)| (key, JsonValue::from(value)))
+                    .collect(),
+            ),
+        }
+    }
+}
+
+/// Resolves the introspection fields (`__schema`, `__type` and `__typename`) of operations
+/// against a schema.
+///
+/// The schema is indexed and its introspection result is computed once, in `new`, so build the
+/// executor once and reuse it for every request. Schemas using type extensions should be passed
+/// through `ast::merge_type_extensions` first.
+pub struct IntrospectionExecutor<'a> {
+    schema: Cow<'a, Schema<'a>>,
+    /// The `__schema` of the introspection result.
+    introspection: JsonValue,
+    /// Index of every type in `introspection["types"]`, by name.
+    type_indexes: HashMap<String, usize>,
+}
+
+impl<'a> IntrospectionExecutor<'a> {
+    pub fn new(schema: impl IntoSchema<'a>) -> Result<Self, IntrospectionExecutionError> {
+        let schema = schema.into_schema();
+        let introspection = serde_json::to_value(
+            introspection_from_schema(schema.document())?.__schema,
+        )
+        .map_err(|error| IntrospectionExecutionError {
+            message: format!("Failed to serialize the introspection result: {}", error),
+        })?;
+
+        let type_indexes = introspection["types"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .enumerate()
+            .filter_map(|(index, t)| Some((t["name"].as_str()?.to_string(), index)))
+            .collect();
+
+        Ok(Self {
+            schema,
+            introspection,
+            type_indexes,
+        })
+    }
+
+    /// Executes the operation `operation_name` of `document` (which should be validated), and
+    /// returns the `data` of the response, with only the introspection fields of the operation.
+    /// Other root fields are left out of the response, for the caller to resolve.
+    ///
+    /// `variables` are the coerced variable values of the operation, see
+    /// `coerce_variable_values`.
+    pub fn execute(
+        &self,
+        document: &query::Document,
+        operation_name: Option<&str>,
+        variables: &CoercedVariableValues,
+    ) -> Result<ResponseValue, IntrospectionExecutionError> {
+        let operation = select_operation(document, operation_name)?;
+
+        let root_type_key = match operation {
+            OperationDefinition::Query(_) | OperationDefinition::SelectionSet(_) => "queryType",
+            OperationDefinition::Mutation(_) => "mutationType",
+            OperationDefinition::Subscription(_) => "subscriptionType",
+        };
+        let root_type_name = self.introspection[root_type_key]["name"]
+            .as_str()
+            .ok_or_else(|| {
+                IntrospectionExecutionError::new(
+                    "Schema is not configured to execute this operation.",
+                )
+            })?;
+
+        let execution = Execution {
+            executor: self,
+            context: OperationVisitorContext::new(document, &*self.schema),
+            variables,
+        };
+
+        let root_type = self.schema.type_by_name(root_type_name).ok_or_else(|| {
+            IntrospectionExecutionError::new("Schema is not configured to execute this operation.")
+        })?;
+        let mut data = vec![];
+
+        for (response_key, fields) in execution.collect_fields(operation.selection_set(), root_type)
+        {
+            let field = &fields[0];
+            let value = match field.name.as_str() {
+                "__typename" => ResponseValue::Leaf(JsonValue::String(root_type_name.to_string())),
+                "__schema" => execution.resolve_object(
+                    &self.introspection,
+                    "__Schema",
+                    &merge_selection_sets(&fields),
+                ),
+                "__type" => match execution.argument(field, "name") {
+                    JsonValue::String(name) => match self.type_by_name(&name) {
+                        Some(type_value) => execution.resolve_object(
+                            type_value,
+                            "__Type",
+                            &merge_selection_sets(&fields),
+                        ),
+                        None => ResponseValue::Leaf(JsonValue::Null),
+                    },
+                    _ => ResponseValue::Leaf(JsonValue::Null),
+                },
+                _ => continue,
+            };
+
+            data.push((response_key, value));
+        }
+
+        Ok(ResponseValue::Object(data))
+    }
+
+    fn type_by_name(&self, name: &str) -> Option<&JsonValue> {
+        self.type_indexes
+            .get(name)
+            .map(|index| &self.introspection["types"][*index])
+    }
+
+    /// Introspection results reference types by name (like `{"kind": "OBJECT", "name": "User"}`),
+    /// except for lists and non-null types. Returns the full type for named type references.
+    fn resolve_type_reference<'v>(&'v self, type_ref: &'v JsonValue) -> &'v JsonValue {
+        match type_ref["kind"].as_str() {
+            Some("LIST") | Some("NON_NULL") => type_ref,
+            _ => type_ref["name"]
+                .as_str()
+                .and_then(|name| self.type_by_name(name))
+                .unwrap_or(type_ref),
+        }
+    }
+}
+
+/// Same as `IntrospectionExecutor::execute`, for a single operation.
+pub fn execute_introspection(
+    schema: &schema::Document,
+    document: &query::Document,
+    operation_name: Option<&str>,
+    variables: &CoercedVariableValues,
+) -> Result<ResponseValue, IntrospectionExecutionError> {
+    IntrospectionExecutor::new(schema)?.execute(document, operation_name, variables)
+}
+
+struct Execution<'a, 's> {
+    executor: &'a IntrospectionExecutor<'s>,
+    context: OperationVisitorContext<'a>,
+    variables: &'a CoercedVariableValues,
+}
+
+impl<'a, 's> Execution<'a, 's> {
+    /// The schema defining `type_name`: introspection types come from `INTROSPECTION_SCHEMA`
+    /// when the schema doesn't define them.
+    fn schema_for(&self, type_name: &str) -> &Schema<'a> {
+        let schema = self.context.indexed_schema();
+
+        if type_name.starts_with("__") && schema.type_by_name(type_name).is_none() {
+            &INTROSPECTION_SCHEMA
+        } else {
+            schema
+        }
+    }
+
+    /// Fields of `selection_set` that apply to `parent_type` and are not skipped, by response key.
+    fn collect_fields(
+        &self,
+        selection_set: &SelectionSet,
+        parent_type: &TypeDefinition,
+    ) -> GroupedFieldSet {
+        collect_fields_by_response_key(
+            self.schema_for(parent_type.name()),
+            parent_type,
+            selection_set,
+            &self.context.known_fragments,
+            self.variables,
+        )
+    }
+
+    fn value(&self, value: &query::Value) -> JsonValue {
+        match value {
+            query::Value::Variable(name) => {
+                self.variables.get(name).cloned().unwrap_or(JsonValue::Null)
+            }
+            value => literal_to_json(value),
+        }
+    }
+
+    fn argument(&self, field: &query::Field, argument: &str) -> JsonValue {
+        field
+            .arguments
+            .iter()
+            .find(|(name, _)| name == argument)
+            .map_or(JsonValue::Null, |(_, value)| self.value(value))
+    }
+
+    /// Resolves the selection of an introspection object (`__Schema`, `__Type`, `__Field`, ...)
+    /// on its value in the introspection result.
+    fn resolve_object(
+        &self,
+        value: &JsonValue,
+        type_name: &str,
+        selection_set: &SelectionSet,
+    ) -> ResponseValue {
+        let value = match type_name {
+            "__Type" => self.executor.resolve_type_reference(value),
+            _ => value,
+        };
+
+        let type_def = match self.schema_for(type_name).type_by_name(type_name) {
+            Some(type_def) => type_def,
+            None => return ResponseValue::Leaf(JsonValue::Null),
+        };
+        let mut result = vec![];
+
+        for (response_key, fields) in self.collect_fields(selection_set, type_def) {
+            let field = &fields[0];
+
+            if field.name == "__typename" {
+                result.push((
+                    response_key,
+                    ResponseValue::Leaf(JsonValue::String(type_name.to_string())),
+                ));
+                continue;
+            }
+
+            let field_def = match type_def.field_by_name(&field.name) {
+                Some(field_def) => field_def,
+                None => continue,
+            };
+
+            let field_value = &value[field.name.as_str()];
+            let field_type = field_def.field_type.inner_type();
+
+            let resolved = match self.schema_for(field_type).type_by_name(field_type) {
+                Some(TypeDefinition::Object(_)) => {
+                    let selection_set = merge_selection_sets(&fields);
+                    let include_deprecated = field_def
+                        .arguments
+                        .iter()
+                        .any(|argument| argument.name == "includeDeprecated")
+                        .then(|| {
+                            self.argument(field, "includeDeprecated") == JsonValue::Bool(true)
+                        });
+
+                    match field_value {
+                        JsonValue::Array(items) => ResponseValue::List(
+                            items
+                                .iter()
+                                .filter(|item| {
+                                    include_deprecated != Some(false)
+                                        || item["isDeprecated"] != JsonValue::Bool(true)
+                                })
+                                .map(|item| self.resolve_object(item, field_type, &selection_set))
+                                .collect(),
+                        ),
+                        JsonValue::Object(_) => {
+                            self.resolve_object(field_value, field_type, &selection_set)
+                        }
+                        _ => ResponseValue::Leaf(JsonValue::Null),
+                    }
+                }
+                _ => ResponseValue::Leaf(field_value.clone()),
+            };
+
+            result.push((response_key, resolved));
+        }
+
+        ResponseValue::Object(result)
+    }
+}
+
+/// The sub-selections of fields sharing a response key, as one selection set.
+pub(super) fn merge_selection_sets(fields: &[query::Field]) -> SelectionSet {
+    SelectionSet {
+        span: fields[0].selection_set.span,
+        items: fields
+            .iter()
+            .flat_map(|field| field.selection_set.items.iter().cloned())
+            .collect(),
+    }
+}
+
+fn select_operation<'d>(
+    document: &'d query::Document,
+    operation_name: Option<&str>,
+) -> Result<&'d OperationDefinition, IntrospectionExecutionError> {
+    get_operation(document, operation_name).ok_or_else(|| match operation_name {
+        Some(operation_name) => IntrospectionExecutionError {
+            message: format!("Unknown operation named \"{}\".", operation_name),
+        },
+        None if document
+            .definitions
+            .iter()
+            .any(|definition| matches!(definition, Definition::Operation(_))) =>
+        {
+            IntrospectionExecutionError::new(
+                "Must provide operation name if query contains multiple operations.",
+            )
+        }
+        None => IntrospectionExecutionError::new("Must provide an operation."),
+    })
+}
+
+#[cfg(test)]
+static INTROSPECTION_TEST_SCHEMA: &str = "
+type Query {
+  user(id: ID!): User
+  node(id: ID!): Node
+}
+
+interface Node { id: ID! }
+
+type User implements Node {
+  id: ID!
+  name: String @deprecated(reason: \"Use `fullName`.\")
+  fullName: String
+  role(asOf: String): Role
+}
+
+enum Role { ADMIN USER GUEST @deprecated }
+";
+
+#[cfg(test)]
+fn execute_test_introspection(
+    operation: &str,
+    operation_name: Option<&str>,
+    variables: serde_json::Value,
+) -> Result<ResponseValue, IntrospectionExecutionError> {
+    let schema = crate::parser::parse_schema::<String>(INTROSPECTION_TEST_SCHEMA)
+        .expect("Failed to parse schema")
+        .into_static();
+    let document = crate::parser::parse_query::<String>(operation)
+        .expect("Failed to parse operation")
+        .into_static();
+    let variables = match variables {
+        JsonValue::Object(variables) => variables,
+        _ => serde_json::Map::new(),
+    };
+
+    execute_introspection(&schema, &document, operation_name, &variables)
+}
+
+#[test]
+fn resolves_introspection_fields_only() {
+    let data = execute_test_introspection(
+        "
+        {
+          __typename
+          user(id: 1) { name }
+          __schema { queryType { name kind } mutationType { name } }
+          userType: __type(name: \"User\") {
+            name
+            kind
+            interfaces { name }
+            fields { name type { kind name ofType { kind name } } }
+          }
+          missing: __type(name: \"Missing\") { name }
+        }",
+        None,
+        serde_json::json!({}),
+    )
+    .unwrap();
+
+    assert_eq!(
+        JsonValue::from(data),
+        serde_json::json!({
+          "__typename": "Query",
+          "__schema": {
+            "queryType": { "name": "Query", "kind": "OBJECT" },
+            "mutationType": null
+          },
+          "userType": {
+            "name": "User",
+            "kind": "OBJECT",
+            "interfaces": [{ "name": "Node" }],
+            "fields": [
+              { "name": "id", "type": { "kind": "NON_NULL", "name": null, "ofType": { "kind": "SCALAR", "name": "ID" } } },
+              { "name": "fullName", "type": { "kind": "SCALAR", "name": "String", "ofType": null } },
+              { "name": "role", "type": { "kind": "ENUM", "name": "Role", "ofType": null } }
+            ]
+          },
+          "missing": null
+        })
+    );
+}
+
+#[test]
+fn resolves_fragments_aliases_and_variables() {
+    let data = execute_test_introspection(
+        "
+        query Other { __typename }
+
+        query Introspect($name: String!, $all: Boolean = false) {
+          ...SchemaFields
+          role: __type(name: $name) {
+            ... on __Type { kind }
+            ... @skip(if: $all) { name }
+            __typename
+            values: enumValues(includeDeprecated: $all) { name isDeprecated deprecationReason }
+            enumValues { name }
+            fields { name }
+          }
+          node: __type(name: \"Node\") { possibleTypes { name } }
+          skipped: __typename @skip(if: true)
+        }
+
+        fragment SchemaFields on Query {
+          __schema { directives { name isRepeatable args { name defaultValue } } }
+        }",
+        Some("Introspect"),
+        serde_json::json!({ "name": "Role", "all": true }),
+    )
+    .unwrap();
+
+    assert_eq!(
+        JsonValue::from(data),
+        serde_json::json!({
+          "__schema": {
+            "directives": [
+              { "name": "include", "isRepeatable": false, "args": [{ "name": "if", "defaultValue": null }] },
+              { "name": "skip", "isRepeatable": false, "args": [{ "name": "if", "defaultValue": null }] },
+              { "name": "deprecated", "isRepeatable": false, "args": [{ "name": "reason", "defaultValue": "\"No longer supported\"" }] },
+              { "name": "specifiedBy", "isRepeatable": false, "args": [{ "name": "url", "defaultValue": null }] }
+            ]
+          },
+          "role": {
+            "kind": "ENUM",
+            "__typename": "__Type",
+            "values": [
+              { "name": "ADMIN", "isDeprecated": false, "deprecationReason": null },
+              { "name": "USER", "isDeprecated": false, "deprecationReason": null },
+              { "name": "GUEST", "isDeprecated": true, "deprecationReason": "No longer supported" }
+            ],
+            "enumValues": [{ "name": "ADMIN" }, { "name": "USER" }],
+            "fields": null
+          },
+          "node": { "possibleTypes": [{ "name": "User" }] }
+        })
+    );
+}
+
+#[test]
+fn keeps_the_order_of_the_selections() {
+    let data = execute_test_introspection(
+        "
+        {
+          zType: __type(name: \"User\") { name kind }
+          __typename
+          aSchema: __schema { queryType { name kind } }
+        }",
+        None,
+        serde_json::json!({}),
+    )
+    .unwrap();
+
+    assert_eq!(
+        data.to_string(),
+        r#"{"zType":{"name":"User","kind":"OBJECT"},"__typename":"Query","aSchema":{"queryType":{"name":"Query","kind":"OBJECT"}}}"#
+    );
+}
+
+#[test]
+fn executes_operations_against_a_prebuilt_schema() {
+    let document = crate::parser::parse_schema::<String>(INTROSPECTION_TEST_SCHEMA)
+        .expect("Failed to parse schema")
+        .into_static();
+    let schema = Schema::new(&document);
+    let executor = IntrospectionExecutor::new(&schema).expect("failed to build executor");
+
+    for (operation, expected) in [
+        ("{ __typename }", r#"{"__typename":"Query"}"#),
+        (
+            "{ __type(name: \"Role\") { ... on __Type { name } } }",
+            r#"{"__type":{"name":"Role"}}"#,
+        ),
+    ] {
+        let operation = crate::parser::parse_query::<String>(operation)
+            .expect("Failed to parse operation")
+            .into_static();
+        let data = executor
+            .execute(&operation, None, &serde_json::Map::new())
+            .expect("failed to execute");
+
+        assert_eq!(data.to_string(), expected);
+    }
+}
+
+#[test]
+fn selects_the_operation_to_execute() {
+    let operations = "query A { __typename } query B { __typename }";
+
+    assert_eq!(
+        execute_test_introspection(operations, None, serde_json::json!({})),
+        Err(IntrospectionExecutionError::new(
+            "Must provide operation name if query contains multiple operations."
+        ))
+    );
+    assert_eq!(
+        execute_test_introspection(operations, Some("C"), serde_json::json!({})),
+        Err(IntrospectionExecutionError::new(
+            "Unknown operation named \"C\"."
+        ))
+    );
+    assert_eq!(
+        execute_test_introspection(operations, Some("B"), serde_json::json!({})),
+        Ok(ResponseValue::Object(vec![(
+            "__typename".to_string(),
+            ResponseValue::Leaf(serde_json::json!("Query"))
+        )]))
+    );
+    assert_eq!(
+        execute_test_introspection("mutation { __typename }", None, serde_json::json!({})),
+        Err(IntrospectionExecutionError::new(
+            "Schema is not configured to execute this operation."
+        ))
+    );
+}
+
+#[test]
+fn executes_full_introspection_query() {
+    use crate::introspection::{build_client_schema, parse_introspection};
+
+    let json_file = std::fs::File::open("./src/introspection/test_files/github_introspection.json")
+        .expect("failed to open json file");
+    let introspection = parse_introspection(json_file).expect("failed to parse json");
+    let schema = build_client_schema(&introspection).expect("failed to build schema");
+    let executor = IntrospectionExecutor::new(&schema).expect("failed to build executor");
+
+    let document = crate::parser::parse_query::<String>(
+        "
+        query IntrospectionQuery {
+          __schema {
+            queryType { name }
+            mutationType { name }
+            types { ...FullType }
+            directives { name locations args { ...InputValue } }
+          }
+        }
+        fragment FullType on __Type {
+          kind
+          name
+          fields(includeDeprecated: true) { name args { ...InputValue } type { ...TypeRef } isDeprecated deprecationReason }
+          inputFields { ...InputValue }
+          interfaces { ...TypeRef }
+          enumValues(includeDeprecated: true) { name isDeprecated deprecationReason }
+          possibleTypes { ...TypeRef }
+        }
+        fragment InputValue on __InputValue { name type { ...TypeRef } defaultValue }
+        fragment TypeRef on __Type {
+          kind
+          name
+          ofType { kind name ofType { kind name ofType { kind name ofType { kind name } } } }
+        }
+        ",
+    )
+    .expect("Failed to parse operation")
+    .into_static();
+
+    let data = executor
+        .execute(&document, None, &serde_json::Map::new())
+        .expect("failed to execute");
+
+    // The result is an introspection result on its own, describing the same schema
+    let executed = crate::introspection::parse_introspection_from_string(&data.to_string())
+        .expect("failed to parse executed introspection");
+    let rebuilt = build_client_schema(&executed).expect("failed to build schema");
+
+    assert_eq!(
+        crate::introspection::introspection_from_schema(&rebuilt)
+            .unwrap()
+            .__schema
+            .types
+            .len(),
+        introspection.__schema.types.len()
+    );
+    let data = JsonValue::from(data);
+    assert_eq!(data["__schema"]["queryType"]["name"], "Query");
+    assert_eq!(data["__schema"]["mutationType"]["name"], "Mutation");
+}
@@ -0,0 +1,7 @@
+pub mod introspection;
+pub mod response_validation;
+pub mod variables;
+
+pub use self::introspection::*;
+pub use self::response_validation::*;
+pub use self::variables::*;
@@ -0,0 +1,699 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+use lazy_static::lazy_static;
+use serde_json::{Map, Value as JsonValue};
+
+use crate::ast::{
+    collect_fields_by_response_key, field_definition, get_operation, IntoSchema,
+    OperationDefinitionExtension, Schema, SchemaDocumentExtension, TypeDefinitionExtension,
+};
+use crate::introspection::{is_built_in_scalar, BUILT_INS};
+use crate::static_graphql::query::{self, Definition, OperationDefinition, SelectionSet, Type};
+use crate::static_graphql::schema::{self, TypeDefinition};
+
+use super::introspection::merge_selection_sets;
+use super::variables::print_path;
+use super::{CoercedVariableValues, PathSegment};
+
+/// A mismatch between the `data` of a response and the operation it answers.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResponseValidationError {
+    /// Path to the mismatched value, from `data` (`["user", "friends", 0, "name"]`).
+    pub path: Vec<PathSegment>,
+    pub message: String,
+}
+
+impl fmt::Display for ResponseValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.path.is_empty() {
+            f.write_str(&self.message)
+        } else {
+            write!(f, "{}: {}", print_path(&self.path), self.message)
+        }
+    }
+}
+
+/// Checks that `data` (the `data` of a response, for mocks or subgraph tests) has the shape of
+/// the operation `operation_name` of `document`:
+///
+/// - objects have a key for each selected field, named after its alias, and no other key
+/// - values match the type of their field: built-in scalars, enum values, objects and lists, and
+///   `null` only for nullable types (custom scalars accept any value)
+/// - for interfaces and unions, the selected `__typename` is a possible type, and the object is
+///   checked against the fields selected on that type
+/// - `__schema` and `__type` on the query type are checked against the introspection types,
+///   whether the schema defines them or not
+///
+/// `@skip` and `@include` are applied with `variables`, the coerced variable values of the
+/// operation. Returns every mismatch found, with its path.
+pub fn validate_response<'a>(
+    schema: impl IntoSchema<'a>,
+    document: &query::Document,
+    operation_name: Option<&str>,
+    variables: &CoercedVariableValues,
+    data: &JsonValue,
+) -> Vec<ResponseValidationError> {
+    let schema = schema.into_schema();
+    let operation = match get_operation(document, operation_name) {
+        Some(operation) => operation,
+        None => {
+            return vec![ResponseValidationError {
+                path: vec![],
+                message: match operation_name {
+                    Some(operation_name) => {
+                        format!("Unknown operation named \"{}\".", operation_name)
+                    }
+                    None => "Must provide a single operation, or an operation name.".to_string(),
+                },
+            }]
+        }
+    };
+
+    let schema_definition = schema.schema_definition();
+    let root_type_name = match operation {
+        OperationDefinition::Query(_) | OperationDefinition::SelectionSet(_) => {
+            Some(schema_definition.query.as_deref().unwrap_or("Query"))
+        }
+        OperationDefinition::Mutation(_) => {
+            Some(schema_definition.mutation.as_deref().unwrap_or("Mutation"))
+        }
+        OperationDefinition::Subscription(_) => Some(
+            schema_definition
+                .subscription
+                .as_deref()
+                .unwrap_or("Subscription"),
+        ),
+    };
+    let root_type = match root_type_name.and_then(|name| schema.type_by_name(name)) {
+        Some(root_type) => root_type,
+        None => {
+            return vec![ResponseValidationError {
+                path: vec![],
+                message: "Schema is not configured to execute this operation.".to_string(),
+            }]
+        }
+    };
+
+    let validation = ResponseValidation {
+        schema: &schema,
+        query_type_name: schema_definition.query.as_deref().unwrap_or("Query"),
+        fragments: document
+            .definitions
+            .iter()
+            .filter_map(|definition| match definition {
+                Definition::Fragment(fragment) => Some((fragment.name.as_str(), fragment)),
+                _ => None,
+            })
+            .collect(),
+        variables,
+    };
+    let mut errors = vec![];
+
+    validation.validate_object(
+        data,
+        root_type,
+        operation.selection_set(),
+        &mut vec![],
+        &mut errors,
+    );
+
+    errors
+}
+
+lazy_static! {
+    /// The introspection types (`__Schema`, `__Type`, ...), for schemas that don't define them.
+    pub(super) static ref INTROSPECTION_SCHEMA: Schema<'static> = Schema::new(&BUILT_INS);
+    /// `__schema: __Schema!`, on the query type.
+    static ref SCHEMA_FIELD: schema::Field = schema::Field {
+        position: Default::default(),
+        description: None,
+        name: "__schema".to_string(),
+        arguments: vec![],
+        field_type: Type::NonNullType(Box::new(Type::NamedType("__Schema".to_string()))),
+        directives: vec![],
+    };
+    /// `__type(name: String!): __Type`, on the query type.
+    static ref TYPE_FIELD: schema::Field = schema::Field {
+        position: Default::default(),
+        description: None,
+        name: "__type".to_string(),
+        arguments: vec![schema::InputValue {
+            position: Default::default(),
+            description: None,
+            name: "name".to_string(),
+            value_type: Type::NonNullType(Box::new(Type::NamedType("String".to_string()))),
+            default_value: None,
+            directives: vec![],
+        }],
+        field_type: Type::NamedType("__Type".to_string()),
+        directives: vec![],
+    };
+}
+
+struct ResponseValidation<'s, 'd> {
+    schema: &'s Schema<'s>,
+    query_type_name: &'s str,
+    fragments: HashMap<&'d str, &'d query::FragmentDefinition>,
+    variables: &'d CoercedVariableValues,
+}
+
+impl<'s, 'd> ResponseValidation<'s, 'd> {
+    /// The schema defining `type_name`: introspection types come from `INTROSPECTION_SCHEMA`
+    /// when the schema doesn't define them.
+    fn schema_for(&self, type_name: &str) -> &Schema<'s> {
+        if type_name.starts_with("__") && self.schema.type_by_name(type_name).is_none() {
+            &INTROSPECTION_SCHEMA
+        } else {
+            self.schema
+        }
+    }
+
+    /// Like `ast::field_definition`, with the `__schema` and `__type` fields of the query type.
+    fn field_definition<'t>(
+        &self,
+        object_type: &'t TypeDefinition,
+        field_name: &str,
+    ) -> Option<&'t schema::Field> {
+        match field_name {
+            "__schema" if object_type.name() == self.query_type_name => Some(&SCHEMA_FIELD),
+            "__type" if object_type.name() == self.query_type_name => Some(&TYPE_FIELD),
+            _ => field_definition(object_type, field_name),
+        }
+    }
+
+    fn validate_value(
+        &self,
+        value: &JsonValue,
+        value_type: &Type,
+        fields: &[query::Field],
+        path: &mut Vec<PathSegment>,
+        errors: &mut Vec<ResponseValidationError>,
+    ) {
+        match value_type {
+            Type::NonNullType(inner_type) => {
+                if value.is_null() {
+                    report(
+                        errors,
+                        path,
+                        format!(
+                            "Expected non-nullable type \"{}\" not to be null.",
+                            value_type
+                        ),
+                    );
+                    return;
+                }
+
+                self.validate_value(value, inner_type, fields, path, errors)
+            }
+            _ if value.is_null() => {}
+            Type::ListType(item_type) => match value {
+                JsonValue::Array(items) => {
+                    for (index, item) in items.iter().enumerate() {
+                        path.push(PathSegment::Index(index));
+                        self.validate_value(item, item_type, fields, path, errors);
+                        path.pop();
+                    }
+                }
+                _ => report(
+                    errors,
+                    path,
+                    format!("Expected a list of type \"{}\", got {}.", value_type, value),
+                ),
+            },
+            Type::NamedType(type_name) => {
+                match self.schema_for(type_name).type_by_name(type_name) {
+                    None if !is_built_in_scalar(type_name) => {
+                        report(errors, path, format!("Unknown type \"{}\".", type_name))
+                    }
+                    // Built-in scalars don't have to be defined in the schema
+                    None | Some(TypeDefinition::Scalar(_)) => {
+                        if !is_valid_built_in_scalar(type_name, value) {
+                            report(
+                                errors,
+                                path,
+                                format!(
+                                    "Expected a value of type \"{}\", got {}.",
+                                    type_name, value
+                                ),
+                            );
+                        }
+                    }
+                    Some(TypeDefinition::Enum(enum_type)) => {
+                        let is_enum_value = value
+                            .as_str()
+                            .is_some_and(|value| enum_type.values.iter().any(|v| v.name == value));
+
+                        if !is_enum_value {
+                            report(
+                                errors,
+                                path,
+                                format!(
+                                    "Expected a value of enum \"{}\", got {}.",
+                                    type_name, value
+                                ),
+                            );
+                        }
+                    }
+                    Some(type_def @ TypeDefinition::Object(_)) => self.validate_object(
+                        value,
+                        type_def,
+                        &merge_selection_sets(fields),
+                        path,
+                        errors,
+                    ),
+                    Some(type_def @ (TypeDefinition::Interface(_) | TypeDefinition::Union(_))) => {
+                        self.validate_abstract_value(
+                            value,
+                            type_def,
+                            &merge_selection_sets(fields),
+                            path,
+                            errors,
+                        )
+                    }
+                    Some(TypeDefinition::InputObject(_)) => report(
+                        errors,
+                        path,
+                        format!(
+                            "Input object type \"{}\" can't be an output type.",
+                            type_name
+                        ),
+                    ),
+                }
+            }
+        }
+    }
+
+    fn validate_object(
+        &self,
+        value: &JsonValue,
+        object_type: &TypeDefinition,
+        selection_set: &SelectionSet,
+        path: &mut Vec<PathSegment>,
+        errors: &mut Vec<ResponseValidationError>,
+    ) {
+        let object = match value {
+            JsonValue::Object(object) => object,
+            _ => {
+                return report(
+                    errors,
+                    path,
+                    format!(
+                        "Expected an object of type \"{}\", got {}.",
+                        object_type.name(),
+                        value
+                    ),
+                )
+            }
+        };
+
+        let grouped_field_set = collect_fields_by_response_key(
+            self.schema_for(object_type.name()),
+            object_type,
+            selection_set,
+            &self.fragments,
+            self.variables,
+        );
+
+        for (response_key, fields) in grouped_field_set.iter() {
+            let field_name = fields[0].name.as_str();
+            path.push(PathSegment::Key(response_key.to_string()));
+
+            match (
+                object.get(response_key),
+                self.field_definition(object_type, field_name),
+            ) {
+                (None, _) => report(errors, path, format!("Missing field \"{}\".", response_key)),
+                (Some(_), None) => report(
+                    errors,
+                    path,
+                    format!(
+                        "Field \"{}\" is not defined on type \"{}\".",
+                        field_name,
+                        object_type.name()
+                    ),
+                ),
+                (Some(field_value), Some(_)) if field_name == "__typename" => {
+                    if field_value.as_str() != Some(object_type.name()) {
+                        report(
+                            errors,
+                            path,
+                            format!(
+                                "Expected \"{}\" as __typename, got {}.",
+                                object_type.name(),
+                                field_value
+                            ),
+                        );
+                    }
+                }
+                (Some(field_value), Some(field_def)) => {
+                    self.validate_value(field_value, &field_def.field_type, fields, path, errors)
+                }
+            }
+
+            path.pop();
+        }
+
+        let response_keys = grouped_field_set.response_keys().collect::<HashSet<_>>();
+
+        for key in object.keys() {
+            if !response_keys.contains(key.as_str()) {
+                path.push(PathSegment::Key(key.clone()));
+                report(errors, path, format!("Unexpected field \"{}\".", key));
+                path.pop();
+            }
+        }
+    }
+
+    /// Objects of interfaces and unions are checked against their runtime type, found with the
+    /// selected `__typename`. When `__typename` isn't selected, the object has to match one of
+    /// the possible types.
+    fn validate_abstract_value(
+        &self,
+        value: &JsonValue,
+        abstract_type: &TypeDefinition,
+        selection_set: &SelectionSet,
+        path: &mut Vec<PathSegment>,
+        errors: &mut Vec<ResponseValidationError>,
+    ) {
+        let object = match value {
+            JsonValue::Object(object) => object,
+            _ => {
+                return report(
+                    errors,
+                    path,
+                    format!(
+                        "Expected an object of type \"{}\", got {}.",
+                        abstract_type.name(),
+                        value
+                    ),
+                )
+            }
+        };
+
+        let possible_types = self
+            .schema
+            .possible_types(abstract_type)
+            .iter()
+            .filter_map(|object_type| self.schema.type_by_name(&object_type.name))
+            .collect::<Vec<_>>();
+        let mut typename = None;
+
+        for possible_type in &possible_types {
+            match self.selected_typename(object, possible_type, selection_set) {
+                Some(selected) if selected.as_str() == Some(possible_type.name()) => {
+                    return self.validate_object(value, possible_type, selection_set, path, errors)
+                }
+                Some(selected) => typename = Some(selected),
+                None => {}
+            }
+        }
+
+        if let Some(typename) = typename {
+            return report(
+                errors,
+                path,
+                format!(
+                    "Expected a possible type of \"{}\" as __typename, got {}.",
+                    abstract_type.name(),
+                    typename
+                ),
+            );
+        }
+
+        let matches_possible_type = possible_types.iter().any(|possible_type| {
+            let mut possible_type_errors = vec![];
+            self.validate_object(
+                value,
+                possible_type,
+                selection_set,
+                path,
+                &mut possible_type_errors,
+            );
+
+            possible_type_errors.is_empty()
+        });
+
+        if !matches_possible_type {
+            report(
+                errors,
+                path,
+                format!(
+                    "Expected an object of a possible type of \"{}\", got {}.",
+                    abstract_type.name(),
+                    value
+                ),
+            );
+        }
+    }
+
+    /// The value of `__typename` in `object`, when it's selected on `object_type`.
+    fn selected_typename<'v>(
+        &self,
+        object: &'v Map<String, JsonValue>,
+        object_type: &TypeDefinition,
+        selection_set: &SelectionSet,
+    ) -> Option<&'v JsonValue> {
+        collect_fields_by_response_key(
+            self.schema_for(object_type.name()),
+            object_type,
+            selection_set,
+            &self.fragments,
+            self.variables,
+        )
+        .iter()
+        .find(|(_, fields)| fields[0].name == "__typename")
+        .and_then(|(response_key, _)| object.get(response_key))
+    }
+}
+
+fn report(errors: &mut Vec<ResponseValidationError>, path: &[PathSegment], message: String) {
+    errors.push(ResponseValidationError {
+        path: path.to_vec(),
+        message,
+    });
+}
+
+/// Checks the serialized values of the built-in scalars. Custom scalars accept any value.
+fn is_valid_built_in_scalar(type_name: &str, value: &JsonValue) -> bool {
+    match type_name {
+        "Int" => value
+            .as_i64()
+            .is_some_and(|value| i32::try_from(value).is_ok()),
+        "Float" => value.is_number(),
+        "String" | "ID" => value.is_string(),
+        "Boolean" => value.is_boolean(),
+        _ => true,
+    }
+}
+
+#[cfg(test)]
+static RESPONSE_TEST_SCHEMA: &str = "
+  type Query { me: User! search: [SearchResult!]! node(id: ID!): Node count: Int }
+  interface Node { id: ID! }
+  type User implements Node { id: ID! name: String role: Role! friends: [User] score: Float }
+  type Post implements Node { id: ID! title: String! meta: JSON }
+  union SearchResult = User | Post
+  enum Role { ADMIN USER }
+  scalar JSON
+";
+
+#[cfg(test)]
+fn validate_test_response(
+    operation: &str,
+    variables: serde_json::Value,
+    data: serde_json::Value,
+) -> Vec<String> {
+    let schema = crate::parser::parse_schema::<String>(RESPONSE_TEST_SCHEMA)
+        .expect("Failed to parse schema")
+        .into_static();
+    let document = crate::parser::parse_query::<String>(operation)
+        .expect("Failed to parse query")
+        .into_static();
+
+    validate_response(
+        &schema,
+        &document,
+        None,
+        variables.as_object().unwrap(),
+        &data,
+    )
+    .iter()
+    .map(|error| error.to_string())
+    .collect()
+}
+
+#[test]
+fn accepts_matching_responses() {
+    let errors = validate_test_response(
+        "
+        query($withFriends: Boolean!) {
+          viewer: me { id name role friends @include(if: $withFriends) { id } ...Score }
+          search { kind: __typename ... on Post { title meta } ... on User { name } }
+          node(id: \"1\") { id }
+          count
+        }
+
+        fragment Score on User { score }
+        ",
+        serde_json::json!({ "withFriends": false }),
+        serde_json::json!({
+          "viewer": { "id": "1", "name": null, "role": "ADMIN", "score": 1 },
+          "search": [
+            { "kind": "Post", "title": "Hello", "meta": { "any": ["value"] } },
+            { "kind": "User", "name": "John" }
+          ],
+          "node": { "id": "2" },
+          "count": 3
+        }),
+    );
+
+    assert_eq!(errors, Vec::<String>::new());
+}
+
+#[test]
+fn reports_mismatches_with_paths() {
+    let errors = validate_test_response(
+        "
+        {
+          me { id name role friends { name } }
+          search { __typename ... on Post { title } }
+          node(id: \"1\") { ... on User { name } }
+          count
+        }
+        ",
+        serde_json::json!({}),
+        serde_json::json!({
+          "me": { "id": 1, "role": "OWNER", "friends": [{ "name": true }, null], "extra": 1 },
+          "search": [{ "__typename": "Post", "title": null }, { "__typename": "Comment" }],
+          "node": { "title": "Hello" },
+          "count": 3000000000u64
+        }),
+    );
+
+    assert_eq!(
+        errors,
+        vec![
+            "me.id: Expected a value of type \"ID\", got 1.",
+            "me.name: Missing field \"name\".",
+            "me.role: Expected a value of enum \"Role\", got \"OWNER\".",
+            "me.friends[0].name: Expected a value of type \"String\", got true.",
+            "me.extra: Unexpected field \"extra\".",
+            "search[0].title: Expected non-nullable type \"String!\" not to be null.",
+            "search[1]: Expected a possible type of \"SearchResult\" as __typename, got \"Comment\".",
+            "node: Expected an object of a possible type of \"Node\", got {\"title\":\"Hello\"}.",
+            "count: Expected a value of type \"Int\", got 3000000000.",
+        ]
+    );
+
+    assert_eq!(
+        validate_test_response(
+            "{ me { id } }",
+            serde_json::json!({}),
+            serde_json::json!(null)
+        ),
+        vec!["Expected an object of type \"Query\", got null."]
+    );
+}
+
+#[test]
+fn checks_introspection_fields_of_the_query_type() {
+    let operation = "
+        {
+          __schema { queryType { name } types { ...TypeName } }
+          user: __type(name: \"User\") { name kind fields { name } }
+          missing: __type(name: \"Missing\") { name }
+          me { __typename }
+        }
+
+        fragment TypeName on __Type { name }
+        ";
+
+    let errors = validate_test_response(
+        operation,
+        serde_json::json!({}),
+        serde_json::json!({
+          "__schema": { "queryType": { "name": "Query" }, "types": [{ "name": "Query" }] },
+          "user": { "name": "User", "kind": "OBJECT", "fields": [{ "name": "id" }] },
+          "missing": null,
+          "me": { "__typename": "User" }
+        }),
+    );
+
+    assert_eq!(errors, Vec::<String>::new());
+
+    let errors = validate_test_response(
+        operation,
+        serde_json::json!({}),
+        serde_json::json!({
+          "__schema": { "queryType": null, "types": [{}] },
+          "user": { "name": "User", "kind": "CLASS", "fields": null },
+          "missing": null,
+          "me": { "__typename": "User" }
+        }),
+    );
+
+    assert_eq!(
+        errors,
+        vec![
+            "__schema.queryType: Expected non-nullable type \"__Type!\" not to be null.",
+            "__schema.types[0].name: Missing field \"name\".",
+            "user.kind: Expected a value of enum \"__TypeKind\", got \"CLASS\".",
+        ]
+    );
+
+    assert_eq!(
+        validate_test_response(
+            "{ me { __schema { queryType { name } } } }",
+            serde_json::json!({}),
+            serde_json::json!({ "me": { "__schema": { "queryType": { "name": "Query" } } } }),
+        ),
+        vec!["me.__schema: Field \"__schema\" is not defined on type \"User\"."]
+    );
+}
+
+#[test]
+fn uses_the_default_root_type_names() {
+    let schema = crate::parser::parse_schema::<String>(
+        "type Query { a: Int } type Mutation { m: Int } type Subscription { s: Int }",
+    )
+    .expect("Failed to parse schema")
+    .into_static();
+    let validate = |operation: &str, data: serde_json::Value| {
+        let document = crate::parser::parse_query::<String>(operation)
+            .expect("Failed to parse query")
+            .into_static();
+
+        validate_response(&schema, &document, None, &serde_json::Map::new(), &data)
+            .iter()
+            .map(|error| error.to_string())
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(
+        validate("mutation { m }", serde_json::json!({ "m": 1 })),
+        Vec::<String>::new()
+    );
+    assert_eq!(
+        validate("subscription { s }", serde_json::json!({ "s": "1" })),
+        vec!["s: Expected a value of type \"Int\", got \"1\"."]
+    );
+
+    let query_only = crate::parser::parse_schema::<String>("type Query { a: Int }")
+        .expect("Failed to parse schema")
+        .into_static();
+    let document = crate::parser::parse_query::<String>("mutation { m }")
+        .expect("Failed to parse query")
+        .into_static();
+    assert_eq!(
+        validate_response(
+            &query_only,
+            &document,
+            None,
+            &serde_json::Map::new(),
+            &serde_json::json!({ "m": 1 })
+        )[0]
+        .message,
+        "Schema is not configured to execute this operation."
+    );
+}
@@ -0,0 +1,720 @@
+use serde::Serialize;
+use serde_json::{Map, Value as JsonValue};
+use serde_with::serde_as;
+
+use crate::ast::{
+    IntoSchema, OperationDefinitionExtension, Schema, TypeDefinitionExtension, TypeExtension,
+};
+use crate::introspection::is_built_in_scalar;
+use crate::parser::Pos;
+pub use crate::scalars::CustomScalarCoercion;
+use crate::static_graphql::query::{self, OperationDefinition, Type, VariableDefinition};
+use crate::static_graphql::schema::{EnumType, InputObjectType, TypeDefinition};
+use crate::validation::utils::{did_you_mean, suggestion_list, PositionDef};
+
+/// Variable values after coercion, by variable name.
+pub type CoercedVariableValues = Map<String, JsonValue>;
+
+/// A segment of the path to an invalid value, inside the variables object or a response.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(untagged)]
+pub enum PathSegment {
+    Key(String),
+    Index(usize),
+}
+
+#[serde_as]
+#[derive(Serialize, Debug, Clone)]
+pub struct VariableCoercionError {
+    #[serde_as(as = "Vec<PositionDef>")]
+    pub locations: Vec<Pos>,
+    pub message: String,
+    /// Path to the invalid value, starting with the variable name (`["input", "ids", 0]`).
+    #[serde(skip_serializing)]
+    pub path: Vec<PathSegment>,
+}
+
+/// Accepts any value for custom scalars, as they are.
+struct AcceptCustomScalars;
+
+impl CustomScalarCoercion for AcceptCustomScalars {
+    fn coerce_scalar(&self, _scalar_name: &str, value: &JsonValue) -> Result<JsonValue, String> {
+        Ok(value.clone())
+    }
+}
+
+const NON_OBJECT_VARIABLES: &str =
+    "Variables must be provided as an Object where each property is a variable value.";
+
+/// Coerces the runtime `variables` of `operation` (usually the `variables` entry of a GraphQL
+/// request), following `CoerceVariableValues` of the spec: default values are applied, and
+/// values are checked against the types of the variable definitions.
+///
+/// Custom scalars accept any value, see `coerce_variable_values_with_custom_scalars` to check them.
+///
+/// https://spec.graphql.org/draft/#sec-Coercing-Variable-Values
+pub fn coerce_variable_values<'a>(
+    schema: impl IntoSchema<'a>,
+    operation: &'a OperationDefinition,
+    variables: JsonValue,
+) -> Result<CoercedVariableValues, Vec<VariableCoercionError>> {
+    coerce_variable_values_with_custom_scalars(schema, operation, variables, &AcceptCustomScalars)
+}
+
+/// Same as `coerce_variable_values`, but values of custom scalars go through `custom_scalars`.
+pub fn coerce_variable_values_with_custom_scalars<'a>(
+    schema: impl IntoSchema<'a>,
+    operation: &'a OperationDefinition,
+    variables: JsonValue,
+    custom_scalars: &dyn CustomScalarCoercion,
+) -> Result<CoercedVariableValues, Vec<VariableCoercionError>> {
+    let schema = schema.into_schema();
+    let coercion = InputCoercion {
+        schema: &schema,
+        custom_scalars,
+    };
+
+    let mut inputs = match variables {
+        JsonValue::Object(inputs) => inputs,
+        JsonValue::Null => Map::new(),
+        _ => {
+            return Err(vec![VariableCoercionError {
+                locations: vec![operation.position()],
+                message: NON_OBJECT_VARIABLES.to_string(),
+                path: vec![],
+            }])
+        }
+    };
+
+    let mut coerced_values = CoercedVariableValues::new();
+    let mut errors = vec![];
+
+    for variable_definition in operation.variable_definitions() {
+        let variable_name = &variable_definition.name;
+        let variable_type = &variable_definition.var_type;
+        let report = |errors: &mut Vec<VariableCoercionError>, message: String, path| {
+            errors.push(VariableCoercionError {
+                locations: vec![variable_definition.position],
+                message,
+                path,
+            })
+        };
+
+        if !coercion.is_input_type(variable_type) {
+            report(
+                &mut errors,
+                format!(
+                    "Variable \"${}\" expected value of type \"{}\" which cannot be used as an input type.",
+                    variable_name, variable_type
+                ),
+                vec![PathSegment::Key(variable_name.clone())],
+            );
+            continue;
+        }
+
+        let value = match inputs.remove(variable_name) {
+            Some(value) => value,
+            None => {
+                if let Some(default_value) = &variable_definition.default_value {
+                    match coercion.coerce_default_value(variable_definition, default_value) {
+                        Ok(value) => {
+                            coerced_values.insert(variable_name.clone(), value);
+                        }
+                        Err(error) => errors.push(error),
+                    }
+                } else if variable_type.is_non_null() {
+                    report(
+                        &mut errors,
+                        format!(
+                            "Variable \"${}\" of required type \"{}\" was not provided.",
+                            variable_name, variable_type
+                        ),
+                        vec![PathSegment::Key(variable_name.clone())],
+                    );
+                }
+
+                continue;
+            }
+        };
+
+        if value.is_null() && variable_type.is_non_null() {
+            report(
+                &mut errors,
+                format!(
+                    "Variable \"${}\" of non-null type \"{}\" must not be null.",
+                    variable_name, variable_type
+                ),
+                vec![PathSegment::Key(variable_name.clone())],
+            );
+            continue;
+        }
+
+        let mut input_errors = vec![];
+        let coerced_value = coercion.coerce_input_value(
+            &value,
+            variable_type,
+            &mut vec![PathSegment::Key(variable_name.clone())],
+            &mut input_errors,
+        );
+
+        if input_errors.is_empty() {
+            coerced_values.insert(variable_name.clone(), coerced_value);
+        }
+
+        for InputError {
+            path,
+            value,
+            message,
+        } in input_errors
+        {
+            let at_path = if path.len() > 1 {
+                format!(" at \"{}\"", print_path(&path))
+            } else {
+                String::new()
+            };
+
+            report(
+                &mut errors,
+                format!(
+                    "Variable \"${}\" got invalid value {}{}; {}",
+                    variable_name, value, at_path, message
+                ),
+                path,
+            );
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(coerced_values)
+    } else {
+        Err(errors)
+    }
+}
+
+pub(super) fn print_path(path: &[PathSegment]) -> String {
+    path.iter()
+        .enumerate()
+        .map(|(index, segment)| match segment {
+            PathSegment::Key(key) if index == 0 => key.clone(),
+            PathSegment::Key(key) => format!(".{}", key),
+            PathSegment::Index(index) => format!("[{}]", index),
+        })
+        .collect()
+}
+
+struct InputError {
+    path: Vec<PathSegment>,
+    value: JsonValue,
+    message: String,
+}
+
+impl InputError {
+    fn new(path: &[PathSegment], value: &JsonValue, message: String) -> Self {
+        InputError {
+            path: path.to_vec(),
+            value: value.clone(),
+            message,
+        }
+    }
+}
+
+struct InputCoercion<'s> {
+    schema: &'s Schema<'s>,
+    custom_scalars: &'s dyn CustomScalarCoercion,
+}
+
+impl<'s> InputCoercion<'s> {
+    fn is_input_type(&self, t: &Type) -> bool {
+        self.schema
+            .type_by_name(t.inner_type())
+            .is_some_and(|type_def| type_def.is_input_type())
+            || is_built_in_scalar(t.inner_type())
+    }
+
+    fn coerce_default_value(
+        &self,
+        variable_definition: &VariableDefinition,
+        default_value: &query::Value,
+    ) -> Result<JsonValue, VariableCoercionError> {
+        let value = literal_to_json(default_value);
+        let mut input_errors = vec![];
+        let coerced_value = self.coerce_input_value(
+            &value,
+            &variable_definition.var_type,
+            &mut vec![PathSegment::Key(variable_definition.name.clone())],
+            &mut input_errors,
+        );
+
+        match input_errors.into_iter().next() {
+            None => Ok(coerced_value),
+            Some(error) => Err(VariableCoercionError {
+                locations: vec![variable_definition.position],
+                message: format!(
+                    "Variable \"${}\" has invalid default value {}; {}",
+                    variable_definition.name, value, error.message
+                ),
+                path: error.path,
+            }),
+        }
+    }
+
+    fn coerce_input_value(
+        &self,
+        value: &JsonValue,
+        input_type: &Type,
+        path: &mut Vec<PathSegment>,
+        errors: &mut Vec<InputError>,
+    ) -> JsonValue {
+        match input_type {
+            Type::NonNullType(inner_type) => {
+                if value.is_null() {
+                    errors.push(InputError::new(
+                        path,
+                        value,
+                        format!(
+                            "Expected non-nullable type \"{}\" not to be null.",
+                            input_type
+                        ),
+                    ));
+                    return JsonValue::Null;
+                }
+
+                self.coerce_input_value(value, inner_type, path, errors)
+            }
+            _ if value.is_null() => JsonValue::Null,
+            Type::ListType(item_type) => match value {
+                JsonValue::Array(items) => JsonValue::Array(
+                    items
+                        .iter()
+                        .enumerate()
+                        .map(|(index, item)| {
+                            path.push(PathSegment::Index(index));
+                            let coerced = self.coerce_input_value(item, item_type, path, errors);
+                            path.pop();
+                            coerced
+                        })
+                        .collect(),
+                ),
+                // A single value is coerced into a list of one item.
+                _ => {
+                    JsonValue::Array(vec![self.coerce_input_value(value, item_type, path, errors)])
+                }
+            },
+            Type::NamedType(type_name) => match self.schema.type_by_name(type_name) {
+                Some(TypeDefinition::InputObject(input_object)) => {
+                    self.coerce_input_object(value, input_object, path, errors)
+                }
+                Some(TypeDefinition::Enum(enum_type)) => match coerce_enum(value, enum_type) {
+                    Ok(coerced) => coerced,
+                    Err(message) => {
+                        errors.push(InputError::new(path, value, message));
+                        JsonValue::Null
+                    }
+                },
+                Some(TypeDefinition::Scalar(_)) | None => {
+                    match self.coerce_scalar(value, type_name) {
+                        Ok(coerced) => coerced,
+                        Err(message) => {
+                            errors.push(InputError::new(path, value, message));
+                            JsonValue::Null
+                        }
+                    }
+                }
+                Some(_) => {
+                    errors.push(InputError::new(
+                        path,
+                        value,
+                        format!("Expected type \"{}\" to be an input type.", type_name),
+                    ));
+                    JsonValue::Null
+                }
+            },
+        }
+    }
+
+    fn coerce_input_object(
+        &self,
+        value: &JsonValue,
+        input_object: &InputObjectType,
+        path: &mut Vec<PathSegment>,
+        errors: &mut Vec<InputError>,
+    ) -> JsonValue {
+        let fields = match value {
+            JsonValue::Object(fields) => fields,
+            _ => {
+                errors.push(InputError::new(
+                    path,
+                    value,
+                    format!("Expected type \"{}\" to be an object.", input_object.name),
+                ));
+                return JsonValue::Null;
+            }
+        };
+
+        let mut coerced = Map::new();
+
+        for field_def in &input_object.fields {
+            match fields.get(&field_def.name) {
+                Some(field_value) => {
+                    path.push(PathSegment::Key(field_def.name.clone()));
+                    let coerced_value =
+                        self.coerce_input_value(field_value, &field_def.value_type, path, errors);
+                    path.pop();
+                    coerced.insert(field_def.name.clone(), coerced_value);
+                }
+                None => {
+                    if let Some(default_value) = &field_def.default_value {
+                        path.push(PathSegment::Key(field_def.name.clone()));
+                        let coerced_value = self.coerce_input_value(
+                            &literal_to_json(default_value),
+                            &field_def.value_type,
+                            path,
+                            errors,
+                        );
+                        path.pop();
+                        coerced.insert(field_def.name.clone(), coerced_value);
+                    } else if field_def.value_type.is_non_null() {
+                        errors.push(InputError::new(
+                            path,
+                            value,
+                            format!(
+                                "Field \"{}\" of required type \"{}\" was not provided.",
+                                field_def.name, field_def.value_type
+                            ),
+                        ));
+                    }
+                }
+            }
+        }
+
+        for field_name in fields.keys() {
+            if !input_object.fields.iter().any(|f| f.name.eq(field_name)) {
+                let suggestions = suggestion_list(
+                    field_name,
+                    input_object.fields.iter().map(|f| f.name.as_str()),
+                );
+
+                errors.push(InputError::new(
+                    path,
+                    value,
+                    format!(
+                        "Field \"{}\" is not defined by type \"{}\".{}",
+                        field_name,
+                        input_object.name,
+                        did_you_mean(None, &suggestions)
+                    ),
+                ));
+            }
+        }
+
+        JsonValue::Object(coerced)
+    }
+
+    fn coerce_scalar(&self, value: &JsonValue, scalar_name: &str) -> Result<JsonValue, String> {
+        match scalar_name {
+            "Int" => match value.as_f64() {
+                Some(number) if number.fract() == 0.0 => {
+                    if number < i32::MIN as f64 || number > i32::MAX as f64 {
+                        Err(format!(
+                            "Int cannot represent non 32-bit signed integer value: {}",
+                            value
+                        ))
+                    } else {
+                        Ok(JsonValue::from(number as i32))
+                    }
+                }
+                _ => Err(format!("Int cannot represent non-integer value: {}", value)),
+            },
+            "Float" => match value.as_f64() {
+                Some(number) => Ok(JsonValue::from(number)),
+                None => Err(format!(
+                    "Float cannot represent non numeric value: {}",
+                    value
+                )),
+            },
+            "String" => match value {
+                JsonValue::String(_) => Ok(value.clone()),
+                _ => Err(format!(
+                    "String cannot represent a non string value: {}",
+                    value
+                )),
+            },
+            "Boolean" => match value {
+                JsonValue::Bool(_) => Ok(value.clone()),
+                _ => Err(format!(
+                    "Boolean cannot represent a non boolean value: {}",
+                    value
+                )),
+            },
+            "ID" => match value {
+                JsonValue::String(_) => Ok(value.clone()),
+                JsonValue::Number(number) if number.is_i64() || number.is_u64() => {
+                    Ok(JsonValue::String(number.to_string()))
+                }
+                _ => Err(format!("ID cannot represent value: {}", value)),
+            },
+            _ => self.custom_scalars.coerce_scalar(scalar_name, value),
+        }
+    }
+}
+
+fn coerce_enum(value: &JsonValue, enum_type: &EnumType) -> Result<JsonValue, String> {
+    let value_names = enum_type.values.iter().map(|v| v.name.as_str());
+
+    match value {
+        JsonValue::String(name) if enum_type.values.iter().any(|v| v.name.eq(name)) => {
+            Ok(value.clone())
+        }
+        JsonValue::String(name) => Err(format!(
+            "Value \"{}\" does not exist in \"{}\" enum.{}",
+            name,
+            enum_type.name,
+            did_you_mean(Some("the enum value"), &suggestion_list(name, value_names))
+        )),
+        _ => Err(format!(
+            "Enum \"{}\" cannot represent non-string value: {}.{}",
+            enum_type.name,
+            value,
+            did_you_mean(
+                Some("the enum value"),
+                &suggestion_list(&value.to_string(), value_names)
+            )
+        )),
+    }
+}
+
+/// Converts a GraphQL literal (like a default value) to JSON. Variables become `null`.
+pub(crate) fn literal_to_json(value: &query::Value) -> JsonValue {
+    match value {
+        query::Value::Variable(_) | query::Value::Null => JsonValue::Null,
+        query::Value::Int(number) => number.as_i64().map_or(JsonValue::Null, JsonValue::from),
+        query::Value::Float(number) => JsonValue::from(*number),
+        query::Value::String(string) => JsonValue::String(string.clone()),
+        query::Value::Boolean(boolean) => JsonValue::Bool(*boolean),
+        query::Value::Enum(name) => JsonValue::String(name.clone()),
+        query::Value::List(items) => JsonValue::Array(items.iter().map(literal_to_json).collect()),
+        query::Value::Object(fields) => JsonValue::Object(
+            fields
+                .iter()
+                .map(|(name, value)| (name.clone(), literal_to_json(value)))
+                .collect(),
+        ),
+    }
+}
+
+#[cfg(test)]
+static VARIABLES_TEST_SCHEMA: &str = "
+type Query {
+  search(input: SearchInput, first: Int, color: Color, at: DateTime): [String]
+}
+
+enum Color {
+  RED
+  GREEN
+}
+
+scalar DateTime
+
+input SearchInput {
+  text: String!
+  ids: [ID!]
+  limit: Int = 10
+  score: Float
+}
+";
+
+#[cfg(test)]
+fn coerce_test_variables(
+    operation: &str,
+    variables: JsonValue,
+) -> Result<CoercedVariableValues, Vec<VariableCoercionError>> {
+    let schema = crate::parser::parse_schema::<String>(VARIABLES_TEST_SCHEMA)
+        .unwrap()
+        .into_static();
+    let operation = crate::parser::parse_query::<String>(operation)
+        .unwrap()
+        .into_static();
+
+    match &operation.definitions[0] {
+        query::Definition::Operation(operation) => {
+            coerce_variable_values(&schema, operation, variables)
+        }
+        _ => panic!("expected an operation"),
+    }
+}
+
+#[cfg(test)]
+fn get_error_messages(
+    result: Result<CoercedVariableValues, Vec<VariableCoercionError>>,
+) -> Vec<String> {
+    result
+        .expect_err("expected coercion errors")
+        .into_iter()
+        .map(|error| error.message)
+        .collect()
+}
+
+#[test]
+fn coerces_values_and_applies_defaults() {
+    use serde_json::json;
+
+    let values = coerce_test_variables(
+        "query ($input: SearchInput!, $first: Int = 5, $color: Color, $score: Float, $unused: Int) {
+          search(input: $input, first: $first, color: $color) }",
+        json!({
+            "input": { "text": "dog", "ids": 1, "score": 2 },
+            "color": "RED",
+            "score": 1
+        }),
+    )
+    .unwrap();
+
+    assert_eq!(
+        JsonValue::Object(values),
+        json!({
+            "input": { "text": "dog", "ids": ["1"], "limit": 10, "score": 2.0 },
+            "first": 5,
+            "color": "RED",
+            "score": 1.0
+        })
+    );
+}
+
+#[test]
+fn keeps_explicit_nulls() {
+    use serde_json::json;
+
+    let values = coerce_test_variables(
+        "query ($first: Int = 5) { search(first: $first) }",
+        json!({ "first": null }),
+    )
+    .unwrap();
+
+    assert_eq!(JsonValue::Object(values), json!({ "first": null }));
+}
+
+#[test]
+fn reports_missing_and_null_required_variables() {
+    use serde_json::json;
+
+    let result = coerce_test_variables(
+        "query ($input: SearchInput!, $first: Int!) { search(input: $input, first: $first) }",
+        json!({ "first": null }),
+    );
+
+    assert_eq!(
+        get_error_messages(result),
+        vec![
+            "Variable \"$input\" of required type \"SearchInput!\" was not provided.",
+            "Variable \"$first\" of non-null type \"Int!\" must not be null."
+        ]
+    );
+}
+
+#[test]
+fn reports_invalid_values_with_their_path() {
+    use serde_json::json;
+
+    let result = coerce_test_variables(
+        "query ($input: SearchInput, $first: Int, $color: Color) {
+          search(input: $input, first: $first, color: $color) }",
+        json!({
+            "input": { "ids": ["1", null, true], "limt": 5 },
+            "first": 1.5,
+            "color": "GREN"
+        }),
+    );
+
+    assert_eq!(
+        get_error_messages(result),
+        vec![
+            "Variable \"$input\" got invalid value {\"ids\":[\"1\",null,true],\"limt\":5}; Field \"text\" of required type \"String!\" was not provided.",
+            "Variable \"$input\" got invalid value null at \"input.ids[1]\"; Expected non-nullable type \"ID!\" not to be null.",
+            "Variable \"$input\" got invalid value true at \"input.ids[2]\"; ID cannot represent value: true",
+            "Variable \"$input\" got invalid value {\"ids\":[\"1\",null,true],\"limt\":5}; Field \"limt\" is not defined by type \"SearchInput\". Did you mean \"limit\"?",
+            "Variable \"$first\" got invalid value 1.5; Int cannot represent non-integer value: 1.5",
+            "Variable \"$color\" got invalid value \"GREN\"; Value \"GREN\" does not exist in \"Color\" enum. Did you mean the enum value \"GREEN\" or \"RED\"?"
+        ]
+    );
+}
+
+#[test]
+fn reports_paths_of_invalid_values() {
+    use serde_json::json;
+
+    let errors = coerce_test_variables(
+        "query ($input: SearchInput) { search(input: $input) }",
+        json!({ "input": { "text": "dog", "ids": ["1", false] } }),
+    )
+    .unwrap_err();
+
+    assert_eq!(
+        errors[0].path,
+        vec![
+            PathSegment::Key("input".to_string()),
+            PathSegment::Key("ids".to_string()),
+            PathSegment::Index(1)
+        ]
+    );
+    assert_eq!(errors[0].locations, vec![Pos { line: 1, column: 8 }]);
+}
+
+#[test]
+fn rejects_non_object_variables() {
+    use serde_json::json;
+
+    let result = coerce_test_variables("query ($first: Int) { search(first: $first) }", json!([1]));
+    let errors = result.unwrap_err();
+
+    assert_eq!(
+        errors[0].message,
+        "Variables must be provided as an Object where each property is a variable value."
+    );
+    assert_eq!(errors[0].locations, vec![Pos { line: 1, column: 1 }]);
+}
+
+#[test]
+fn custom_scalars_use_the_coercion_hook() {
+    use serde_json::json;
+
+    let schema = crate::parser::parse_schema::<String>(VARIABLES_TEST_SCHEMA)
+        .unwrap()
+        .into_static();
+    let document = crate::parser::parse_query::<String>(
+        "query ($at: DateTime, $other: DateTime) { search(at: $at) }",
+    )
+    .unwrap()
+    .into_static();
+    let operation = match &document.definitions[0] {
+        query::Definition::Operation(operation) => operation,
+        _ => unreachable!(),
+    };
+    let date_time = |scalar_name: &str, value: &JsonValue| match value.as_str() {
+        Some(date) if date.len() == 10 => Ok(JsonValue::String(format!("{}T00:00:00Z", date))),
+        Some(date) if date.ends_with('Z') => Ok(value.clone()),
+        _ => Err(format!("{} must be an ISO-8601 string.", scalar_name)),
+    };
+
+    let values = coerce_variable_values_with_custom_scalars(
+        &schema,
+        operation,
+        json!({ "at": "2024-01-01" }),
+        &date_time,
+    )
+    .unwrap();
+    assert_eq!(
+        JsonValue::Object(values),
+        json!({ "at": "2024-01-01T00:00:00Z" })
+    );
+
+    let result = coerce_variable_values_with_custom_scalars(
+        &schema,
+        operation,
+        json!({ "at": 1, "other": "2024-01-01T10:00:00Z" }),
+        &date_time,
+    );
+    assert_eq!(
+        get_error_messages(result),
+        vec!["Variable \"$at\" got invalid value 1; DateTime must be an ISO-8601 string."]
+    );
+}
@@ -0,0 +1,743 @@
+use std::fmt;
+
+use serde_json::Value as JsonValue;
+
+use crate::parser::Pos;
+use crate::static_graphql::schema::{
+    Definition, Directive, DirectiveDefinition, DirectiveLocation as SchemaDirectiveLocation,
+    Document, EnumType, EnumValue, Field, InputObjectType, InputValue, InterfaceType, ObjectType,
+    ScalarType, SchemaDefinition, Type, TypeDefinition, UnionType, Value,
+};
+
+use super::{
+    IntrospectionDirective, IntrospectionField, IntrospectionInputTypeRef, IntrospectionInputValue,
+    IntrospectionOutputTypeRef, IntrospectionQuery, IntrospectionType,
+};
+
+/// An introspection result that can't be turned into a schema document.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BuildClientSchemaError {
+    pub message: String,
+}
+
+impl BuildClientSchemaError {
+    fn new(message: String) -> Self {
+        Self { message }
+    }
+}
+
+impl fmt::Display for BuildClientSchemaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for BuildClientSchemaError {}
+
+/// Builds a schema `Document` from an introspection result, like `buildClientSchema` in
+/// graphql-js. The document can then be used with `validate`, or with `Schema::new`.
+///
+/// Every type of the result is kept, including the introspection types (`__Schema`, `__Type`,
+/// ...) and the built-in scalars, along with every directive definition. Deprecations become
+/// `@deprecated` directives, `specifiedByURL`s become `@specifiedBy` directives, and `isOneOf`
+/// input objects get a `@oneOf` directive.
+///
+/// Note that the `VARIABLE_DEFINITION` directive location is dropped when using the
+/// `graphql_parser` feature, as the parser can't represent it.
+pub fn build_client_schema(
+    introspection: &IntrospectionQuery,
+) -> Result<Document, BuildClientSchemaError> {
+    let schema = &introspection.__schema;
+    let mut definitions = vec![Definition::SchemaDefinition(SchemaDefinition {
+        position: Pos::default(),
+        directives: vec![],
+        query: Some(schema.query_type.name.clone()),
+        mutation: schema.mutation_type.as_ref().map(|t| t.name.clone()),
+        subscription: schema.subscription_type.as_ref().map(|t| t.name.clone()),
+    })];
+
+    for introspection_type in &schema.types {
+        definitions.push(Definition::TypeDefinition(build_type(introspection_type)?));
+    }
+
+    for directive in &schema.directives {
+        definitions.push(Definition::DirectiveDefinition(build_directive(directive)?));
+    }
+
+    Ok(Document { definitions })
+}
+
+fn build_type(
+    introspection_type: &IntrospectionType,
+) -> Result<TypeDefinition, BuildClientSchemaError> {
+    Ok(match introspection_type {
+        IntrospectionType::SCALAR(scalar) => TypeDefinition::Scalar(ScalarType {
+            position: Pos::default(),
+            description: scalar.description.clone(),
+            name: scalar.name.clone(),
+            directives: scalar
+                .specified_by_url
+                .as_ref()
+                .map(|url| {
+                    vec![directive(
+                        "specifiedBy",
+                        vec![("url".to_string(), Value::String(url.clone()))],
+                    )]
+                })
+                .unwrap_or_default(),
+        }),
+        IntrospectionType::OBJECT(object) => TypeDefinition::Object(ObjectType {
+            position: Pos::default(),
+            description: object.description.clone(),
+            name: object.name.clone(),
+            implements_interfaces: object.interfaces.iter().map(|i| i.name.clone()).collect(),
+            directives: vec![],
+            fields: build_fields(&object.name, &object.fields)?,
+        }),
+        IntrospectionType::INTERFACE(interface) => TypeDefinition::Interface(InterfaceType {
+            position: Pos::default(),
+            description: interface.description.clone(),
+            name: interface.name.clone(),
+            implements_interfaces: interface
+                .interfaces
+                .iter()
+                .flatten()
+                .map(|i| i.name.clone())
+                .collect(),
+            directives: vec![],
+            fields: build_fields(&interface.name, &interface.fields)?,
+        }),
+        IntrospectionType::UNION(union) => TypeDefinition::Union(UnionType {
+            position: Pos::default(),
+            description: union.description.clone(),
+            name: union.name.clone(),
+            directives: vec![],
+            types: union
+                .possible_types
+                .iter()
+                .map(|t| t.name.clone())
+                .collect(),
+        }),
+        IntrospectionType::ENUM(enum_type) => TypeDefinition::Enum(EnumType {
+            position: Pos::default(),
+            description: enum_type.description.clone(),
+            name: enum_type.name.clone(),
+            directives: vec![],
+            values: enum_type
+                .enum_values
+                .iter()
+                .map(|value| EnumValue {
+                    position: Pos::default(),
+                    description: value.description.clone(),
+                    name: value.name.clone(),
+                    directives: deprecation(value.is_deprecated, &value.deprecation_reason),
+                })
+                .collect(),
+        }),
+        IntrospectionType::INPUT_OBJECT(input_object) => {
+            TypeDefinition::InputObject(InputObjectType {
+                position: Pos::default(),
+                description: input_object.description.clone(),
+                name: input_object.name.clone(),
+                directives: match input_object.is_one_of {
+                    Some(true) => vec![directive("oneOf", vec![])],
+                    _ => vec![],
+                },
+                fields: build_input_values(&input_object.input_fields, |name| {
+                    format!("{}.{}", input_object.name, name)
+                })?,
+            })
+        }
+    })
+}
+
+fn build_fields(
+    type_name: &str,
+    fields: &[IntrospectionField],
+) -> Result<Vec<Field>, BuildClientSchemaError> {
+    fields
+        .iter()
+        .map(|field| {
+            let coordinate = format!("{}.{}", type_name, field.name);
+
+            Ok(Field {
+                position: Pos::default(),
+                description: field.description.clone(),
+                name: field.name.clone(),
+                arguments: build_input_values(&field.args, |name| {
+                    format!("{}({}:)", coordinate, name)
+                })?,
+                field_type: build_output_type_ref(&coordinate, &field.type_ref)?,
+                directives: deprecation(field.is_deprecated, &field.deprecation_reason),
+            })
+        })
+        .collect()
+}
+
+/// `coordinate` returns the schema coordinate of an input value from its name, for errors.
+fn build_input_values(
+    input_values: &[IntrospectionInputValue],
+    coordinate: impl Fn(&str) -> String,
+) -> Result<Vec<InputValue>, BuildClientSchemaError> {
+    input_values
+        .iter()
+        .map(|input_value| {
+            let coordinate = coordinate(&input_value.name);
+
+            let value_type = match &input_value.type_ref {
+                Some(type_ref) => build_input_type_ref(&coordinate, type_ref)?,
+                None => {
+                    return Err(BuildClientSchemaError::new(format!(
+                        "Introspection result missing type reference for \"{}\".",
+                        coordinate
+                    )))
+                }
+            };
+
+            let default_value = match &input_value.default_value {
+                None | Some(JsonValue::Null) => None,
+                Some(JsonValue::String(literal)) => {
+                    Some(parse_value_literal(&coordinate, literal)?)
+                }
+                Some(value) => Some(json_to_value(value)),
+            };
+
+            Ok(InputValue {
+                position: Pos::default(),
+                description: input_value.description.clone(),
+                name: input_value.name.clone(),
+                value_type,
+                default_value,
+                directives: deprecation(input_value.is_deprecated, &input_value.deprecation_reason),
+            })
+        })
+        .collect()
+}
+
+fn build_directive(
+    directive: &IntrospectionDirective,
+) -> Result<DirectiveDefinition, BuildClientSchemaError> {
+    Ok(DirectiveDefinition {
+        position: Pos::default(),
+        description: directive.description.clone(),
+        name: directive.name.clone(),
+        arguments: build_input_values(&directive.args, |name| {
+            format!("@{}({}:)", directive.name, name)
+        })?,
+        repeatable: directive.is_repeatable.unwrap_or(false),
+        locations: directive
+            .locations
+            .iter()
+            .filter_map(|location| location.as_str().parse::<SchemaDirectiveLocation>().ok())
+            .collect(),
+    })
+}
+
+fn missing_of_type(coordinate: &str) -> BuildClientSchemaError {
+    BuildClientSchemaError::new(format!(
+        "Decorated type deeper than introspection query at \"{}\".",
+        coordinate
+    ))
+}
+
+fn build_output_type_ref(
+    coordinate: &str,
+    type_ref: &IntrospectionOutputTypeRef,
+) -> Result<Type, BuildClientSchemaError> {
+    Ok(match type_ref {
+        IntrospectionOutputTypeRef::LIST { of_type } => match of_type {
+            Some(of_type) => Type::ListType(Box::new(build_output_type_ref(coordinate, of_type)?)),
+            None => return Err(missing_of_type(coordinate)),
+        },
+        IntrospectionOutputTypeRef::NON_NULL { of_type } => match of_type {
+            Some(of_type) => {
+                Type::NonNullType(Box::new(build_output_type_ref(coordinate, of_type)?))
+            }
+            None => return Err(missing_of_type(coordinate)),
+        },
+        IntrospectionOutputTypeRef::SCALAR(named)
+        | IntrospectionOutputTypeRef::ENUM(named)
+        | IntrospectionOutputTypeRef::INPUT_OBJECT(named)
+        | IntrospectionOutputTypeRef::UNION(named)
+        | IntrospectionOutputTypeRef::OBJECT(named)
+        | IntrospectionOutputTypeRef::INTERFACE(named) => Type::NamedType(named.name.clone()),
+    })
+}
+
+fn build_input_type_ref(
+    coordinate: &str,
+    type_ref: &IntrospectionInputTypeRef,
+) -> Result<Type, BuildClientSchemaError> {
+    Ok(match type_ref {
+        IntrospectionInputTypeRef::LIST { of_type } => match of_type {
+            Some(of_type) => Type::ListType(Box::new(build_input_type_ref(coordinate, of_type)?)),
+            None => return Err(missing_of_type(coordinate)),
+        },
+        IntrospectionInputTypeRef::NON_NULL { of_type } => match of_type {
+            Some(of_type) => {
+                Type::NonNullType(Box::new(build_input_type_ref(coordinate, of_type)?))
+            }
+            None => return Err(missing_of_type(coordinate)),
+        },
+        IntrospectionInputTypeRef::SCALAR(named)
+        | IntrospectionInputTypeRef::ENUM(named)
+        | IntrospectionInputTypeRef::INPUT_OBJECT(named) => Type::NamedType(named.name.clone()),
+    })
+}
+
+fn directive(name: &str, arguments: Vec<(String, Value)>) -> Directive {
+    Directive {
+        position: Pos::default(),
+        name: name.to_string(),
+        arguments,
+    }
+}
+
+fn deprecation(is_deprecated: Option<bool>, reason: &Option<String>) -> Vec<Directive> {
+    if is_deprecated != Some(true) {
+        return vec![];
+    }
+
+    let arguments = reason
+        .iter()
+        .map(|reason| ("reason".to_string(), Value::String(reason.clone())))
+        .collect();
+
+    vec![directive("deprecated", arguments)]
+}
+
+/// Default values are printed as GraphQL literals (`{field: CREATED_AT, direction: DESC}`) in
+/// introspection results.
+fn parse_value_literal(coordinate: &str, literal: &str) -> Result<Value, BuildClientSchemaError> {
+    let source = format!("input DefaultValue {{ value: Int = {} }}", literal);
+    let invalid_default_value = |reason: String| {
+        BuildClientSchemaError::new(format!(
+            "Invalid default value {} for \"{}\": {}",
+            literal, coordinate, reason
+        ))
+    };
+
+    let document = crate::parser::parse_schema::<String>(&source)
+        .map_err(|error| invalid_default_value(error.to_string()))?
+        .into_static();
+
+    document
+        .definitions
+        .into_iter()
+        .find_map(|definition| match definition {
+            Definition::TypeDefinition(TypeDefinition::InputObject(input_object)) => input_object
+                .fields
+                .into_iter()
+                .next()
+                .and_then(|field| field.default_value),
+            _ => None,
+        })
+        .ok_or_else(|| invalid_default_value("expected a single value.".to_string()))
+}
+
+fn json_to_value(value: &JsonValue) -> Value {
+    match value {
+        JsonValue::Null => Value::Null,
+        JsonValue::Bool(value) => Value::Boolean(*value),
+        JsonValue::Number(number) => match number.as_i64().map(i32::try_from) {
+            Some(Ok(int)) => Value::Int(int.into()),
+            _ => Value::Float(number.as_f64().unwrap_or_default()),
+        },
+        JsonValue::String(value) => Value::String(value.clone()),
+        JsonValue::Array(items) => Value::List(items.iter().map(json_to_value).collect()),
+        JsonValue::Object(fields) => Value::Object(
+            fields
+                .iter()
+                .map(|(name, value)| (name.clone(), json_to_value(value)))
+                .collect(),
+        ),
+    }
+}
+
+#[cfg(test)]
+fn output_type_ref_to_string(type_ref: &IntrospectionOutputTypeRef) -> String {
+    match type_ref {
+        IntrospectionOutputTypeRef::LIST { of_type } => {
+            format!("[{}]", output_type_ref_to_string(of_type.as_ref().unwrap()))
+        }
+        IntrospectionOutputTypeRef::NON_NULL { of_type } => {
+            format!("{}!", output_type_ref_to_string(of_type.as_ref().unwrap()))
+        }
+        IntrospectionOutputTypeRef::SCALAR(named)
+        | IntrospectionOutputTypeRef::ENUM(named)
+        | IntrospectionOutputTypeRef::INPUT_OBJECT(named)
+        | IntrospectionOutputTypeRef::UNION(named)
+        | IntrospectionOutputTypeRef::OBJECT(named)
+        | IntrospectionOutputTypeRef::INTERFACE(named) => named.name.clone(),
+    }
+}
+
+#[cfg(test)]
+fn assert_matches_introspection(document: &Document, introspection: &IntrospectionQuery) {
+    use crate::ast::{FieldByNameExtension, SchemaDocumentExtension, TypeDefinitionExtension};
+
+    for introspection_type in &introspection.__schema.types {
+        let type_def = document
+            .type_by_name(introspection_type.name())
+            .unwrap_or_else(|| panic!("missing type {}", introspection_type.name()));
+
+        let fields = match introspection_type {
+            IntrospectionType::OBJECT(object) => &object.fields,
+            IntrospectionType::INTERFACE(interface) => &interface.fields,
+            _ => continue,
+        };
+
+        for field in fields {
+            let field_def = type_def
+                .field_by_name(&field.name)
+                .unwrap_or_else(|| panic!("missing field {}.{}", type_def.name(), field.name));
+
+            assert_eq!(
+                field_def.field_type.to_string(),
+                output_type_ref_to_string(&field.type_ref)
+            );
+            assert_eq!(field_def.arguments.len(), field.args.len());
+        }
+    }
+
+    let directives = document
+        .definitions
+        .iter()
+        .filter(|definition| matches!(definition, Definition::DirectiveDefinition(_)))
+        .count();
+    assert_eq!(directives, introspection.__schema.directives.len());
+}
+
+/// The JSON of `introspection` with the optional fields left at their default removed (a
+/// missing `isDeprecated` reads as `false`, for example). Default values are compared as they
+/// are, except the ones with input object literals: the parser keeps their fields sorted by name.
+#[cfg(test)]
+fn normalized_introspection_json(introspection: &IntrospectionQuery) -> JsonValue {
+    fn normalize(value: &mut JsonValue) {
+        match value {
+            JsonValue::Object(object) => {
+                object.retain(|key, value| match (key.as_str(), &*value) {
+                    (_, JsonValue::Null) => false,
+                    ("isDeprecated" | "isRepeatable" | "isOneOf", JsonValue::Bool(false)) => false,
+                    ("interfaces", JsonValue::Array(interfaces)) => !interfaces.is_empty(),
+                    _ => true,
+                });
+
+                for (key, value) in object.iter_mut() {
+                    match (key.as_str(), &*value) {
+                        ("defaultValue", JsonValue::String(literal)) => {
+                            let parsed = parse_value_literal("defaultValue", literal)
+                                .expect("failed to parse default value");
+
+                            if contains_object(&parsed) {
+                                *value = JsonValue::String(crate::ast::print_schema::print_value(
+                                    &parsed,
+                                ));
+                            }
+                        }
+                        _ => normalize(value),
+                    }
+                }
+            }
+            JsonValue::Array(items) => items.iter_mut().for_each(normalize),
+            _ => {}
+        }
+    }
+
+    fn contains_object(value: &Value) -> bool {
+        match value {
+            Value::Object(_) => true,
+            Value::List(items) => items.iter().any(contains_object),
+            _ => false,
+        }
+    }
+
+    let mut value = serde_json::to_value(introspection).expect("failed to serialize");
+    normalize(&mut value);
+    value
+}
+
+/// Checks that `document` introspects to `introspection`: types, fields, arguments, enum values
+/// and directives, with their descriptions, default values and deprecations.
+#[cfg(test)]
+fn assert_introspects_to(document: &Document, introspection: &IntrospectionQuery) {
+    let mut generated =
+        super::introspection_from_schema(document).expect("failed to introspect schema");
+
+    // `introspection_from_schema` adds the built-in directives a server may leave out
+    generated.__schema.directives.retain(|generated| {
+        introspection
+            .__schema
+            .directives
+            .iter()
+            .any(|directive| directive.name == generated.name)
+    });
+
+    assert_eq!(
+        normalized_introspection_json(&generated),
+        normalized_introspection_json(introspection)
+    );
+}
+
+#[cfg(test)]
+fn assert_round_trip(path: &str) {
+    let json_file = std::fs::File::open(path).expect("failed to open json file");
+    let introspection =
+        super::parse_introspection(json_file).expect("failed to parse introspection json");
+    let document = build_client_schema(&introspection).expect("failed to build schema");
+
+    assert_matches_introspection(&document, &introspection);
+    assert_introspects_to(&document, &introspection);
+
+    // The document prints as SDL that parses back to the same schema, descriptions, default
+    // values and deprecations included
+    let printed = crate::ast::print_schema(&document, &Default::default());
+    let reparsed = crate::parser::parse_schema::<String>(&printed)
+        .expect("failed to parse printed schema")
+        .into_static();
+
+    assert_matches_introspection(&reparsed, &introspection);
+    assert_eq!(
+        crate::ast::print_schema(&reparsed, &Default::default()),
+        printed
+    );
+}
+
+#[test]
+fn round_trips_product_introspection() {
+    assert_round_trip("./src/introspection/test_files/product_introspection.json");
+}
+
+#[test]
+fn round_trips_github_introspection() {
+    assert_round_trip("./src/introspection/test_files/github_introspection.json");
+}
+
+#[test]
+fn round_trips_shopify_introspection() {
+    assert_round_trip("./src/introspection/test_files/shopify_introspection.json");
+}
+
+#[test]
+fn converts_default_values_and_deprecations() {
+    use crate::ast::SchemaDocumentExtension;
+
+    let introspection = super::parse_introspection_from_string(
+        r#"{
+  "__schema": {
+    "queryType": { "name": "Query" },
+    "types": [
+      {
+        "kind": "OBJECT",
+        "name": "Query",
+        "interfaces": [],
+        "fields": [
+          {
+            "name": "users",
+            "args": [
+              {
+                "name": "orderBy",
+                "defaultValue": "{field: CREATED_AT, direction: DESC}",
+                "type": { "kind": "INPUT_OBJECT", "name": "UserOrder" }
+              },
+              {
+                "name": "first",
+                "defaultValue": "10",
+                "isDeprecated": true,
+                "deprecationReason": "Use `last`.",
+                "type": { "kind": "SCALAR", "name": "Int" }
+              }
+            ],
+            "isDeprecated": true,
+            "type": { "kind": "LIST", "ofType": { "kind": "SCALAR", "name": "String" } }
+          }
+        ]
+      },
+      {
+        "kind": "SCALAR",
+        "name": "DateTime",
+        "specifiedByURL": "https://scalars.graphql.org/andimarek/date-time"
+      }
+    ],
+    "directives": [
+      {
+        "name": "cached",
+        "isRepeatable": true,
+        "locations": ["FIELD_DEFINITION", "OBJECT"],
+        "args": [
+          {
+            "name": "ttl",
+            "defaultValue": "60",
+            "type": { "kind": "NON_NULL", "ofType": { "kind": "SCALAR", "name": "Int" } }
+          }
+        ]
+      }
+    ]
+  }
+}"#,
+    )
+    .expect("failed to parse introspection json");
+
+    let document = build_client_schema(&introspection).expect("failed to build schema");
+
+    assert_eq!(
+        document.to_string(),
+        r#"schema {
+  query: Query
+}
+
+type Query {
+  users(orderBy: UserOrder = {direction: DESC, field: CREATED_AT}, first: Int = 10 @deprecated(reason: "Use `last`.")): [String] @deprecated
+}
+
+scalar DateTime @specifiedBy(url: "https://scalars.graphql.org/andimarek/date-time")
+
+directive @cached(ttl: Int! = 60) repeatable on FIELD_DEFINITION | OBJECT
+"#
+    );
+    assert_eq!(document.query_type().name, "Query");
+}
+
+#[test]
+fn round_trips_escaped_string_default_values() {
+    let introspection = super::parse_introspection_from_string(
+        r#"{
+  "__schema": {
+    "queryType": { "name": "Query" },
+    "types": [
+      {
+        "kind": "OBJECT",
+        "name": "Query",
+        "interfaces": [],
+        "fields": [
+          {
+            "name": "greet",
+            "args": [
+              {
+                "name": "text",
+                "defaultValue": "\"he\\\"llo\\n\"",
+                "type": { "kind": "SCALAR", "name": "String" }
+              }
+            ],
+            "type": { "kind": "SCALAR", "name": "String" }
+          }
+        ]
+      },
+      { "kind": "SCALAR", "name": "String" }
+    ],
+    "directives": []
+  }
+}"#,
+    )
+    .expect("failed to parse introspection json");
+
+    let document = build_client_schema(&introspection).expect("failed to build schema");
+    let generated =
+        super::introspection_from_schema(&document).expect("failed to introspect schema");
+    let json = serde_json::to_value(&generated).unwrap();
+
+    assert_eq!(
+        json["__schema"]["types"][0]["fields"][0]["args"][0]["defaultValue"],
+        r#""he\"llo\n""#
+    );
+}
+
+#[test]
+fn reports_types_deeper_than_the_introspection_query() {
+    let introspection = super::parse_introspection_from_string(
+        r#"{
+  "__schema": {
+    "queryType": { "name": "Query" },
+    "types": [
+      {
+        "kind": "OBJECT",
+        "name": "Query",
+        "interfaces": [],
+        "fields": [
+          { "name": "a", "args": [], "type": { "kind": "LIST" } }
+        ]
+      }
+    ],
+    "directives": []
+  }
+}"#,
+    )
+    .expect("failed to parse introspection json");
+
+    assert_eq!(
+        build_client_schema(&introspection).map(|_| ()),
+        Err(BuildClientSchemaError {
+            message: "Decorated type deeper than introspection query at \"Query.a\".".to_string()
+        })
+    );
+}
+
+#[test]
+fn reports_invalid_default_values() {
+    let introspection = super::parse_introspection_from_string(
+        r#"{
+  "__schema": {
+    "queryType": { "name": "Query" },
+    "types": [
+      {
+        "kind": "OBJECT",
+        "name": "Query",
+        "interfaces": [],
+        "fields": [
+          {
+            "name": "b",
+            "args": [{ "name": "x", "defaultValue": "{a:", "type": { "kind": "SCALAR", "name": "Int" } }],
+            "type": { "kind": "SCALAR", "name": "Int" }
+          }
+        ]
+      }
+    ],
+    "directives": []
+  }
+}"#,
+    )
+    .expect("failed to parse introspection json");
+
+    assert_eq!(
+        build_client_schema(&introspection).map(|_| ()),
+        Err(BuildClientSchemaError {
+            message: "Invalid default value {a: for \"Query.b(x:)\": schema parse error: \
+                Parse error at 1:39\nUnexpected `}[Punctuator]`\n\
+                Expected `IntValue`, `FloatValue`, `StringValue`, `BlockString`, \
+                `true`, `false`, `null` or `Name`\n"
+                .to_string()
+        })
+    );
+}
+
+#[test]
+fn validates_operations_against_introspection() {
+    use crate::validation::rules::default_rules_validation_plan;
+    use crate::validation::validate::validate_with_source;
+
+    let json_file = std::fs::File::open("./src/introspection/test_files/github_introspection.json")
+        .expect("failed to open json file");
+    let introspection = super::parse_introspection(json_file).expect("failed to parse json");
+    let schema = build_client_schema(&introspection).expect("failed to build schema");
+    let plan = default_rules_validation_plan();
+
+    let source = "query { viewer { login repositories(first: 10) { nodes { name } } } __typename }";
+    let operation = crate::parser::parse_query::<String>(source)
+        .expect("failed to parse operation")
+        .into_static();
+    assert_eq!(
+        validate_with_source(&schema, &operation, source, &plan).len(),
+        0
+    );
+
+    let source = "query { viewer { unknownField } }";
+    let operation = crate::parser::parse_query::<String>(source)
+        .expect("failed to parse operation")
+        .into_static();
+    let errors = validate_with_source(&schema, &operation, source, &plan);
+    assert_eq!(errors.len(), 1);
+    assert_eq!(
+        errors[0].message,
+        "Cannot query field \"unknownField\" on type \"User\"."
+    );
+}
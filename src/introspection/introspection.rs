@@ -1,4 +1,5 @@
 use std::io;
+use std::str::FromStr;
 
 use serde::{Deserialize, Serialize};
 use serde_json::{Result, Value};
@@ -31,7 +32,6 @@ pub struct IntrospectionInputValue {
 }
 
 #[derive(Serialize, Deserialize, Debug)]
-#[serde(tag = "kind")]
 pub struct IntrospectionField {
     pub name: String,
     pub description: Option<String>,
@@ -94,6 +94,8 @@ pub struct IntrospectionInputObjectType {
     pub description: Option<String>,
     #[serde(rename = "inputFields")]
     pub input_fields: Vec<IntrospectionInputValue>,
+    #[serde(rename = "isOneOf", default, skip_serializing_if = "Option::is_none")]
+    pub is_one_of: Option<bool>,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -215,6 +217,61 @@ pub enum DirectiveLocation {
     INPUT_FIELD_DEFINITION,
 }
 
+impl DirectiveLocation {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DirectiveLocation::QUERY => "QUERY",
+            DirectiveLocation::MUTATION => "MUTATION",
+            DirectiveLocation::SUBSCRIPTION => "SUBSCRIPTION",
+            DirectiveLocation::FIELD => "FIELD",
+            DirectiveLocation::FRAGMENT_DEFINITION => "FRAGMENT_DEFINITION",
+            DirectiveLocation::FRAGMENT_SPREAD => "FRAGMENT_SPREAD",
+            DirectiveLocation::INLINE_FRAGMENT => "INLINE_FRAGMENT",
+            DirectiveLocation::VARIABLE_DEFINITION => "VARIABLE_DEFINITION",
+            DirectiveLocation::SCHEMA => "SCHEMA",
+            DirectiveLocation::SCALAR => "SCALAR",
+            DirectiveLocation::OBJECT => "OBJECT",
+            DirectiveLocation::FIELD_DEFINITION => "FIELD_DEFINITION",
+            DirectiveLocation::ARGUMENT_DEFINITION => "ARGUMENT_DEFINITION",
+            DirectiveLocation::INTERFACE => "INTERFACE",
+            DirectiveLocation::UNION => "UNION",
+            DirectiveLocation::ENUM => "ENUM",
+            DirectiveLocation::ENUM_VALUE => "ENUM_VALUE",
+            DirectiveLocation::INPUT_OBJECT => "INPUT_OBJECT",
+            DirectiveLocation::INPUT_FIELD_DEFINITION => "INPUT_FIELD_DEFINITION",
+        }
+    }
+}
+
+impl FromStr for DirectiveLocation {
+    type Err = ();
+
+    fn from_str(location: &str) -> std::result::Result<Self, Self::Err> {
+        Ok(match location {
+            "QUERY" => DirectiveLocation::QUERY,
+            "MUTATION" => DirectiveLocation::MUTATION,
+            "SUBSCRIPTION" => DirectiveLocation::SUBSCRIPTION,
+            "FIELD" => DirectiveLocation::FIELD,
+            "FRAGMENT_DEFINITION" => DirectiveLocation::FRAGMENT_DEFINITION,
+            "FRAGMENT_SPREAD" => DirectiveLocation::FRAGMENT_SPREAD,
+            "INLINE_FRAGMENT" => DirectiveLocation::INLINE_FRAGMENT,
+            "VARIABLE_DEFINITION" => DirectiveLocation::VARIABLE_DEFINITION,
+            "SCHEMA" => DirectiveLocation::SCHEMA,
+            "SCALAR" => DirectiveLocation::SCALAR,
+            "OBJECT" => DirectiveLocation::OBJECT,
+            "FIELD_DEFINITION" => DirectiveLocation::FIELD_DEFINITION,
+            "ARGUMENT_DEFINITION" => DirectiveLocation::ARGUMENT_DEFINITION,
+            "INTERFACE" => DirectiveLocation::INTERFACE,
+            "UNION" => DirectiveLocation::UNION,
+            "ENUM" => DirectiveLocation::ENUM,
+            "ENUM_VALUE" => DirectiveLocation::ENUM_VALUE,
+            "INPUT_OBJECT" => DirectiveLocation::INPUT_OBJECT,
+            "INPUT_FIELD_DEFINITION" => DirectiveLocation::INPUT_FIELD_DEFINITION,
+            _ => return Err(()),
+        })
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 pub struct IntrospectionDirective {
     pub name: String,
@@ -0,0 +1,599 @@
+use std::fmt;
+
+use crate::ast::print_schema::print_value;
+use crate::ast::{merge_type_extensions, Schema, SchemaDocumentExtension, TypeDefinitionExtension};
+use crate::static_graphql::schema::{
+    self, Definition, Directive, DirectiveDefinition, Field, InputValue, Type, TypeDefinition,
+    Value,
+};
+
+use super::{
+    with_built_ins, DirectiveLocation, IntrospectionDirective, IntrospectionEnumType,
+    IntrospectionEnumValue, IntrospectionField, IntrospectionInputObjectType,
+    IntrospectionInputTypeRef, IntrospectionInputValue, IntrospectionInterfaceType,
+    IntrospectionNamedTypeRef, IntrospectionObjectType, IntrospectionOutputTypeRef,
+    IntrospectionQuery, IntrospectionScalarType, IntrospectionSchema, IntrospectionType,
+    IntrospectionUnionType,
+};
+
+/// The reason of `@deprecated` when it's used without one.
+pub const DEFAULT_DEPRECATION_REASON: &str = "No longer supported";
+
+/// A schema document that can't be described with introspection, because it references types
+/// that don't exist or uses types where they aren't allowed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct IntrospectionFromSchemaError {
+    pub message: String,
+}
+
+impl IntrospectionFromSchemaError {
+    fn new(message: String) -> Self {
+        Self { message }
+    }
+}
+
+impl fmt::Display for IntrospectionFromSchemaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for IntrospectionFromSchemaError {}
+
+/// Builds the result of the introspection query for `document`, like `introspectionFromSchema`
+/// in graphql-js. Serialize it with `serde_json` to get the `data` of the introspection response.
+///
+/// Type extensions are merged first, and the built-in scalars, built-in directives and
+/// introspection types are added when the document doesn't define them (see `with_built_ins`).
+/// Deprecated fields, arguments, input fields and enum values are all included.
+pub fn introspection_from_schema(
+    document: &schema::Document,
+) -> Result<IntrospectionQuery, IntrospectionFromSchemaError> {
+    let document = with_built_ins(&merge_type_extensions(document));
+    let schema = Schema::new(&document);
+
+    let explicit_schema_definition =
+        document
+            .definitions
+            .iter()
+            .find_map(|definition| match definition {
+                Definition::SchemaDefinition(schema_definition) => Some(schema_definition),
+                _ => None,
+            });
+
+    // Without a schema definition, the root types are the types with the default names
+    let root_type = |explicit: Option<&String>, default_name: &str| match explicit_schema_definition
+    {
+        Some(_) => explicit.map(|name| IntrospectionNamedTypeRef { name: name.clone() }),
+        None => schema
+            .object_type_by_name(default_name)
+            .map(|_| IntrospectionNamedTypeRef {
+                name: default_name.to_string(),
+            }),
+    };
+
+    let schema_definition = schema.schema_definition();
+    let query_type = root_type(schema_definition.query.as_ref(), "Query").ok_or_else(|| {
+        IntrospectionFromSchemaError::new("Query root type must be provided.".to_string())
+    })?;
+
+    let introspection = Introspection { schema: &schema };
+    let mut types = vec![];
+    let mut directives = vec![];
+
+    for definition in &document.definitions {
+        match definition {
+            Definition::TypeDefinition(type_def) => {
+                types.push(introspection.introspect_type(type_def)?);
+            }
+            Definition::DirectiveDefinition(directive_def) => {
+                directives.push(introspection.introspect_directive(directive_def)?);
+            }
+            _ => {}
+        }
+    }
+
+    Ok(IntrospectionQuery {
+        __schema: IntrospectionSchema {
+            description: None,
+            query_type,
+            mutation_type: root_type(schema_definition.mutation.as_ref(), "Mutation"),
+            subscription_type: root_type(schema_definition.subscription.as_ref(), "Subscription"),
+            types,
+            directives,
+        },
+    })
+}
+
+struct Introspection<'a> {
+    schema: &'a Schema<'a>,
+}
+
+impl<'a> Introspection<'a> {
+    fn introspect_type(
+        &self,
+        type_def: &TypeDefinition,
+    ) -> Result<IntrospectionType, IntrospectionFromSchemaError> {
+        Ok(match type_def {
+            TypeDefinition::Scalar(scalar) => IntrospectionType::SCALAR(IntrospectionScalarType {
+                name: scalar.name.clone(),
+                description: scalar.description.clone(),
+                specified_by_url: directive_argument(&scalar.directives, "specifiedBy", "url"),
+            }),
+            TypeDefinition::Object(object) => IntrospectionType::OBJECT(IntrospectionObjectType {
+                name: object.name.clone(),
+                description: object.description.clone(),
+                fields: self.introspect_fields(&object.name, &object.fields)?,
+                interfaces: named_type_refs(&object.implements_interfaces),
+            }),
+            TypeDefinition::Interface(interface) => {
+                IntrospectionType::INTERFACE(IntrospectionInterfaceType {
+                    name: interface.name.clone(),
+                    description: interface.description.clone(),
+                    fields: self.introspect_fields(&interface.name, &interface.fields)?,
+                    interfaces: Some(named_type_refs(&interface.implements_interfaces)),
+                    possible_types: self
+                        .schema
+                        .possible_types(type_def)
+                        .iter()
+                        .map(|object| IntrospectionNamedTypeRef {
+                            name: object.name.clone(),
+                        })
+                        .collect(),
+                })
+            }
+            TypeDefinition::Union(union) => IntrospectionType::UNION(IntrospectionUnionType {
+                name: union.name.clone(),
+                description: union.description.clone(),
+                possible_types: named_type_refs(&union.types),
+            }),
+            TypeDefinition::Enum(enum_type) => IntrospectionType::ENUM(IntrospectionEnumType {
+                name: enum_type.name.clone(),
+                description: enum_type.description.clone(),
+                enum_values: enum_type
+                    .values
+                    .iter()
+                    .map(|value| {
+                        let deprecation_reason = deprecation_reason(&value.directives);
+
+                        IntrospectionEnumValue {
+                            name: value.name.clone(),
+                            description: value.description.clone(),
+                            is_deprecated: Some(deprecation_reason.is_some()),
+                            deprecation_reason,
+                        }
+                    })
+                    .collect(),
+            }),
+            TypeDefinition::InputObject(input_object) => {
+                IntrospectionType::INPUT_OBJECT(IntrospectionInputObjectType {
+                    name: input_object.name.clone(),
+                    description: input_object.description.clone(),
+                    input_fields: self.introspect_input_values(&input_object.fields, |name| {
+                        format!("{}.{}", input_object.name, name)
+                    })?,
+                    is_one_of: Some(
+                        input_object
+                            .directives
+                            .iter()
+                            .any(|directive| directive.name == "oneOf"),
+                    ),
+                })
+            }
+        })
+    }
+
+    fn introspect_fields(
+        &self,
+        type_name: &str,
+        fields: &[Field],
+    ) -> Result<Vec<IntrospectionField>, IntrospectionFromSchemaError> {
+        fields
+            .iter()
+            .map(|field| {
+                let coordinate = format!("{}.{}", type_name, field.name);
+                let deprecation_reason = deprecation_reason(&field.directives);
+
+                Ok(IntrospectionField {
+                    name: field.name.clone(),
+                    description: field.description.clone(),
+                    args: self.introspect_input_values(&field.arguments, |name| {
+                        format!("{}({}:)", coordinate, name)
+                    })?,
+                    is_deprecated: Some(deprecation_reason.is_some()),
+                    deprecation_reason,
+                    type_ref: self.output_type_ref(&coordinate, &field.field_type)?,
+                })
+            })
+            .collect()
+    }
+
+    /// `coordinate` returns the schema coordinate of an input value from its name, for errors.
+    fn introspect_input_values(
+        &self,
+        input_values: &[InputValue],
+        coordinate: impl Fn(&str) -> String,
+    ) -> Result<Vec<IntrospectionInputValue>, IntrospectionFromSchemaError> {
+        input_values
+            .iter()
+            .map(|input_value| {
+                let coordinate = coordinate(&input_value.name);
+                let deprecation_reason = deprecation_reason(&input_value.directives);
+
+                Ok(IntrospectionInputValue {
+                    name: input_value.name.clone(),
+                    description: input_value.description.clone(),
+                    default_value: input_value
+                        .default_value
+                        .as_ref()
+                        .map(|value| serde_json::Value::String(print_value(value))),
+                    is_deprecated: Some(deprecation_reason.is_some()),
+                    deprecation_reason,
+                    type_ref: Some(self.input_type_ref(&coordinate, &input_value.value_type)?),
+                })
+            })
+            .collect()
+    }
+
+    fn introspect_directive(
+        &self,
+        directive_def: &DirectiveDefinition,
+    ) -> Result<IntrospectionDirective, IntrospectionFromSchemaError> {
+        Ok(IntrospectionDirective {
+            name: directive_def.name.clone(),
+            description: directive_def.description.clone(),
+            is_repeatable: Some(directive_def.repeatable),
+            locations: directive_def
+                .locations
+                .iter()
+                .filter_map(|location| location.as_str().parse::<DirectiveLocation>().ok())
+                .collect(),
+            args: self.introspect_input_values(&directive_def.arguments, |name| {
+                format!("@{}({}:)", directive_def.name, name)
+            })?,
+        })
+    }
+
+    fn named_type(
+        &self,
+        coordinate: &str,
+        name: &str,
+    ) -> Result<&'a TypeDefinition, IntrospectionFromSchemaError> {
+        self.schema.type_by_name(name).ok_or_else(|| {
+            IntrospectionFromSchemaError::new(format!(
+                "Unknown type \"{}\" referenced by \"{}\".",
+                name, coordinate
+            ))
+        })
+    }
+
+    fn output_type_ref(
+        &self,
+        coordinate: &str,
+        type_: &Type,
+    ) -> Result<IntrospectionOutputTypeRef, IntrospectionFromSchemaError> {
+        Ok(match type_ {
+            Type::ListType(of_type) => IntrospectionOutputTypeRef::LIST {
+                of_type: Some(Box::new(self.output_type_ref(coordinate, of_type)?)),
+            },
+            Type::NonNullType(of_type) => IntrospectionOutputTypeRef::NON_NULL {
+                of_type: Some(Box::new(self.output_type_ref(coordinate, of_type)?)),
+            },
+            Type::NamedType(name) => {
+                let named = IntrospectionNamedTypeRef { name: name.clone() };
+
+                match self.named_type(coordinate, name)? {
+                    TypeDefinition::Scalar(_) => IntrospectionOutputTypeRef::SCALAR(named),
+                    TypeDefinition::Object(_) => IntrospectionOutputTypeRef::OBJECT(named),
+                    TypeDefinition::Interface(_) => IntrospectionOutputTypeRef::INTERFACE(named),
+                    TypeDefinition::Union(_) => IntrospectionOutputTypeRef::UNION(named),
+                    TypeDefinition::Enum(_) => IntrospectionOutputTypeRef::ENUM(named),
+                    TypeDefinition::InputObject(_) => {
+                        return Err(IntrospectionFromSchemaError::new(format!(
+                            "The type of \"{}\" must be Output Type but got: {}.",
+                            coordinate, name
+                        )))
+                    }
+                }
+            }
+        })
+    }
+
+    fn input_type_ref(
+        &self,
+        coordinate: &str,
+        type_: &Type,
+    ) -> Result<IntrospectionInputTypeRef, IntrospectionFromSchemaError> {
+        Ok(match type_ {
+            Type::ListType(of_type) => IntrospectionInputTypeRef::LIST {
+                of_type: Some(Box::new(self.input_type_ref(coordinate, of_type)?)),
+            },
+            Type::NonNullType(of_type) => IntrospectionInputTypeRef::NON_NULL {
+                of_type: Some(Box::new(self.input_type_ref(coordinate, of_type)?)),
+            },
+            Type::NamedType(name) => {
+                let named = IntrospectionNamedTypeRef { name: name.clone() };
+
+                match self.named_type(coordinate, name)? {
+                    TypeDefinition::Scalar(_) => IntrospectionInputTypeRef::SCALAR(named),
+                    TypeDefinition::Enum(_) => IntrospectionInputTypeRef::ENUM(named),
+                    TypeDefinition::InputObject(_) => {
+                        IntrospectionInputTypeRef::INPUT_OBJECT(named)
+                    }
+                    type_def => {
+                        return Err(IntrospectionFromSchemaError::new(format!(
+                            "The type of \"{}\" must be Input Type but got: {}.",
+                            coordinate,
+                            type_def.name()
+                        )))
+                    }
+                }
+            }
+        })
+    }
+}
+
+fn named_type_refs(names: &[String]) -> Vec<IntrospectionNamedTypeRef> {
+    names
+        .iter()
+        .map(|name| IntrospectionNamedTypeRef { name: name.clone() })
+        .collect()
+}
+
+/// Returns the string value of `argument` of the first `directive` in `directives`.
+fn directive_argument(directives: &[Directive], directive: &str, argument: &str) -> Option<String> {
+    directives
+        .iter()
+        .filter(|d| d.name == directive)
+        .flat_map(|d| d.arguments.iter())
+        .find_map(|(name, value)| match value {
+            Value::String(value) if name == argument => Some(value.clone()),
+            _ => None,
+        })
+}
+
+/// The reason of the `@deprecated` directive in `directives`, if there is one.
+fn deprecation_reason(directives: &[Directive]) -> Option<String> {
+    directives.iter().any(|d| d.name == "deprecated").then(|| {
+        directive_argument(directives, "deprecated", "reason")
+            .unwrap_or_else(|| DEFAULT_DEPRECATION_REASON.to_string())
+    })
+}
+
+#[cfg(test)]
+fn assert_round_trip(path: &str) {
+    use super::{build_client_schema, parse_introspection};
+
+    let json_file = std::fs::File::open(path).expect("failed to open json file");
+    let introspection = parse_introspection(json_file).expect("failed to parse introspection json");
+    let document = build_client_schema(&introspection).expect("failed to build schema");
+    let generated = introspection_from_schema(&document).expect("failed to introspect schema");
+
+    let type_names = |introspection: &IntrospectionQuery| {
+        introspection
+            .__schema
+            .types
+            .iter()
+            .map(|t| t.name().clone())
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(type_names(&generated), type_names(&introspection));
+    assert_eq!(
+        generated.__schema.query_type.name,
+        introspection.__schema.query_type.name
+    );
+
+    // Converting the generated introspection again gives the same result
+    let regenerated = introspection_from_schema(
+        &build_client_schema(&generated).expect("failed to build schema"),
+    )
+    .expect("failed to introspect schema");
+    assert_eq!(
+        serde_json::to_value(&regenerated).unwrap(),
+        serde_json::to_value(&generated).unwrap()
+    );
+}
+
+#[test]
+fn round_trips_product_introspection() {
+    assert_round_trip("./src/introspection/test_files/product_introspection.json");
+}
+
+#[test]
+fn round_trips_github_introspection() {
+    assert_round_trip("./src/introspection/test_files/github_introspection.json");
+}
+
+#[test]
+fn round_trips_shopify_introspection() {
+    assert_round_trip("./src/introspection/test_files/shopify_introspection.json");
+}
+
+#[test]
+fn introspects_schema_document() {
+    let document = crate::parser::parse_schema::<String>(
+        r#"
+"Root"
+type Query implements Node {
+  id: ID!
+  search(text: String = "a", limit: Int = 10 @deprecated, filter: Filter): [Result!] @deprecated(reason: "Use `find`.")
+}
+
+interface Node { id: ID! }
+
+union Result = Query
+
+input Filter {
+  tags: [String!] = ["x"]
+  old: Boolean @deprecated(reason: "Gone.")
+  note: String = "he\"llo\n"
+}
+
+enum Color { RED GREEN @deprecated }
+
+scalar DateTime @specifiedBy(url: "https://scalars.graphql.org/andimarek/date-time")
+
+directive @cached(ttl: Int) repeatable on FIELD_DEFINITION | OBJECT
+"#,
+    )
+    .expect("Failed to parse schema")
+    .into_static();
+
+    let introspection = introspection_from_schema(&document).expect("failed to introspect");
+    let json = serde_json::to_value(&introspection).unwrap();
+    let schema = &json["__schema"];
+
+    assert_eq!(schema["queryType"], serde_json::json!({"name": "Query"}));
+    assert_eq!(schema["mutationType"], serde_json::Value::Null);
+
+    let type_names: Vec<&str> = schema["types"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|t| t["name"].as_str().unwrap())
+        .collect();
+    assert_eq!(
+        type_names,
+        vec![
+            "Query",
+            "Node",
+            "Result",
+            "Filter",
+            "Color",
+            "DateTime",
+            "String",
+            "Int",
+            "Boolean",
+            "ID",
+            "__Schema",
+            "__Type",
+            "__TypeKind",
+            "__Field",
+            "__InputValue",
+            "__EnumValue",
+            "__Directive",
+            "__DirectiveLocation",
+        ]
+    );
+
+    let directive_names: Vec<&str> = schema["directives"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|d| d.as_object().unwrap()["name"].as_str().unwrap())
+        .collect();
+    assert_eq!(
+        directive_names,
+        vec!["cached", "include", "skip", "deprecated", "specifiedBy"]
+    );
+    assert_eq!(schema["directives"][0]["isRepeatable"], true);
+    assert_eq!(
+        schema["directives"][0]["locations"],
+        serde_json::json!(["FIELD_DEFINITION", "OBJECT"])
+    );
+
+    let search = &schema["types"][0]["fields"][1];
+    assert_eq!(
+        search,
+        &serde_json::json!({
+          "name": "search",
+          "description": null,
+          "args": [
+            {
+              "name": "text",
+              "description": null,
+              "defaultValue": "\"a\"",
+              "isDeprecated": false,
+              "deprecationReason": null,
+              "type": { "kind": "SCALAR", "name": "String" }
+            },
+            {
+              "name": "limit",
+              "description": null,
+              "defaultValue": "10",
+              "isDeprecated": true,
+              "deprecationReason": "No longer supported",
+              "type": { "kind": "SCALAR", "name": "Int" }
+            },
+            {
+              "name": "filter",
+              "description": null,
+              "defaultValue": null,
+              "isDeprecated": false,
+              "deprecationReason": null,
+              "type": { "kind": "INPUT_OBJECT", "name": "Filter" }
+            }
+          ],
+          "isDeprecated": true,
+          "deprecationReason": "Use `find`.",
+          "type": {
+            "kind": "LIST",
+            "ofType": {
+              "kind": "NON_NULL",
+              "ofType": { "kind": "UNION", "name": "Result" }
+            }
+          }
+        })
+    );
+
+    assert_eq!(schema["types"][0]["description"], "Root");
+    assert_eq!(
+        schema["types"][8]["description"],
+        "The `Boolean` scalar type represents `true` or `false`."
+    );
+    assert_eq!(
+        schema["directives"][3]["args"][0]["defaultValue"],
+        "\"No longer supported\""
+    );
+    assert_eq!(
+        schema["types"][1]["possibleTypes"],
+        serde_json::json!([{"name": "Query"}])
+    );
+    assert_eq!(
+        schema["types"][3]["inputFields"][0]["defaultValue"],
+        "[\"x\"]"
+    );
+    assert_eq!(
+        schema["types"][3]["inputFields"][1]["deprecationReason"],
+        "Gone."
+    );
+    assert_eq!(
+        schema["types"][3]["inputFields"][2]["defaultValue"],
+        r#""he\"llo\n""#
+    );
+    assert_eq!(schema["types"][4]["enumValues"][1]["isDeprecated"], true);
+    assert_eq!(
+        schema["types"][5]["specifiedByURL"],
+        "https://scalars.graphql.org/andimarek/date-time"
+    );
+}
+
+#[test]
+fn reports_invalid_type_references() {
+    let document = crate::parser::parse_schema::<String>(
+        "
+type Query { a: Missing, b(input: Query): Int }
+",
+    )
+    .expect("Failed to parse schema")
+    .into_static();
+
+    assert_eq!(
+        introspection_from_schema(&document).map(|_| ()),
+        Err(IntrospectionFromSchemaError {
+            message: "Unknown type \"Missing\" referenced by \"Query.a\".".to_string()
+        })
+    );
+
+    let document = crate::parser::parse_schema::<String>("type Query { b(input: Query): Int }")
+        .expect("Failed to parse schema")
+        .into_static();
+
+    assert_eq!(
+        introspection_from_schema(&document).map(|_| ()),
+        Err(IntrospectionFromSchemaError {
+            message: "The type of \"Query.b(input:)\" must be Input Type but got: Query."
+                .to_string()
+        })
+    );
+}
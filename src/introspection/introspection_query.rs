@@ -0,0 +1,304 @@
+use crate::static_graphql::query;
+
+/// Options of `get_introspection_query`, with the same defaults as graphql-js.
+#[derive(Debug, Clone, PartialEq)]
+pub struct IntrospectionOptions {
+    /// Whether to include descriptions. Defaults to `true`.
+    pub descriptions: bool,
+    /// Whether to include `specifiedByURL` on scalars. Defaults to `false`.
+    pub specified_by_url: bool,
+    /// Whether to include `isRepeatable` on directives. Defaults to `false`.
+    pub directive_is_repeatable: bool,
+    /// Whether to include the description of the schema. Defaults to `false`.
+    pub schema_description: bool,
+    /// Whether to include deprecated arguments and input fields, along with their
+    /// `isDeprecated` and `deprecationReason`. Defaults to `false`.
+    pub input_value_deprecation: bool,
+    /// Whether to include `isOneOf` on input objects. Defaults to `false`.
+    pub one_of: bool,
+    /// How many levels of `ofType` the type references go down. Defaults to 9.
+    pub type_depth: usize,
+}
+
+impl Default for IntrospectionOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl IntrospectionOptions {
+    pub fn new() -> Self {
+        Self {
+            descriptions: true,
+            specified_by_url: false,
+            directive_is_repeatable: false,
+            schema_description: false,
+            input_value_deprecation: false,
+            one_of: false,
+            type_depth: 9,
+        }
+    }
+}
+
+/// Returns the query fetching the introspection of a schema, like `getIntrospectionQuery` in
+/// graphql-js. Its result can be read with `parse_introspection`.
+pub fn get_introspection_query(options: &IntrospectionOptions) -> String {
+    let descriptions = if options.descriptions {
+        "description"
+    } else {
+        ""
+    };
+    let (args, input_fields, is_deprecated, deprecation_reason) = if options.input_value_deprecation
+    {
+        (
+            "args(includeDeprecated: true) {",
+            "inputFields(includeDeprecated: true) {",
+            "isDeprecated",
+            "deprecationReason",
+        )
+    } else {
+        ("args {", "inputFields {", "", "")
+    };
+
+    let lines: Vec<(usize, &str)> = vec![
+        (0, "query IntrospectionQuery {"),
+        (1, "__schema {"),
+        (
+            2,
+            if options.schema_description {
+                descriptions
+            } else {
+                ""
+            },
+        ),
+        (2, "queryType { name }"),
+        (2, "mutationType { name }"),
+        (2, "subscriptionType { name }"),
+        (2, "types {"),
+        (3, "...FullType"),
+        (2, "}"),
+        (2, "directives {"),
+        (3, "name"),
+        (3, descriptions),
+        (
+            3,
+            if options.directive_is_repeatable {
+                "isRepeatable"
+            } else {
+                ""
+            },
+        ),
+        (3, "locations"),
+        (3, args),
+        (4, "...InputValue"),
+        (3, "}"),
+        (2, "}"),
+        (1, "}"),
+        (0, "}"),
+        (0, ""),
+        (0, "fragment FullType on __Type {"),
+        (1, "kind"),
+        (1, "name"),
+        (1, descriptions),
+        (
+            1,
+            if options.specified_by_url {
+                "specifiedByURL"
+            } else {
+                ""
+            },
+        ),
+        (1, if options.one_of { "isOneOf" } else { "" }),
+        (1, "fields(includeDeprecated: true) {"),
+        (2, "name"),
+        (2, descriptions),
+        (2, args),
+        (3, "...InputValue"),
+        (2, "}"),
+        (2, "type {"),
+        (3, "...TypeRef"),
+        (2, "}"),
+        (2, "isDeprecated"),
+        (2, "deprecationReason"),
+        (1, "}"),
+        (1, input_fields),
+        (2, "...InputValue"),
+        (1, "}"),
+        (1, "interfaces {"),
+        (2, "...TypeRef"),
+        (1, "}"),
+        (1, "enumValues(includeDeprecated: true) {"),
+        (2, "name"),
+        (2, descriptions),
+        (2, "isDeprecated"),
+        (2, "deprecationReason"),
+        (1, "}"),
+        (1, "possibleTypes {"),
+        (2, "...TypeRef"),
+        (1, "}"),
+        (0, "}"),
+        (0, ""),
+        (0, "fragment InputValue on __InputValue {"),
+        (1, "name"),
+        (1, descriptions),
+        (1, "type { ...TypeRef }"),
+        (1, "defaultValue"),
+        (1, is_deprecated),
+        (1, deprecation_reason),
+        (0, "}"),
+        (0, ""),
+        (0, "fragment TypeRef on __Type {"),
+        (1, "kind"),
+        (1, "name"),
+    ];
+
+    let mut query = String::new();
+
+    for (indent, line) in lines {
+        // Empty lines separate the definitions, and are left by the options turned off
+        if line.is_empty() {
+            if indent == 0 {
+                query.push('\n');
+            }
+            continue;
+        }
+
+        query.push_str(&"  ".repeat(indent));
+        query.push_str(line);
+        query.push('\n');
+    }
+
+    for depth in 1..=options.type_depth {
+        query.push_str(&"  ".repeat(depth));
+        query.push_str("ofType {\n");
+        query.push_str(&"  ".repeat(depth + 1));
+        query.push_str("kind\n");
+        query.push_str(&"  ".repeat(depth + 1));
+        query.push_str("name\n");
+    }
+
+    for depth in (1..=options.type_depth).rev() {
+        query.push_str(&"  ".repeat(depth));
+        query.push_str("}\n");
+    }
+
+    query.push_str("}\n");
+    query
+}
+
+/// Same as `get_introspection_query`, parsed.
+pub fn get_introspection_query_document(options: &IntrospectionOptions) -> query::Document {
+    crate::parser::parse_query::<String>(&get_introspection_query(options))
+        .expect("the introspection query is valid")
+        .into_static()
+}
+
+#[cfg(test)]
+static INTROSPECTION_QUERY_TEST_SCHEMA: &str = "
+type Query {
+  __schema: __Schema!
+  __type(name: String!): __Type
+  search(filter: Filter): [String!]
+}
+
+input Filter @oneOf {
+  id: ID
+  name: String
+}
+
+directive @oneOf on INPUT_OBJECT
+";
+
+#[test]
+fn follows_the_options() {
+    let default_query = get_introspection_query(&IntrospectionOptions::default());
+
+    assert!(default_query.starts_with("query IntrospectionQuery {\n  __schema {\n    queryType"));
+    assert!(default_query.contains("  description\n"));
+    assert!(default_query.contains("  inputFields {\n"));
+    assert!(!default_query.contains("isOneOf"));
+    assert!(!default_query.contains("isRepeatable"));
+    assert!(!default_query.contains("specifiedByURL"));
+    assert_eq!(default_query.matches("ofType {").count(), 9);
+
+    let query = get_introspection_query(&IntrospectionOptions {
+        descriptions: false,
+        specified_by_url: true,
+        directive_is_repeatable: true,
+        schema_description: true,
+        input_value_deprecation: true,
+        one_of: true,
+        type_depth: 3,
+    });
+
+    assert!(!query.contains("description"));
+    assert!(query.contains("  specifiedByURL\n"));
+    assert!(query.contains("  isRepeatable\n"));
+    assert!(query.contains("  isOneOf\n"));
+    assert!(query.contains("  args(includeDeprecated: true) {\n"));
+    assert!(query.contains("  inputFields(includeDeprecated: true) {\n"));
+    assert_eq!(query.matches("ofType {").count(), 3);
+}
+
+#[test]
+fn passes_validation_against_the_introspection_schema() {
+    use crate::validation::{rules::default_rules_validation_plan, validate::validate_with_source};
+
+    let schema = crate::parser::parse_schema::<String>(INTROSPECTION_QUERY_TEST_SCHEMA)
+        .expect("Failed to parse schema")
+        .into_static();
+    let schema = super::with_built_ins(&schema);
+    let plan = default_rules_validation_plan();
+
+    let all_options = IntrospectionOptions {
+        specified_by_url: true,
+        directive_is_repeatable: true,
+        schema_description: true,
+        input_value_deprecation: true,
+        one_of: true,
+        ..IntrospectionOptions::default()
+    };
+
+    for options in [IntrospectionOptions::default(), all_options] {
+        let source = get_introspection_query(&options);
+        let document = get_introspection_query_document(&options);
+        let errors = validate_with_source(&schema, &document, &source, &plan);
+
+        assert_eq!(errors.len(), 0, "{:?}", errors);
+    }
+}
+
+#[test]
+fn is_executed_by_the_introspection_executor() {
+    use crate::execution::IntrospectionExecutor;
+
+    let schema = crate::parser::parse_schema::<String>(INTROSPECTION_QUERY_TEST_SCHEMA)
+        .expect("Failed to parse schema")
+        .into_static();
+    let executor = IntrospectionExecutor::new(&schema).expect("failed to build executor");
+    let document = get_introspection_query_document(&IntrospectionOptions {
+        input_value_deprecation: true,
+        one_of: true,
+        ..IntrospectionOptions::default()
+    });
+
+    let data = executor
+        .execute(&document, None, &serde_json::Map::new())
+        .expect("failed to execute");
+    let introspection = super::parse_introspection_from_string(&data.to_string())
+        .expect("failed to parse executed introspection");
+    let filter = introspection
+        .__schema
+        .types
+        .iter()
+        .find_map(|introspection_type| match introspection_type {
+            super::IntrospectionType::INPUT_OBJECT(input_object) => {
+                Some(input_object).filter(|input_object| input_object.name == "Filter")
+            }
+            _ => None,
+        })
+        .expect("missing Filter input object");
+
+    assert_eq!(filter.is_one_of, Some(true));
+    assert_eq!(filter.input_fields.len(), 2);
+}
@@ -1,4 +1,13 @@
 #![allow(non_camel_case_types)]
+mod build_client_schema;
+mod built_ins;
+#[allow(clippy::module_inception)]
 mod introspection;
+mod introspection_from_schema;
+mod introspection_query;
 
+pub use self::build_client_schema::*;
+pub use self::built_ins::*;
 pub use self::introspection::*;
+pub use self::introspection_from_schema::*;
+pub use self::introspection_query::*;
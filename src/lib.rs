@@ -28,12 +28,20 @@ pub mod static_graphql {
     static_graphql!(schema, schema, {
       Field, Directive, InterfaceType, ObjectType, Value, TypeDefinition,
       EnumType, Type, Document, ScalarType, InputValue, DirectiveDefinition,
-      UnionType, InputObjectType, EnumValue, SchemaDefinition,
+      UnionType, InputObjectType, EnumValue, SchemaDefinition, Definition, TypeExtension,
+      ScalarTypeExtension, ObjectTypeExtension, InterfaceTypeExtension, UnionTypeExtension,
+      EnumTypeExtension, InputObjectTypeExtension,
     });
 }
 
+pub mod diff;
+
+pub mod execution;
+
 pub mod introspection;
 
+pub mod scalars;
+
 pub mod validation;
 
 #[cfg(feature = "graphql_parser")]
@@ -0,0 +1,18 @@
+use serde_json::Value as JsonValue;
+
+/// Coerces variable values of custom scalars, that is, every scalar that isn't built-in.
+///
+/// Implemented for closures taking the scalar name and the value.
+pub trait CustomScalarCoercion {
+    /// Returns the coerced value, or a message explaining why `value` isn't valid.
+    fn coerce_scalar(&self, scalar_name: &str, value: &JsonValue) -> Result<JsonValue, String>;
+}
+
+impl<F> CustomScalarCoercion for F
+where
+    F: Fn(&str, &JsonValue) -> Result<JsonValue, String>,
+{
+    fn coerce_scalar(&self, scalar_name: &str, value: &JsonValue) -> Result<JsonValue, String> {
+        self(scalar_name, value)
+    }
+}
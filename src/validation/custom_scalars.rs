@@ -0,0 +1,340 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+
+use serde_json::Value as JsonValue;
+
+use crate::scalars::CustomScalarCoercion;
+use crate::static_graphql::query::Value;
+use crate::static_graphql::schema::{self, Definition, TypeDefinition};
+
+/// Checks the values of a custom scalar.
+///
+/// `ValuesOfCorrectType` accepts any literal for a custom scalar, unless a validator is
+/// registered for it (see `ValuesOfCorrectType::with_scalar_validators`).
+///
+/// Implemented for closures taking the literal.
+pub trait ScalarValidator: Send + Sync {
+    /// Checks a literal written in an operation. Returns the reason when it's not valid.
+    fn validate_literal(&self, value: &Value) -> Result<(), String>;
+
+    /// Checks a runtime (JSON) value, for example a variable value. Accepts everything by default.
+    fn validate_value(&self, _value: &JsonValue) -> Result<(), String> {
+        Ok(())
+    }
+}
+
+impl<F> ScalarValidator for F
+where
+    F: Fn(&Value) -> Result<(), String> + Send + Sync,
+{
+    fn validate_literal(&self, value: &Value) -> Result<(), String> {
+        self(value)
+    }
+}
+
+/// Validators of custom scalars, by scalar name.
+#[derive(Clone, Default)]
+pub struct ScalarValidators {
+    validators: HashMap<String, Arc<dyn ScalarValidator>>,
+}
+
+impl fmt::Debug for ScalarValidators {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.validators.keys()).finish()
+    }
+}
+
+impl ScalarValidators {
+    pub fn new() -> Self {
+        Self {
+            validators: HashMap::new(),
+        }
+    }
+
+    /// Creates validators for the scalars of `schema` with a `@specifiedBy` URL of a known
+    /// specification (see `validator_for_specification`).
+    pub fn from_specified_by(schema: &schema::Document) -> Self {
+        let mut validators = Self::new();
+
+        for definition in &schema.definitions {
+            if let Definition::TypeDefinition(TypeDefinition::Scalar(scalar)) = definition {
+                let url = scalar
+                    .directives
+                    .iter()
+                    .filter(|directive| directive.name == "specifiedBy")
+                    .flat_map(|directive| directive.arguments.iter())
+                    .find_map(|(name, value)| match (name.as_str(), value) {
+                        ("url", Value::String(url)) => Some(url.as_str()),
+                        _ => None,
+                    });
+
+                if let Some(validator) = url.and_then(validator_for_specification) {
+                    validators
+                        .validators
+                        .insert(scalar.name.clone(), validator.into());
+                }
+            }
+        }
+
+        validators
+    }
+
+    pub fn add_validator(&mut self, scalar_name: &str, validator: impl ScalarValidator + 'static) {
+        self.validators
+            .insert(scalar_name.to_string(), Arc::new(validator));
+    }
+
+    pub fn get(&self, scalar_name: &str) -> Option<&dyn ScalarValidator> {
+        self.validators.get(scalar_name).map(|v| v.as_ref())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.validators.is_empty()
+    }
+}
+
+/// Checks custom scalar variable values with the registered validators, and keeps them as they
+/// are. Scalars without a validator accept any value.
+impl CustomScalarCoercion for ScalarValidators {
+    fn coerce_scalar(&self, scalar_name: &str, value: &JsonValue) -> Result<JsonValue, String> {
+        match self.get(scalar_name) {
+            Some(validator) => validator.validate_value(value).map(|_| value.clone()),
+            None => Ok(value.clone()),
+        }
+    }
+}
+
+/// Returns a built-in validator for the specification at `url` (a `@specifiedBy` URL):
+///
+/// - `DateTimeValidator` for https://scalars.graphql.org/andimarek/date-time and RFC 3339
+/// - `UuidValidator` for RFC 4122
+pub fn validator_for_specification(url: &str) -> Option<Box<dyn ScalarValidator>> {
+    let url = url.trim_end_matches('/').trim_end_matches(".html");
+
+    match url {
+        "https://scalars.graphql.org/andimarek/date-time"
+        | "https://tools.ietf.org/html/rfc3339"
+        | "https://datatracker.ietf.org/doc/html/rfc3339"
+        | "https://www.rfc-editor.org/rfc/rfc3339" => Some(Box::new(DateTimeValidator)),
+        "https://tools.ietf.org/html/rfc4122"
+        | "https://datatracker.ietf.org/doc/html/rfc4122"
+        | "https://www.rfc-editor.org/rfc/rfc4122" => Some(Box::new(UuidValidator)),
+        _ => None,
+    }
+}
+
+fn validate_string_literal(
+    value: &Value,
+    is_valid: fn(&str) -> bool,
+    reason: &str,
+) -> Result<(), String> {
+    match value {
+        Value::String(value) if is_valid(value) => Ok(()),
+        _ => Err(reason.to_string()),
+    }
+}
+
+fn validate_string_value(
+    value: &JsonValue,
+    is_valid: fn(&str) -> bool,
+    reason: &str,
+) -> Result<(), String> {
+    match value {
+        JsonValue::String(value) if is_valid(value) => Ok(()),
+        _ => Err(reason.to_string()),
+    }
+}
+
+/// Date and time strings, as defined by RFC 3339 (for example `2023-01-31T10:15:30.5Z`).
+pub struct DateTimeValidator;
+
+const INVALID_DATE_TIME: &str = "Expected an RFC 3339 date-time string.";
+
+impl ScalarValidator for DateTimeValidator {
+    fn validate_literal(&self, value: &Value) -> Result<(), String> {
+        validate_string_literal(value, is_date_time, INVALID_DATE_TIME)
+    }
+
+    fn validate_value(&self, value: &JsonValue) -> Result<(), String> {
+        validate_string_value(value, is_date_time, INVALID_DATE_TIME)
+    }
+}
+
+/// UUID strings, as defined by RFC 4122 (for example `123e4567-e89b-12d3-a456-426614174000`).
+pub struct UuidValidator;
+
+const INVALID_UUID: &str = "Expected an RFC 4122 UUID string.";
+
+impl ScalarValidator for UuidValidator {
+    fn validate_literal(&self, value: &Value) -> Result<(), String> {
+        validate_string_literal(value, is_uuid, INVALID_UUID)
+    }
+
+    fn validate_value(&self, value: &JsonValue) -> Result<(), String> {
+        validate_string_value(value, is_uuid, INVALID_UUID)
+    }
+}
+
+/// Parses the digits of `value[start..end]`.
+fn parse_digits(value: &[u8], start: usize, end: usize) -> Option<u32> {
+    let digits = value.get(start..end)?;
+
+    if !digits.iter().all(u8::is_ascii_digit) {
+        return None;
+    }
+
+    Some(
+        digits
+            .iter()
+            .fold(0, |number, digit| number * 10 + u32::from(digit - b'0')),
+    )
+}
+
+fn is_date_time(value: &str) -> bool {
+    let bytes = value.as_bytes();
+
+    // YYYY-MM-DDTHH:MM:SS
+    if bytes.len() < 20
+        || bytes[4] != b'-'
+        || bytes[7] != b'-'
+        || !matches!(bytes[10], b'T' | b't')
+        || bytes[13] != b':'
+        || bytes[16] != b':'
+    {
+        return false;
+    }
+
+    let (year, month, day, hour, minute, second) = match (
+        parse_digits(bytes, 0, 4),
+        parse_digits(bytes, 5, 7),
+        parse_digits(bytes, 8, 10),
+        parse_digits(bytes, 11, 13),
+        parse_digits(bytes, 14, 16),
+        parse_digits(bytes, 17, 19),
+    ) {
+        (Some(year), Some(month), Some(day), Some(hour), Some(minute), Some(second)) => {
+            (year, month, day, hour, minute, second)
+        }
+        _ => return false,
+    };
+
+    let is_leap_year = year % 4 == 0 && (year % 100 != 0 || year % 400 == 0);
+    let days_in_month = match month {
+        1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+        4 | 6 | 9 | 11 => 30,
+        2 if is_leap_year => 29,
+        2 => 28,
+        _ => return false,
+    };
+
+    if day == 0 || day > days_in_month || hour > 23 || minute > 59 || second > 59 {
+        return false;
+    }
+
+    // Optional fraction of a second
+    let mut rest = &bytes[19..];
+    if let Some(fraction) = rest.strip_prefix(b".") {
+        let digits = fraction.iter().take_while(|b| b.is_ascii_digit()).count();
+        if digits == 0 {
+            return false;
+        }
+        rest = &fraction[digits..];
+    }
+
+    // Offset
+    match rest {
+        [b'Z' | b'z'] => true,
+        [b'+' | b'-', _, _, b':', _, _] => {
+            matches!(
+                (parse_digits(rest, 1, 3), parse_digits(rest, 4, 6)),
+                (Some(hours), Some(minutes)) if hours <= 23 && minutes <= 59
+            )
+        }
+        _ => false,
+    }
+}
+
+fn is_uuid(value: &str) -> bool {
+    let bytes = value.as_bytes();
+
+    bytes.len() == 36
+        && bytes.iter().enumerate().all(|(index, byte)| match index {
+            8 | 13 | 18 | 23 => *byte == b'-',
+            _ => byte.is_ascii_hexdigit(),
+        })
+}
+
+#[test]
+fn date_time_strings() {
+    for valid in [
+        "2023-01-31T10:15:30Z",
+        "2023-01-31t10:15:30z",
+        "2024-02-29T23:59:59.123456+05:30",
+        "2023-12-01T00:00:00-08:00",
+    ] {
+        assert!(is_date_time(valid), "{} should be valid", valid);
+    }
+
+    for invalid in [
+        "2023-01-31",
+        "2023-01-31T10:15:30",
+        "2023-02-29T10:15:30Z",
+        "2023-13-01T10:15:30Z",
+        "2023-01-31T24:00:00Z",
+        "2023-01-31T10:15:30.Z",
+        "2023-01-31T10:15:30+0530",
+        "2023-01-31 10:15:30Z",
+        "not a date",
+    ] {
+        assert!(!is_date_time(invalid), "{} should be invalid", invalid);
+    }
+}
+
+#[test]
+fn uuid_strings() {
+    assert!(is_uuid("123e4567-e89b-12d3-a456-426614174000"));
+    assert!(is_uuid("123E4567-E89B-12D3-A456-426614174000"));
+    assert!(!is_uuid("123e4567e89b12d3a456426614174000"));
+    assert!(!is_uuid("123e4567-e89b-12d3-a456-42661417400g"));
+}
+
+#[test]
+fn validators_from_specified_by() {
+    let schema = crate::parser::parse_schema::<String>(
+        r#"
+scalar DateTime @specifiedBy(url: "https://scalars.graphql.org/andimarek/date-time")
+scalar UUID @specifiedBy(url: "https://tools.ietf.org/html/rfc4122")
+scalar Custom @specifiedBy(url: "https://example.com/custom")
+scalar JSON
+"#,
+    )
+    .expect("Failed to parse schema")
+    .into_static();
+
+    let validators = ScalarValidators::from_specified_by(&schema);
+
+    assert!(validators.get("Custom").is_none());
+    assert!(validators.get("JSON").is_none());
+
+    let date_time = validators.get("DateTime").unwrap();
+    assert!(date_time
+        .validate_literal(&Value::String("2023-01-31T10:15:30Z".to_string()))
+        .is_ok());
+    assert!(date_time.validate_literal(&Value::Boolean(true)).is_err());
+
+    let uuid = validators.get("UUID").unwrap();
+    assert!(uuid
+        .validate_value(&JsonValue::String(
+            "123e4567-e89b-12d3-a456-426614174000".to_string()
+        ))
+        .is_ok());
+    assert_eq!(
+        validators.coerce_scalar("UUID", &JsonValue::from("abc")),
+        Err(INVALID_UUID.to_string())
+    );
+    assert_eq!(
+        validators.coerce_scalar("JSON", &JsonValue::from(1)),
+        Ok(JsonValue::from(1))
+    );
+}
@@ -1,6 +1,9 @@
+pub mod custom_scalars;
 pub mod rules;
+pub mod schema_rules;
 pub mod utils;
 pub mod validate;
+pub mod validate_schema;
 
 #[cfg(test)]
 pub mod test_utils;
@@ -6,12 +6,12 @@ use super::{
     NoFragmentsCycle, NoUndefinedVariables, NoUnusedFragments, NoUnusedVariables,
     OverlappingFieldsCanBeMerged, PossibleFragmentSpreads, ProvidedRequiredArguments,
     SingleFieldSubscriptions, UniqueArgumentNames, UniqueDirectivesPerLocation,
-    UniqueFragmentNames, UniqueOperationNames, UniqueVariableNames, ValuesOfCorrectType,
-    VariablesAreInputTypes, VariablesInAllowedPosition,
+    UniqueFragmentNames, UniqueInputFieldNames, UniqueOperationNames, UniqueVariableNames,
+    ValuesOfCorrectType, VariablesAreInputTypes, VariablesInAllowedPosition,
 };
 
 pub fn default_rules_validation_plan() -> ValidationPlan {
-    let mut plan = ValidationPlan { rules: vec![] };
+    let mut plan = ValidationPlan::new();
 
     plan.add_rule(Box::new(UniqueOperationNames::new()));
     plan.add_rule(Box::new(LoneAnonymousOperation::new()));
@@ -37,6 +37,7 @@ pub fn default_rules_validation_plan() -> ValidationPlan {
     plan.add_rule(Box::new(VariablesInAllowedPosition::new()));
     plan.add_rule(Box::new(ValuesOfCorrectType::new()));
     plan.add_rule(Box::new(UniqueDirectivesPerLocation::new()));
+    plan.add_rule(Box::new(UniqueInputFieldNames::new()));
 
     plan
 }
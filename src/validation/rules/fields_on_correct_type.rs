@@ -1,7 +1,15 @@
+use std::collections::HashMap;
+
 use crate::ast::ext::TypeDefinitionExtension;
-use crate::ast::{visit_document, FieldByNameExtension, OperationVisitor, OperationVisitorContext};
+use crate::ast::{
+    FieldByNameExtension, ImplementingInterfaceExtension, OperationVisitor,
+    OperationVisitorContext, Schema, SchemaDocumentExtension,
+};
 use crate::static_graphql::query::{Field, OperationDefinition, Selection};
-use crate::validation::utils::{ValidationError, ValidationErrorContext};
+use crate::static_graphql::schema::TypeDefinition;
+use crate::validation::utils::{
+    did_you_mean, natural_compare, suggestion_list, ValidationError, ValidationErrorContext,
+};
 
 use super::ValidationRule;
 
@@ -25,6 +33,90 @@ impl FieldsOnCorrectType {
     }
 }
 
+/// Go through all of the implementations of type, as well as the interfaces that
+/// they implement. If any of those types include the provided field, suggest them,
+/// sorted by how often the type is referenced.
+fn get_suggested_type_names<'a>(
+    schema: &'a Schema,
+    parent_type: &TypeDefinition,
+    field_name: &str,
+) -> Vec<&'a str> {
+    if !parent_type.is_abstract_type() {
+        // Must be an Object type, which does not have possible fields.
+        return vec![];
+    }
+
+    let field_name = field_name.to_string();
+    let mut suggested_types: Vec<&'a TypeDefinition> = vec![];
+    let mut usage_count: HashMap<&str, usize> = HashMap::new();
+
+    for possible_type in schema.possible_types(parent_type) {
+        let possible_type = match schema.type_by_name(&possible_type.name) {
+            Some(type_def) => type_def,
+            None => continue,
+        };
+
+        if possible_type.field_by_name(&field_name).is_none() {
+            continue;
+        }
+
+        suggested_types.push(possible_type);
+        usage_count.insert(possible_type.name(), 1);
+
+        for interface_name in possible_type.interfaces() {
+            if let Some(possible_interface) = schema.type_by_name(&interface_name) {
+                if possible_interface.field_by_name(&field_name).is_none() {
+                    continue;
+                }
+
+                let count = usage_count.entry(possible_interface.name()).or_insert(0);
+
+                if *count == 0 {
+                    suggested_types.push(possible_interface);
+                }
+
+                *count += 1;
+            }
+        }
+    }
+
+    suggested_types.sort_by(|type_a, type_b| {
+        // Suggest both interface and object types based on how common they are.
+        usage_count[type_b.name()]
+            .cmp(&usage_count[type_a.name()])
+            .then_with(|| {
+                // Suggest super types first followed by subtypes
+                if type_a.is_interface_type() && schema.is_possible_type(type_a, type_b) {
+                    std::cmp::Ordering::Less
+                } else if type_b.is_interface_type() && schema.is_possible_type(type_b, type_a) {
+                    std::cmp::Ordering::Greater
+                } else {
+                    std::cmp::Ordering::Equal
+                }
+            })
+            .then_with(|| natural_compare(type_a.name(), type_b.name()))
+    });
+
+    suggested_types.into_iter().map(|t| t.name()).collect()
+}
+
+/// For the field name provided, determine if there are any similar field names
+/// that may be the result of a typo.
+fn get_suggested_field_names<'a>(
+    parent_type: &'a TypeDefinition,
+    field_name: &str,
+) -> Vec<&'a str> {
+    match parent_type {
+        TypeDefinition::Object(object) => {
+            suggestion_list(field_name, object.fields.iter().map(|f| f.name.as_str()))
+        }
+        TypeDefinition::Interface(interface) => {
+            suggestion_list(field_name, interface.fields.iter().map(|f| f.name.as_str()))
+        }
+        _ => vec![],
+    }
+}
+
 impl<'a> OperationVisitor<'a, ValidationErrorContext> for FieldsOnCorrectType {
     fn enter_operation_definition(
         &mut self,
@@ -39,6 +131,7 @@ impl<'a> OperationVisitor<'a, ValidationErrorContext> for FieldsOnCorrectType {
                     if field.name == "__typename" {
                         user_context.report_error(ValidationError {
                           error_code: self.error_code(),
+                          extensions: None,
                           message: "`__typename` may not be included as a root field in a subscription operation".to_string(),
                           locations: vec![subscription.position],
                         });
@@ -63,12 +156,29 @@ impl<'a> OperationVisitor<'a, ValidationErrorContext> for FieldsOnCorrectType {
             }
 
             if parent_type.field_by_name(field_name).is_none() {
+                // First determine if there are any suggested types to condition on.
+                let mut suggestion = did_you_mean(
+                    Some("to use an inline fragment on"),
+                    &get_suggested_type_names(
+                        visitor_context.indexed_schema(),
+                        parent_type,
+                        field_name,
+                    ),
+                );
+
+                // If there are no suggested types, then perhaps this was a typo?
+                if suggestion.is_empty() {
+                    suggestion =
+                        did_you_mean(None, &get_suggested_field_names(parent_type, field_name));
+                }
+
                 user_context.report_error(ValidationError {
                     error_code: self.error_code(),
+                    extensions: None,
                     locations: vec![field.position],
                     message: format!(
-                        "Cannot query field \"{}\" on type \"{}\".",
-                        field_name, type_name
+                        "Cannot query field \"{}\" on type \"{}\".{}",
+                        field_name, type_name, suggestion
                     ),
                 });
             }
@@ -81,17 +191,8 @@ impl ValidationRule for FieldsOnCorrectType {
         "FieldsOnCorrectType"
     }
 
-    fn validate(
-        &self,
-        ctx: &mut OperationVisitorContext,
-        error_collector: &mut ValidationErrorContext,
-    ) {
-        visit_document(
-            &mut FieldsOnCorrectType::new(),
-            ctx.operation,
-            ctx,
-            error_collector,
-        );
+    fn visitor<'a>(&self) -> Option<Box<dyn OperationVisitor<'a, ValidationErrorContext> + 'a>> {
+        Some(Box::new(FieldsOnCorrectType::new()))
     }
 }
 
@@ -234,7 +335,7 @@ fn unknown_query_field() {
         "query test {
           unknownField
         }",
-        &FIELDS_ON_CORRECT_TYPE_TEST_SCHEMA,
+        FIELDS_ON_CORRECT_TYPE_TEST_SCHEMA,
         &mut plan,
     );
 
@@ -255,7 +356,7 @@ fn unknown_mutation_field() {
         "mutation test {
           unknownField
         }",
-        &FIELDS_ON_CORRECT_TYPE_TEST_SCHEMA,
+        FIELDS_ON_CORRECT_TYPE_TEST_SCHEMA,
         &mut plan,
     );
 
@@ -276,7 +377,7 @@ fn unknown_subscription_field() {
         "subscription test {
           unknownField
         }",
-        &FIELDS_ON_CORRECT_TYPE_TEST_SCHEMA,
+        FIELDS_ON_CORRECT_TYPE_TEST_SCHEMA,
         &mut plan,
     );
 
@@ -333,7 +434,7 @@ fn field_not_defined_on_fragment() {
     assert_eq!(messages.len(), 1);
     assert_eq!(
         messages,
-        vec!["Cannot query field \"meowVolume\" on type \"Dog\"."]
+        vec!["Cannot query field \"meowVolume\" on type \"Dog\". Did you mean \"barkVolume\"?"]
     );
 }
 
@@ -402,7 +503,7 @@ fn field_not_defined_on_inline_fragment() {
     assert_eq!(messages.len(), 1);
     assert_eq!(
         messages,
-        vec!["Cannot query field \"meowVolume\" on type \"Dog\"."]
+        vec!["Cannot query field \"meowVolume\" on type \"Dog\". Did you mean \"barkVolume\"?"]
     );
 }
 
@@ -423,7 +524,7 @@ fn aliased_field_target_not_defined() {
     assert_eq!(messages.len(), 1);
     assert_eq!(
         messages,
-        vec!["Cannot query field \"mooVolume\" on type \"Dog\"."]
+        vec!["Cannot query field \"mooVolume\" on type \"Dog\". Did you mean \"barkVolume\"?"]
     );
 }
 
@@ -444,7 +545,7 @@ fn aliased_lying_field_target_not_defined() {
     assert_eq!(messages.len(), 1);
     assert_eq!(
         messages,
-        vec!["Cannot query field \"kawVolume\" on type \"Dog\"."]
+        vec!["Cannot query field \"kawVolume\" on type \"Dog\". Did you mean \"barkVolume\"?"]
     );
 }
 
@@ -486,7 +587,7 @@ fn defined_on_implementors_but_not_on_interface() {
     assert_eq!(messages.len(), 1);
     assert_eq!(
         messages,
-        vec!["Cannot query field \"nickname\" on type \"Pet\"."]
+        vec!["Cannot query field \"nickname\" on type \"Pet\". Did you mean to use an inline fragment on \"Cat\" or \"Dog\"?"]
     );
 }
 
@@ -528,7 +629,7 @@ fn defined_on_implementors_queried_on_union() {
     assert_eq!(messages.len(), 1);
     assert_eq!(
         messages,
-        vec!["Cannot query field \"name\" on type \"CatOrDog\"."]
+        vec!["Cannot query field \"name\" on type \"CatOrDog\". Did you mean to use an inline fragment on \"Pet\", \"Cat\", or \"Dog\"?"]
     );
 }
 
@@ -591,3 +692,66 @@ fn forbidden_typename_on_subscription_type() {
         vec!["`__typename` may not be included as a root field in a subscription operation"]
     );
 }
+
+#[test]
+fn sort_type_suggestions_based_on_inheritance_order() {
+    use crate::validation::test_utils::*;
+
+    let mut plan = create_plan_from_rule(Box::new(FieldsOnCorrectType {}));
+    let errors = test_operation_with_schema(
+        "{ t { foo } }",
+        "
+        interface T { bar: String }
+        type Query { t: T }
+
+        interface Z implements T {
+          foo: String
+          bar: String
+        }
+
+        interface Y implements Z & T {
+          foo: String
+          bar: String
+        }
+
+        type X implements Y & Z & T {
+          foo: String
+          bar: String
+        }",
+        &mut plan,
+    );
+
+    let messages = get_messages(&errors);
+    assert_eq!(messages.len(), 1);
+    assert_eq!(
+        messages,
+        vec!["Cannot query field \"foo\" on type \"T\". Did you mean to use an inline fragment on \"Z\", \"Y\", or \"X\"?"]
+    );
+}
+
+#[test]
+fn limits_field_suggestions() {
+    use crate::validation::test_utils::*;
+
+    let mut plan = create_plan_from_rule(Box::new(FieldsOnCorrectType {}));
+    let errors = test_operation_with_schema(
+        "{ f }",
+        "
+        type Query {
+          z: String
+          y: String
+          x: String
+          w: String
+          v: String
+          u: String
+        }",
+        &mut plan,
+    );
+
+    let messages = get_messages(&errors);
+    assert_eq!(messages.len(), 1);
+    assert_eq!(
+        messages,
+        vec!["Cannot query field \"f\" on type \"Query\". Did you mean \"u\", \"v\", \"w\", \"x\", or \"y\"?"]
+    );
+}
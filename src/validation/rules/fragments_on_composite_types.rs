@@ -1,8 +1,5 @@
 use super::ValidationRule;
-use crate::ast::{
-    visit_document, OperationVisitor, OperationVisitorContext, SchemaDocumentExtension,
-    TypeDefinitionExtension,
-};
+use crate::ast::{OperationVisitor, OperationVisitorContext, TypeDefinitionExtension};
 use crate::static_graphql::query::*;
 use crate::validation::utils::{ValidationError, ValidationErrorContext};
 
@@ -35,11 +32,15 @@ impl<'a> OperationVisitor<'a, ValidationErrorContext> for FragmentsOnCompositeTy
         inline_fragment: &InlineFragment,
     ) {
         if let Some(TypeCondition::On(type_condition)) = &inline_fragment.type_condition {
-            if let Some(gql_type) = visitor_context.schema.type_by_name(type_condition) {
+            if let Some(gql_type) = visitor_context
+                .indexed_schema()
+                .type_by_name(type_condition)
+            {
                 if !gql_type.is_composite_type() {
                     user_context.report_error(ValidationError {
                         locations: vec![inline_fragment.position],
                         error_code: self.error_code(),
+                        extensions: None,
                         message: format!(
                             "Fragment cannot condition on non composite type \"{}\".",
                             type_condition
@@ -58,11 +59,15 @@ impl<'a> OperationVisitor<'a, ValidationErrorContext> for FragmentsOnCompositeTy
     ) {
         let TypeCondition::On(type_condition) = &fragment_definition.type_condition;
 
-        if let Some(gql_type) = visitor_context.schema.type_by_name(type_condition) {
+        if let Some(gql_type) = visitor_context
+            .indexed_schema()
+            .type_by_name(type_condition)
+        {
             if !gql_type.is_composite_type() {
                 user_context.report_error(ValidationError {
                     locations: vec![fragment_definition.position],
                     error_code: self.error_code(),
+                    extensions: None,
                     message: format!(
                         "Fragment \"{}\" cannot condition on non composite type \"{}\".",
                         fragment_definition.name, type_condition
@@ -78,17 +83,8 @@ impl ValidationRule for FragmentsOnCompositeTypes {
         "FragmentsOnCompositeTypes"
     }
 
-    fn validate(
-        &self,
-        ctx: &mut OperationVisitorContext,
-        error_collector: &mut ValidationErrorContext,
-    ) {
-        visit_document(
-            &mut FragmentsOnCompositeTypes::new(),
-            ctx.operation,
-            ctx,
-            error_collector,
-        );
+    fn visitor<'a>(&self) -> Option<Box<dyn OperationVisitor<'a, ValidationErrorContext> + 'a>> {
+        Some(Box::new(FragmentsOnCompositeTypes::new()))
     }
 }
 
@@ -1,12 +1,11 @@
 use super::ValidationRule;
 use crate::ast::ext::TypeDefinitionExtension;
-use crate::ast::{
-    visit_document, FieldByNameExtension, OperationVisitor, OperationVisitorContext,
-    SchemaDocumentExtension,
-};
+use crate::ast::{FieldByNameExtension, OperationVisitor, OperationVisitorContext};
 use crate::static_graphql::query::Directive;
 use crate::static_graphql::schema::{InputValue, TypeDefinition};
-use crate::validation::utils::{ValidationError, ValidationErrorContext};
+use crate::validation::utils::{
+    did_you_mean, suggestion_list, ValidationError, ValidationErrorContext,
+};
 /// Known argument names
 ///
 /// A GraphQL field/directive is only valid if all supplied arguments are defined by
@@ -45,7 +44,10 @@ impl<'a> OperationVisitor<'a, ValidationErrorContext> for KnownArgumentNames<'a>
         _: &mut ValidationErrorContext,
         directive: &Directive,
     ) {
-        if let Some(directive_def) = visitor_context.schema.directive_by_name(&directive.name) {
+        if let Some(directive_def) = visitor_context
+            .indexed_schema()
+            .directive_by_name(&directive.name)
+        {
             self.current_known_arguments = Some((
                 ArgumentParent::Directive(&directive_def.name),
                 &directive_def.arguments,
@@ -94,33 +96,43 @@ impl<'a> OperationVisitor<'a, ValidationErrorContext> for KnownArgumentNames<'a>
 
     fn enter_argument(
         &mut self,
-        _: &mut OperationVisitorContext,
+        visitor_context: &mut OperationVisitorContext,
         user_context: &mut ValidationErrorContext,
         (argument_name, _argument_value): &(String, crate::static_graphql::query::Value),
     ) {
+        let argument_position = visitor_context.current_position();
+
         if let Some((arg_position, args)) = &self.current_known_arguments {
             if !args.iter().any(|a| a.name.eq(argument_name)) {
+                let suggestions = did_you_mean(
+                    None,
+                    &suggestion_list(argument_name, args.iter().map(|a| a.name.as_str())),
+                );
+
                 match arg_position {
                     ArgumentParent::Field(field_name, type_name) => {
                         user_context.report_error(ValidationError {
                             error_code: self.error_code(),
+                            extensions: None,
                             message: format!(
-                                "Unknown argument \"{}\" on field \"{}.{}\".",
+                                "Unknown argument \"{}\" on field \"{}.{}\".{}",
                                 argument_name,
                                 type_name.name(),
-                                field_name
+                                field_name,
+                                suggestions
                             ),
-                            locations: vec![],
+                            locations: vec![argument_position],
                         })
                     }
                     ArgumentParent::Directive(directive_name) => {
                         user_context.report_error(ValidationError {
                             error_code: self.error_code(),
+                            extensions: None,
                             message: format!(
-                                "Unknown argument \"{}\" on directive \"@{}\".",
-                                argument_name, directive_name
+                                "Unknown argument \"{}\" on directive \"@{}\".{}",
+                                argument_name, directive_name, suggestions
                             ),
-                            locations: vec![],
+                            locations: vec![argument_position],
                         })
                     }
                 };
@@ -134,17 +146,8 @@ impl<'k> ValidationRule for KnownArgumentNames<'k> {
         "KnownArgumentNames"
     }
 
-    fn validate(
-        &self,
-        ctx: &mut OperationVisitorContext,
-        error_collector: &mut ValidationErrorContext,
-    ) {
-        visit_document(
-            &mut KnownArgumentNames::new(),
-            ctx.operation,
-            ctx,
-            error_collector,
-        );
+    fn visitor<'a>(&self) -> Option<Box<dyn OperationVisitor<'a, ValidationErrorContext> + 'a>> {
+        Some(Box::new(KnownArgumentNames::new()))
     }
 }
 
@@ -288,6 +291,13 @@ fn field_args_are_invalid() {
         messages,
         vec!["Unknown argument \"unless\" on directive \"@skip\"."]
     );
+    assert_eq!(
+        errors[0].locations,
+        vec![Pos {
+            line: 2,
+            column: 21
+        }]
+    );
 }
 
 #[test]
@@ -326,10 +336,16 @@ fn arg_passed_to_directive_without_arg_is_reported() {
         messages,
         vec!["Unknown argument \"if\" on directive \"@onField\"."]
     );
+    assert_eq!(
+        errors[0].locations,
+        vec![Pos {
+            line: 2,
+            column: 24
+        }]
+    );
 }
 
 #[test]
-#[ignore = "Suggestions are not yet supported"]
 fn misspelled_directive_args_are_reported() {
     use crate::validation::test_utils::*;
 
@@ -346,7 +362,14 @@ fn misspelled_directive_args_are_reported() {
     assert_eq!(messages.len(), 1);
     assert_eq!(
         messages,
-        vec!["Unknown argument \"iff\" on directive \"@onField\". Did you mean \"if\"?"]
+        vec!["Unknown argument \"iff\" on directive \"@skip\". Did you mean \"if\"?"]
+    );
+    assert_eq!(
+        errors[0].locations,
+        vec![Pos {
+            line: 2,
+            column: 21
+        }]
     );
 }
 
@@ -369,10 +392,16 @@ fn invalid_arg_name() {
         messages,
         vec!["Unknown argument \"unknown\" on field \"Dog.doesKnowCommand\"."]
     );
+    assert_eq!(
+        errors[0].locations,
+        vec![Pos {
+            line: 2,
+            column: 27
+        }]
+    );
 }
 
 #[test]
-#[ignore = "Suggestions are not yet supported"]
 fn misspelled_arg_name_is_reported() {
     use crate::validation::test_utils::*;
 
@@ -391,6 +420,13 @@ fn misspelled_arg_name_is_reported() {
         messages,
         vec!["Unknown argument \"DogCommand\" on field \"Dog.doesKnowCommand\". Did you mean \"dogCommand\"?"]
     );
+    assert_eq!(
+        errors[0].locations,
+        vec![Pos {
+            line: 2,
+            column: 27
+        }]
+    );
 }
 
 #[test]
@@ -415,6 +451,20 @@ fn unknown_args_amongst_known_args() {
             "Unknown argument \"unknown\" on field \"Dog.doesKnowCommand\"."
         ]
     );
+    assert_eq!(
+        errors[0].locations,
+        vec![Pos {
+            line: 2,
+            column: 27
+        }]
+    );
+    assert_eq!(
+        errors[1].locations,
+        vec![Pos {
+            line: 2,
+            column: 57
+        }]
+    );
 }
 
 #[test]
@@ -448,4 +498,18 @@ fn unknown_args_deeply() {
             "Unknown argument \"unknown\" on field \"Dog.doesKnowCommand\"."
         ]
     );
+    assert_eq!(
+        errors[0].locations,
+        vec![Pos {
+            line: 3,
+            column: 29
+        }]
+    );
+    assert_eq!(
+        errors[1].locations,
+        vec![Pos {
+            line: 8,
+            column: 33
+        }]
+    );
 }
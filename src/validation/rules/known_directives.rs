@@ -1,5 +1,5 @@
 use super::ValidationRule;
-use crate::ast::{visit_document, OperationVisitor, OperationVisitorContext};
+use crate::ast::{OperationVisitor, OperationVisitorContext};
 use crate::static_graphql::query::{
     Directive, Field, FragmentDefinition, InlineFragment, OperationDefinition,
 };
@@ -132,7 +132,10 @@ impl<'a> OperationVisitor<'a, ValidationErrorContext> for KnownDirectives {
         user_context: &mut ValidationErrorContext,
         directive: &Directive,
     ) {
-        if let Some(directive_type) = visitor_context.directives.get(&directive.name) {
+        if let Some(directive_type) = visitor_context
+            .indexed_schema()
+            .directive_by_name(&directive.name)
+        {
             if let Some(current_location) = &self.recent_location {
                 if !directive_type
                     .locations
@@ -141,6 +144,7 @@ impl<'a> OperationVisitor<'a, ValidationErrorContext> for KnownDirectives {
                 {
                     user_context.report_error(ValidationError {
                         error_code: self.error_code(),
+                        extensions: None,
                         locations: vec![directive.position],
                         message: format!(
                             "Directive \"@{}\" may not be used on {}",
@@ -153,6 +157,7 @@ impl<'a> OperationVisitor<'a, ValidationErrorContext> for KnownDirectives {
         } else {
             user_context.report_error(ValidationError {
                 error_code: self.error_code(),
+                extensions: None,
                 locations: vec![directive.position],
                 message: format!("Unknown directive \"@{}\".", directive.name),
             });
@@ -165,17 +170,8 @@ impl ValidationRule for KnownDirectives {
         "KnownDirectives"
     }
 
-    fn validate(
-        &self,
-        ctx: &mut OperationVisitorContext,
-        error_collector: &mut ValidationErrorContext,
-    ) {
-        visit_document(
-            &mut KnownDirectives::new(),
-            ctx.operation,
-            ctx,
-            error_collector,
-        );
+    fn visitor<'a>(&self) -> Option<Box<dyn OperationVisitor<'a, ValidationErrorContext> + 'a>> {
+        Some(Box::new(KnownDirectives::new()))
     }
 }
 
@@ -1,7 +1,9 @@
 use super::ValidationRule;
-use crate::ast::{visit_document, OperationVisitor, OperationVisitorContext};
+use crate::ast::{OperationVisitor, OperationVisitorContext};
 use crate::static_graphql::query::*;
-use crate::validation::utils::{ValidationError, ValidationErrorContext};
+use crate::validation::utils::{
+    did_you_mean, suggestion_list, ValidationError, ValidationErrorContext,
+};
 
 /// Known fragment names
 ///
@@ -34,10 +36,20 @@ impl<'a> OperationVisitor<'a, ValidationErrorContext> for KnownFragmentNames {
             .known_fragments
             .contains_key(fragment_spread.fragment_name.as_str())
         {
+            let suggestions = suggestion_list(
+                &fragment_spread.fragment_name,
+                visitor_context.known_fragments.keys().copied(),
+            );
+
             user_context.report_error(ValidationError {
                 error_code: self.error_code(),
+                extensions: None,
                 locations: vec![fragment_spread.position],
-                message: format!("Unknown fragment \"{}\".", fragment_spread.fragment_name),
+                message: format!(
+                    "Unknown fragment \"{}\".{}",
+                    fragment_spread.fragment_name,
+                    did_you_mean(None, &suggestions)
+                ),
             })
         }
     }
@@ -48,17 +60,8 @@ impl ValidationRule for KnownFragmentNames {
         "KnownFragmentNames"
     }
 
-    fn validate(
-        &self,
-        ctx: &mut OperationVisitorContext,
-        error_collector: &mut ValidationErrorContext,
-    ) {
-        visit_document(
-            &mut KnownFragmentNames::new(),
-            ctx.operation,
-            ctx,
-            error_collector,
-        );
+    fn visitor<'a>(&self) -> Option<Box<dyn OperationVisitor<'a, ValidationErrorContext> + 'a>> {
+        Some(Box::new(KnownFragmentNames::new()))
     }
 }
 
@@ -129,3 +132,29 @@ fn invalid_fragment() {
         ]
     );
 }
+
+#[test]
+fn misspelled_fragment_name_is_reported() {
+    use crate::validation::test_utils::*;
+
+    let mut plan = create_plan_from_rule(Box::new(KnownFragmentNames {}));
+    let errors = test_operation_with_schema(
+        "{
+          human(id: 4) {
+            ...HumanFeilds
+          }
+        }
+        fragment HumanFields on Human {
+          name
+        }",
+        TEST_SCHEMA,
+        &mut plan,
+    );
+
+    let messages = get_messages(&errors);
+    assert_eq!(messages.len(), 1);
+    assert_eq!(
+        messages,
+        vec!["Unknown fragment \"HumanFeilds\". Did you mean \"HumanFields\"?"]
+    );
+}
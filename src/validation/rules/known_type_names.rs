@@ -1,10 +1,12 @@
 use super::ValidationRule;
 use crate::ast::{
-    visit_document, OperationVisitor, OperationVisitorContext, SchemaDocumentExtension,
-    TypeExtension,
+    OperationVisitor, OperationVisitorContext, Schema, SchemaDocumentExtension, TypeExtension,
 };
+use crate::parser::Pos;
 use crate::static_graphql::query::TypeCondition;
-use crate::validation::utils::{ValidationError, ValidationErrorContext};
+use crate::validation::utils::{
+    did_you_mean, suggestion_list, ValidationError, ValidationErrorContext,
+};
 
 /// Known type names
 ///
@@ -24,6 +26,27 @@ impl KnownTypeNames {
     pub fn new() -> Self {
         KnownTypeNames
     }
+
+    fn report_unknown_type(
+        &self,
+        schema: &Schema,
+        user_context: &mut ValidationErrorContext,
+        type_name: &str,
+        position: Pos,
+    ) {
+        let suggested_types = suggestion_list(type_name, schema.type_map().keys().copied());
+
+        user_context.report_error(ValidationError {
+            error_code: self.error_code(),
+            extensions: None,
+            locations: vec![position],
+            message: format!(
+                "Unknown type \"{}\".{}",
+                type_name,
+                did_you_mean(None, &suggested_types)
+            ),
+        });
+    }
 }
 
 impl<'a> OperationVisitor<'a, ValidationErrorContext> for KnownTypeNames {
@@ -35,12 +58,18 @@ impl<'a> OperationVisitor<'a, ValidationErrorContext> for KnownTypeNames {
     ) {
         let TypeCondition::On(fragment_type_name) = &fragment_definition.type_condition;
 
-        if visitor_context.schema.type_by_name(fragment_type_name).is_none() && !fragment_type_name.starts_with("__") {
-            user_context.report_error(ValidationError {
-                error_code: self.error_code(),
-                locations: vec![fragment_definition.position],
-                message: format!("Unknown type \"{}\".", fragment_type_name),
-            });
+        if visitor_context
+            .schema
+            .type_by_name(fragment_type_name)
+            .is_none()
+            && !fragment_type_name.starts_with("__")
+        {
+            self.report_unknown_type(
+                visitor_context.indexed_schema(),
+                user_context,
+                fragment_type_name,
+                fragment_definition.position,
+            );
         }
     }
 
@@ -51,12 +80,18 @@ impl<'a> OperationVisitor<'a, ValidationErrorContext> for KnownTypeNames {
         inline_fragment: &crate::static_graphql::query::InlineFragment,
     ) {
         if let Some(TypeCondition::On(fragment_type_name)) = &inline_fragment.type_condition {
-            if visitor_context.schema.type_by_name(fragment_type_name).is_none() && !fragment_type_name.starts_with("__") {
-                user_context.report_error(ValidationError {
-                    error_code: self.error_code(),
-                    locations: vec![inline_fragment.position],
-                    message: format!("Unknown type \"{}\".", fragment_type_name),
-                });
+            if visitor_context
+                .schema
+                .type_by_name(fragment_type_name)
+                .is_none()
+                && !fragment_type_name.starts_with("__")
+            {
+                self.report_unknown_type(
+                    visitor_context.indexed_schema(),
+                    user_context,
+                    fragment_type_name,
+                    inline_fragment.position,
+                );
             }
         }
     }
@@ -69,12 +104,18 @@ impl<'a> OperationVisitor<'a, ValidationErrorContext> for KnownTypeNames {
     ) {
         let base_type = variable_definition.var_type.inner_type();
 
-        if visitor_context.schema.type_by_name(base_type).is_none() && !base_type.starts_with("__") {
-            user_context.report_error(ValidationError {
-                error_code: self.error_code(),
-                locations: vec![variable_definition.position],
-                message: format!("Unknown type \"{}\".", base_type),
-            });
+        if visitor_context
+            .indexed_schema()
+            .type_by_name(base_type)
+            .is_none()
+            && !base_type.starts_with("__")
+        {
+            self.report_unknown_type(
+                visitor_context.indexed_schema(),
+                user_context,
+                base_type,
+                variable_definition.position,
+            );
         }
     }
 }
@@ -84,17 +125,8 @@ impl ValidationRule for KnownTypeNames {
         "KnownTypeNames"
     }
 
-    fn validate(
-        &self,
-        ctx: &mut OperationVisitorContext,
-        error_collector: &mut ValidationErrorContext,
-    ) {
-        visit_document(
-            &mut KnownTypeNames::new(),
-            ctx.operation,
-            ctx,
-            error_collector,
-        );
+    fn visitor<'a>(&self) -> Option<Box<dyn OperationVisitor<'a, ValidationErrorContext> + 'a>> {
+        Some(Box::new(KnownTypeNames::new()))
     }
 }
 
@@ -152,7 +184,7 @@ fn unknown_type_names_are_invalid() {
         vec![
             "Unknown type \"JumbledUpLetters\".",
             "Unknown type \"Badger\".",
-            "Unknown type \"Peat\"."
+            "Unknown type \"Peat\". Did you mean \"Pet\" or \"Cat\"?"
         ]
     );
 }
@@ -1,6 +1,6 @@
 use super::ValidationRule;
 use crate::{
-    ast::{visit_document, OperationVisitor, OperationVisitorContext, TypeDefinitionExtension},
+    ast::{OperationVisitor, OperationVisitorContext, TypeDefinitionExtension},
     validation::utils::{ValidationError, ValidationErrorContext},
 };
 
@@ -40,6 +40,7 @@ impl<'a> OperationVisitor<'a, ValidationErrorContext> for LeafFieldSelections {
                 if field_selection_count > 0 {
                     user_context.report_error(ValidationError {
                         error_code: self.error_code(),
+                        extensions: None,
                         locations: vec![field.position],
                         message: format!(
                   "Field \"{}\" must not have a selection since type \"{}\" has no subfields.",
@@ -49,7 +50,8 @@ impl<'a> OperationVisitor<'a, ValidationErrorContext> for LeafFieldSelections {
                     });
                 }
             } else if field_selection_count == 0 {
-                      user_context.report_error(ValidationError {error_code: self.error_code(),
+                user_context.report_error(ValidationError {error_code: self.error_code(),
+                extensions: None,
                 locations: vec![field.position],
                 message: format!(
                     "Field \"{}\" of type \"{}\" must have a selection of subfields. Did you mean \"{} {{ ... }}\"?",
@@ -58,7 +60,7 @@ impl<'a> OperationVisitor<'a, ValidationErrorContext> for LeafFieldSelections {
                     field.name
                 ),
             });
-                  }
+            }
         }
     }
 }
@@ -68,17 +70,8 @@ impl ValidationRule for LeafFieldSelections {
         "LeafFieldSelections"
     }
 
-    fn validate(
-        &self,
-        ctx: &mut OperationVisitorContext,
-        error_collector: &mut ValidationErrorContext,
-    ) {
-        visit_document(
-            &mut LeafFieldSelections::new(),
-            ctx.operation,
-            ctx,
-            error_collector,
-        );
+    fn visitor<'a>(&self) -> Option<Box<dyn OperationVisitor<'a, ValidationErrorContext> + 'a>> {
+        Some(Box::new(LeafFieldSelections::new()))
     }
 }
 
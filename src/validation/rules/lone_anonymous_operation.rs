@@ -1,5 +1,5 @@
 use super::ValidationRule;
-use crate::ast::{visit_document, OperationVisitor, OperationVisitorContext};
+use crate::ast::{OperationVisitor, OperationVisitorContext};
 use crate::static_graphql::query::*;
 use crate::validation::utils::{ValidationError, ValidationErrorContext};
 
@@ -33,56 +33,54 @@ impl<'a> OperationVisitor<'a, ValidationErrorContext> for LoneAnonymousOperation
         let operations_count = document
             .definitions
             .iter()
-            .filter(|n| match n {
-                Definition::Operation(OperationDefinition::SelectionSet(_)) => true,
-                Definition::Operation(OperationDefinition::Query(_)) => true,
-                Definition::Operation(OperationDefinition::Mutation(_)) => true,
-                Definition::Operation(OperationDefinition::Subscription(_)) => true,
-                _ => false,
-            })
+            .filter(|n| matches!(n, Definition::Operation(_)))
             .count();
 
         for definition in &document.definitions {
             match definition {
-                Definition::Operation(OperationDefinition::SelectionSet(_)) => {
-                    if operations_count > 1 {
-                        user_context.report_error(ValidationError {
-                            error_code: self.error_code(),
-                            message: "This anonymous operation must be the only defined operation."
-                                .to_string(),
-                            locations: vec![],
-                        })
-                    }
+                Definition::Operation(OperationDefinition::SelectionSet(selection_set))
+                    if operations_count > 1 =>
+                {
+                    user_context.report_error(ValidationError {
+                        error_code: self.error_code(),
+                        extensions: None,
+                        message: "This anonymous operation must be the only defined operation."
+                            .to_string(),
+                        locations: vec![selection_set.span.0],
+                    })
                 }
-                Definition::Operation(OperationDefinition::Query(query)) => {
-                    if query.name.is_none() && operations_count > 1 {
-                        user_context.report_error(ValidationError {
-                            error_code: self.error_code(),
-                            message: "This anonymous operation must be the only defined operation."
-                                .to_string(),
-                            locations: vec![query.position],
-                        })
-                    }
+                Definition::Operation(OperationDefinition::Query(query))
+                    if query.name.is_none() && operations_count > 1 =>
+                {
+                    user_context.report_error(ValidationError {
+                        error_code: self.error_code(),
+                        extensions: None,
+                        message: "This anonymous operation must be the only defined operation."
+                            .to_string(),
+                        locations: vec![query.position],
+                    })
                 }
-                Definition::Operation(OperationDefinition::Mutation(mutation)) => {
-                    if mutation.name.is_none() && operations_count > 1 {
-                        user_context.report_error(ValidationError {
-                            error_code: self.error_code(),
-                            message: "This anonymous operation must be the only defined operation."
-                                .to_string(),
-                            locations: vec![mutation.position],
-                        })
-                    }
+                Definition::Operation(OperationDefinition::Mutation(mutation))
+                    if mutation.name.is_none() && operations_count > 1 =>
+                {
+                    user_context.report_error(ValidationError {
+                        error_code: self.error_code(),
+                        extensions: None,
+                        message: "This anonymous operation must be the only defined operation."
+                            .to_string(),
+                        locations: vec![mutation.position],
+                    })
                 }
-                Definition::Operation(OperationDefinition::Subscription(subscription)) => {
-                    if subscription.name.is_none() && operations_count > 1 {
-                        user_context.report_error(ValidationError {
-                            error_code: self.error_code(),
-                            message: "This anonymous operation must be the only defined operation."
-                                .to_string(),
-                            locations: vec![subscription.position],
-                        })
-                    }
+                Definition::Operation(OperationDefinition::Subscription(subscription))
+                    if subscription.name.is_none() && operations_count > 1 =>
+                {
+                    user_context.report_error(ValidationError {
+                        error_code: self.error_code(),
+                        extensions: None,
+                        message: "This anonymous operation must be the only defined operation."
+                            .to_string(),
+                        locations: vec![subscription.position],
+                    })
                 }
                 _ => {}
             };
@@ -95,17 +93,8 @@ impl ValidationRule for LoneAnonymousOperation {
         "LoneAnonymousOperation"
     }
 
-    fn validate(
-        &self,
-        ctx: &mut OperationVisitorContext,
-        error_collector: &mut ValidationErrorContext,
-    ) {
-        visit_document(
-            &mut LoneAnonymousOperation::new(),
-            ctx.operation,
-            ctx,
-            error_collector,
-        );
+    fn visitor<'a>(&self) -> Option<Box<dyn OperationVisitor<'a, ValidationErrorContext> + 'a>> {
+        Some(Box::new(LoneAnonymousOperation::new()))
     }
 }
 
@@ -204,6 +193,8 @@ fn multiple_anon_operations() {
             "This anonymous operation must be the only defined operation."
         ]
     );
+    assert_eq!(errors[0].locations, vec![Pos { line: 1, column: 1 }]);
+    assert_eq!(errors[1].locations, vec![Pos { line: 4, column: 9 }]);
 }
 
 #[test]
@@ -228,6 +219,7 @@ fn anon_operation_with_mutation() {
         messages,
         vec!["This anonymous operation must be the only defined operation."]
     );
+    assert_eq!(errors[0].locations, vec![Pos { line: 1, column: 1 }]);
 }
 
 #[test]
@@ -252,4 +244,5 @@ fn anon_operation_with_subscription() {
         messages,
         vec!["This anonymous operation must be the only defined operation."]
     );
+    assert_eq!(errors[0].locations, vec![Pos { line: 1, column: 1 }]);
 }
@@ -20,6 +20,7 @@ pub mod single_field_subscriptions;
 pub mod unique_argument_names;
 pub mod unique_directives_per_location;
 pub mod unique_fragment_names;
+pub mod unique_input_field_names;
 pub mod unique_operation_names;
 pub mod unique_variable_names;
 pub mod values_of_correct_type;
@@ -48,6 +49,7 @@ pub use self::single_field_subscriptions::*;
 pub use self::unique_argument_names::*;
 pub use self::unique_directives_per_location::*;
 pub use self::unique_fragment_names::*;
+pub use self::unique_input_field_names::*;
 pub use self::unique_operation_names::*;
 pub use self::unique_variable_names::*;
 pub use self::values_of_correct_type::*;
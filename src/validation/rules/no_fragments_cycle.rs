@@ -1,6 +1,6 @@
 use super::ValidationRule;
 use crate::ast::ext::{AstNodeWithName, FragmentSpreadExtraction};
-use crate::ast::{visit_document, OperationVisitor, OperationVisitorContext};
+use crate::ast::{OperationVisitor, OperationVisitorContext};
 use crate::static_graphql::query::{FragmentDefinition, FragmentSpread};
 use crate::validation::utils::{ValidationError, ValidationErrorContext};
 use std::collections::{HashMap, HashSet};
@@ -81,6 +81,7 @@ impl NoFragmentsCycle {
 
                     error_context.report_error(ValidationError {
                         error_code: self.error_code(),
+                        extensions: None,
                         locations: cycle_path.iter().map(|f| f.position).collect(),
                         message: match via_path.len() {
                             0 => {
@@ -128,17 +129,8 @@ impl ValidationRule for NoFragmentsCycle {
         "NoFragmentsCycle"
     }
 
-    fn validate(
-        &self,
-        ctx: &mut OperationVisitorContext,
-        error_collector: &mut ValidationErrorContext,
-    ) {
-        visit_document(
-            &mut NoFragmentsCycle::new(),
-            ctx.operation,
-            ctx,
-            error_collector,
-        );
+    fn visitor<'a>(&self) -> Option<Box<dyn OperationVisitor<'a, ValidationErrorContext> + 'a>> {
+        Some(Box::new(NoFragmentsCycle::new()))
     }
 }
 
@@ -247,6 +239,22 @@ fn spreading_recursively_within_field_fails() {
     assert_eq!(mes, vec!["Cannot spread fragment \"fragA\" within itself."]);
 }
 
+#[test]
+fn spreading_recursively_within_nested_fields_fails() {
+    use crate::validation::test_utils::*;
+
+    let mut plan = create_plan_from_rule(Box::new(NoFragmentsCycle::new()));
+    let errors = test_operation_with_schema(
+        "fragment fragA on Human { relatives { relatives { ... on Human { ...fragA } } } }",
+        TEST_SCHEMA,
+        &mut plan,
+    );
+
+    let mes = get_messages(&errors);
+    assert_eq!(mes.len(), 1);
+    assert_eq!(mes, vec!["Cannot spread fragment \"fragA\" within itself."]);
+}
+
 #[test]
 fn no_spreading_itself_directly() {
     use crate::validation::test_utils::*;
@@ -1,7 +1,8 @@
 use super::ValidationRule;
 use crate::ast::{
-    visit_document, AstNodeWithName, OperationVisitor, OperationVisitorContext, ValueExtension,
+    AstNodeWithName, OperationDefinitionExtension, OperationVisitor, OperationVisitorContext,
 };
+use crate::parser::Pos;
 use crate::static_graphql::query::{self, OperationDefinition};
 use crate::validation::utils::{ValidationError, ValidationErrorContext};
 use std::collections::{HashMap, HashSet};
@@ -15,7 +16,8 @@ use std::collections::{HashMap, HashSet};
 pub struct NoUndefinedVariables<'a> {
     current_scope: Option<NoUndefinedVariablesScope<'a>>,
     defined_variables: HashMap<Option<&'a str>, HashSet<&'a str>>,
-    used_variables: HashMap<NoUndefinedVariablesScope<'a>, Vec<&'a str>>,
+    operation_positions: HashMap<Option<&'a str>, Pos>,
+    used_variables: HashMap<NoUndefinedVariablesScope<'a>, Vec<(&'a str, Pos)>>,
     spreads: HashMap<NoUndefinedVariablesScope<'a>, Vec<&'a str>>,
 }
 
@@ -30,6 +32,7 @@ impl<'a> NoUndefinedVariables<'a> {
         Self {
             current_scope: None,
             defined_variables: HashMap::new(),
+            operation_positions: HashMap::new(),
             used_variables: HashMap::new(),
             spreads: HashMap::new(),
         }
@@ -41,7 +44,7 @@ impl<'a> NoUndefinedVariables<'a> {
         &self,
         from: &NoUndefinedVariablesScope<'a>,
         defined: &HashSet<&str>,
-        unused: &mut HashSet<&'a str>,
+        unused: &mut Vec<(&'a str, Pos)>,
         visited: &mut HashSet<NoUndefinedVariablesScope<'a>>,
     ) {
         if visited.contains(from) {
@@ -51,9 +54,9 @@ impl<'a> NoUndefinedVariables<'a> {
         visited.insert(from.clone());
 
         if let Some(used_vars) = self.used_variables.get(from) {
-            for var in used_vars {
-                if !defined.contains(*var) {
-                    unused.insert(*var);
+            for (var, position) in used_vars {
+                if !defined.contains(*var) && !unused.iter().any(|(name, _)| name == var) {
+                    unused.push((*var, *position));
                 }
             }
         }
@@ -87,6 +90,8 @@ impl<'a> OperationVisitor<'a, ValidationErrorContext> for NoUndefinedVariables<'
         let op_name = operation_definition.node_name();
         self.current_scope = Some(NoUndefinedVariablesScope::Operation(op_name));
         self.defined_variables.insert(op_name, HashSet::new());
+        self.operation_positions
+            .insert(op_name, operation_definition.position());
     }
 
     fn enter_fragment_definition(
@@ -127,17 +132,19 @@ impl<'a> OperationVisitor<'a, ValidationErrorContext> for NoUndefinedVariables<'
         }
     }
 
-    fn enter_argument(
+    fn enter_variable_value(
         &mut self,
-        _: &mut OperationVisitorContext,
+        visitor_context: &mut OperationVisitorContext,
         _: &mut ValidationErrorContext,
-        (_arg_name, arg_value): &'a (String, query::Value),
+        variable_name: &'a str,
     ) {
+        let position = visitor_context.current_position();
+
         if let Some(ref scope) = self.current_scope {
             self.used_variables
                 .entry(scope.clone())
                 .or_default()
-                .append(&mut arg_value.variables_in_use());
+                .push((variable_name, position));
         }
     }
 
@@ -147,22 +154,27 @@ impl<'a> OperationVisitor<'a, ValidationErrorContext> for NoUndefinedVariables<'
         user_context: &mut ValidationErrorContext,
         _: &query::Document,
     ) {
-        for (op_name, def_vars) in &self.defined_variables {
-            let mut unused = HashSet::new();
+        // Report in document order
+        let mut operations: Vec<_> = self.operation_positions.iter().collect();
+        operations.sort_by_key(|(_, position)| **position);
+
+        for (op_name, operation_position) in operations {
+            let mut unused = Vec::new();
             let mut visited = HashSet::new();
 
             self.find_undefined_vars(
                 &NoUndefinedVariablesScope::Operation(*op_name),
-                def_vars,
+                &self.defined_variables[op_name],
                 &mut unused,
                 &mut visited,
             );
 
-            unused.iter().for_each(|var| {
+            unused.iter().for_each(|(var, position)| {
                 user_context.report_error(ValidationError {
                     error_code: self.error_code(),
+                    extensions: None,
                     message: error_message(var, op_name),
-                    locations: vec![],
+                    locations: vec![*position, *operation_position],
                 })
             })
         }
@@ -185,17 +197,8 @@ impl<'n> ValidationRule for NoUndefinedVariables<'n> {
         "NoUndefinedVariables"
     }
 
-    fn validate(
-        &self,
-        ctx: &mut OperationVisitorContext,
-        error_collector: &mut ValidationErrorContext,
-    ) {
-        visit_document(
-            &mut NoUndefinedVariables::new(),
-            ctx.operation,
-            ctx,
-            error_collector,
-        );
+    fn visitor<'a>(&self) -> Option<Box<dyn OperationVisitor<'a, ValidationErrorContext> + 'a>> {
+        Some(Box::new(NoUndefinedVariables::new()))
     }
 }
 
@@ -382,6 +385,16 @@ fn variable_not_defined() {
         messages,
         vec!["Variable \"$d\" is not defined by operation \"Foo\"."]
     );
+    assert_eq!(
+        errors[0].locations,
+        vec![
+            Pos {
+                line: 2,
+                column: 41
+            },
+            Pos { line: 1, column: 1 }
+        ]
+    );
 }
 
 #[test]
@@ -400,6 +413,16 @@ fn variable_not_defined_by_un_named_query() {
     let messages = get_messages(&errors);
     assert_eq!(messages.len(), 1);
     assert_eq!(messages, vec!["Variable \"$a\" is not defined."]);
+    assert_eq!(
+        errors[0].locations,
+        vec![
+            Pos {
+                line: 2,
+                column: 20
+            },
+            Pos { line: 1, column: 1 }
+        ]
+    );
 }
 
 #[test]
@@ -419,6 +442,26 @@ fn multiple_variables_not_defined() {
     assert_eq!(messages.len(), 2);
     assert!(messages.contains(&&"Variable \"$a\" is not defined by operation \"Foo\".".to_owned()));
     assert!(messages.contains(&&"Variable \"$c\" is not defined by operation \"Foo\".".to_owned()));
+    assert_eq!(
+        errors[0].locations,
+        vec![
+            Pos {
+                line: 2,
+                column: 20
+            },
+            Pos { line: 1, column: 1 }
+        ]
+    );
+    assert_eq!(
+        errors[1].locations,
+        vec![
+            Pos {
+                line: 2,
+                column: 34
+            },
+            Pos { line: 1, column: 1 }
+        ]
+    );
 }
 
 #[test]
@@ -440,6 +483,16 @@ fn variable_in_fragment_not_defined_by_un_named_query() {
     let messages = get_messages(&errors);
     assert_eq!(messages.len(), 1);
     assert_eq!(messages, vec!["Variable \"$a\" is not defined.",]);
+    assert_eq!(
+        errors[0].locations,
+        vec![
+            Pos {
+                line: 5,
+                column: 20
+            },
+            Pos { line: 1, column: 1 }
+        ]
+    );
 }
 
 #[test]
@@ -474,6 +527,16 @@ fn variable_in_fragment_not_defined_by_operation() {
         messages,
         vec!["Variable \"$c\" is not defined by operation \"Foo\"."]
     );
+    assert_eq!(
+        errors[0].locations,
+        vec![
+            Pos {
+                line: 15,
+                column: 20
+            },
+            Pos { line: 1, column: 1 }
+        ]
+    );
 }
 
 #[test]
@@ -506,6 +569,26 @@ fn multiple_variables_in_fragments_not_defined() {
     assert_eq!(messages.len(), 2);
     assert!(messages.contains(&&"Variable \"$c\" is not defined by operation \"Foo\".".to_owned()));
     assert!(messages.contains(&&"Variable \"$a\" is not defined by operation \"Foo\".".to_owned()));
+    assert_eq!(
+        errors[0].locations,
+        vec![
+            Pos {
+                line: 5,
+                column: 20
+            },
+            Pos { line: 1, column: 1 }
+        ]
+    );
+    assert_eq!(
+        errors[1].locations,
+        vec![
+            Pos {
+                line: 15,
+                column: 20
+            },
+            Pos { line: 1, column: 1 }
+        ]
+    );
 }
 
 #[test]
@@ -531,6 +614,26 @@ fn single_variable_in_fragment_not_defined_by_multiple_operations() {
     assert_eq!(messages.len(), 2);
     assert!(messages.contains(&&"Variable \"$b\" is not defined by operation \"Bar\".".to_owned()));
     assert!(messages.contains(&&"Variable \"$b\" is not defined by operation \"Foo\".".to_owned()));
+    assert_eq!(
+        errors[0].locations,
+        vec![
+            Pos {
+                line: 8,
+                column: 27
+            },
+            Pos { line: 1, column: 1 }
+        ]
+    );
+    assert_eq!(
+        errors[1].locations,
+        vec![
+            Pos {
+                line: 8,
+                column: 27
+            },
+            Pos { line: 4, column: 9 }
+        ]
+    );
 }
 
 #[test]
@@ -556,6 +659,26 @@ fn variables_in_fragment_not_defined_by_multiple_operations() {
     assert_eq!(messages.len(), 2);
     assert!(messages.contains(&&"Variable \"$a\" is not defined by operation \"Foo\".".to_owned()));
     assert!(messages.contains(&&"Variable \"$b\" is not defined by operation \"Bar\".".to_owned()));
+    assert_eq!(
+        errors[0].locations,
+        vec![
+            Pos {
+                line: 8,
+                column: 20
+            },
+            Pos { line: 1, column: 1 }
+        ]
+    );
+    assert_eq!(
+        errors[1].locations,
+        vec![
+            Pos {
+                line: 8,
+                column: 27
+            },
+            Pos { line: 4, column: 9 }
+        ]
+    );
 }
 
 #[test]
@@ -584,6 +707,26 @@ fn variable_in_fragment_used_by_other_operation() {
     assert_eq!(messages.len(), 2);
     assert!(messages.contains(&&"Variable \"$a\" is not defined by operation \"Foo\".".to_owned()));
     assert!(messages.contains(&&"Variable \"$b\" is not defined by operation \"Bar\".".to_owned()));
+    assert_eq!(
+        errors[0].locations,
+        vec![
+            Pos {
+                line: 8,
+                column: 20
+            },
+            Pos { line: 1, column: 1 }
+        ]
+    );
+    assert_eq!(
+        errors[1].locations,
+        vec![
+            Pos {
+                line: 11,
+                column: 20
+            },
+            Pos { line: 4, column: 9 }
+        ]
+    );
 }
 #[test]
 fn multiple_undefined_variables_produce_multiple_errors() {
@@ -615,4 +758,44 @@ fn multiple_undefined_variables_produce_multiple_errors() {
     assert!(messages.contains(&&"Variable \"$a\" is not defined by operation \"Foo\".".to_owned()));
     assert!(messages.contains(&&"Variable \"$b\" is not defined by operation \"Bar\".".to_owned()));
     assert!(messages.contains(&&"Variable \"$c\" is not defined by operation \"Bar\".".to_owned()));
+    assert_eq!(
+        errors[0].locations,
+        vec![
+            Pos {
+                line: 8,
+                column: 21
+            },
+            Pos { line: 1, column: 1 }
+        ]
+    );
+    assert_eq!(
+        errors[1].locations,
+        vec![
+            Pos {
+                line: 13,
+                column: 21
+            },
+            Pos { line: 1, column: 1 }
+        ]
+    );
+    assert_eq!(
+        errors[2].locations,
+        vec![
+            Pos {
+                line: 8,
+                column: 28
+            },
+            Pos { line: 4, column: 9 }
+        ]
+    );
+    assert_eq!(
+        errors[3].locations,
+        vec![
+            Pos {
+                line: 13,
+                column: 21
+            },
+            Pos { line: 4, column: 9 }
+        ]
+    );
 }
@@ -1,5 +1,5 @@
 use super::ValidationRule;
-use crate::ast::{visit_document, OperationVisitor, OperationVisitorContext};
+use crate::ast::{OperationVisitor, OperationVisitorContext};
 use crate::static_graphql::query::*;
 use crate::validation::utils::{ValidationError, ValidationErrorContext};
 
@@ -31,20 +31,20 @@ impl<'a> OperationVisitor<'a, ValidationErrorContext> for NoUnusedFragments<'a>
         _document: &Document,
     ) {
         visitor_context
-            .known_fragments
+            .operation
+            .definitions
             .iter()
-            .filter_map(|(fragment_name, _fragment)| {
-                if !self.fragments_in_use.contains(fragment_name) {
-                    Some(fragment_name)
-                } else {
-                    None
-                }
+            .filter_map(|definition| match definition {
+                Definition::Fragment(fragment) => Some(fragment),
+                _ => None,
             })
-            .for_each(|unused_fragment_name| {
+            .filter(|fragment| !self.fragments_in_use.contains(&fragment.name.as_str()))
+            .for_each(|unused_fragment| {
                 user_context.report_error(ValidationError {
                     error_code: self.error_code(),
-                    locations: vec![],
-                    message: format!("Fragment \"{}\" is never used.", unused_fragment_name),
+                    extensions: None,
+                    locations: vec![unused_fragment.position],
+                    message: format!("Fragment \"{}\" is never used.", unused_fragment.name),
                 });
             });
     }
@@ -69,17 +69,8 @@ impl<'n> ValidationRule for NoUnusedFragments<'n> {
         "NoUnusedFragments"
     }
 
-    fn validate(
-        &self,
-        ctx: &mut OperationVisitorContext,
-        error_collector: &mut ValidationErrorContext,
-    ) {
-        visit_document(
-            &mut NoUnusedFragments::new(),
-            ctx.operation,
-            ctx,
-            error_collector,
-        );
+    fn visitor<'a>(&self) -> Option<Box<dyn OperationVisitor<'a, ValidationErrorContext> + 'a>> {
+        Some(Box::new(NoUnusedFragments::new()))
     }
 }
 
@@ -187,6 +178,20 @@ fn contains_unknown_fragments() {
 
     let messages = get_messages(&errors);
     assert_eq!(messages.len(), 2);
+    assert_eq!(
+        errors[0].locations,
+        vec![Pos {
+            line: 21,
+            column: 9
+        }]
+    );
+    assert_eq!(
+        errors[1].locations,
+        vec![Pos {
+            line: 24,
+            column: 9
+        }]
+    );
 }
 
 // TODO: Fix this one :( It's not working
@@ -263,4 +268,5 @@ fn contains_unknown_and_undef_fragments() {
     let messages = get_messages(&errors);
     assert_eq!(messages.len(), 1);
     assert_eq!(messages, vec!["Fragment \"foo\" is never used.",]);
+    assert_eq!(errors[0].locations, vec![Pos { line: 6, column: 9 }]);
 }
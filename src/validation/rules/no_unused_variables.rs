@@ -2,8 +2,10 @@ use std::collections::{HashMap, HashSet};
 
 use super::ValidationRule;
 use crate::ast::{
-    visit_document, AstNodeWithName, OperationVisitor, OperationVisitorContext, ValueExtension,
+    AstNodeWithName, OperationDefinitionExtension, OperationVisitor, OperationVisitorContext,
+    ValueExtension,
 };
+use crate::parser::Pos;
 use crate::static_graphql::query::{self, OperationDefinition};
 use crate::validation::utils::{ValidationError, ValidationErrorContext};
 
@@ -15,7 +17,8 @@ use crate::validation::utils::{ValidationError, ValidationErrorContext};
 /// See https://spec.graphql.org/draft/#sec-All-Variables-Used
 pub struct NoUnusedVariables<'a> {
     current_scope: Option<NoUnusedVariablesScope<'a>>,
-    defined_variables: HashMap<Option<&'a str>, HashSet<&'a str>>,
+    defined_variables: HashMap<Option<&'a str>, Vec<(&'a str, Pos)>>,
+    operation_positions: HashMap<Option<&'a str>, Pos>,
     used_variables: HashMap<NoUnusedVariablesScope<'a>, Vec<&'a str>>,
     spreads: HashMap<NoUnusedVariablesScope<'a>, Vec<&'a str>>,
 }
@@ -31,6 +34,7 @@ impl<'a> NoUnusedVariables<'a> {
         Self {
             current_scope: None,
             defined_variables: HashMap::new(),
+            operation_positions: HashMap::new(),
             used_variables: HashMap::new(),
             spreads: HashMap::new(),
         }
@@ -41,7 +45,7 @@ impl<'a> NoUnusedVariables<'a> {
     fn find_used_vars(
         &self,
         from: &NoUnusedVariablesScope<'a>,
-        defined: &HashSet<&str>,
+        defined: &[(&str, Pos)],
         used: &mut HashSet<&'a str>,
         visited: &mut HashSet<NoUnusedVariablesScope<'a>>,
     ) {
@@ -53,7 +57,7 @@ impl<'a> NoUnusedVariables<'a> {
 
         if let Some(used_vars) = self.used_variables.get(from) {
             for var in used_vars {
-                if defined.contains(var) {
+                if defined.iter().any(|(name, _)| name == var) {
                     used.insert(var);
                 }
             }
@@ -87,7 +91,9 @@ impl<'a> OperationVisitor<'a, ValidationErrorContext> for NoUnusedVariables<'a>
     ) {
         let op_name = operation_definition.node_name();
         self.current_scope = Some(NoUnusedVariablesScope::Operation(op_name));
-        self.defined_variables.insert(op_name, HashSet::new());
+        self.defined_variables.insert(op_name, Vec::new());
+        self.operation_positions
+            .insert(op_name, operation_definition.position());
     }
 
     fn enter_fragment_definition(
@@ -121,7 +127,7 @@ impl<'a> OperationVisitor<'a, ValidationErrorContext> for NoUnusedVariables<'a>
     ) {
         if let Some(NoUnusedVariablesScope::Operation(ref name)) = self.current_scope {
             if let Some(vars) = self.defined_variables.get_mut(name) {
-                vars.insert(&variable_definition.name);
+                vars.push((&variable_definition.name, variable_definition.position));
             }
         }
     }
@@ -146,7 +152,12 @@ impl<'a> OperationVisitor<'a, ValidationErrorContext> for NoUnusedVariables<'a>
         user_context: &mut ValidationErrorContext,
         _: &query::Document,
     ) {
-        for (op_name, def_vars) in &self.defined_variables {
+        // Report in document order
+        let mut operations: Vec<_> = self.operation_positions.iter().collect();
+        operations.sort_by_key(|(_, position)| **position);
+
+        for (op_name, _) in operations {
+            let def_vars = &self.defined_variables[op_name];
             let mut used = HashSet::new();
             let mut visited = HashSet::new();
 
@@ -159,12 +170,13 @@ impl<'a> OperationVisitor<'a, ValidationErrorContext> for NoUnusedVariables<'a>
 
             def_vars
                 .iter()
-                .filter(|var| !used.contains(*var))
-                .for_each(|var| {
+                .filter(|(var, _)| !used.contains(var))
+                .for_each(|(var, position)| {
                     user_context.report_error(ValidationError {
                         error_code: self.error_code(),
+                        extensions: None,
                         message: error_message(var, op_name),
-                        locations: vec![],
+                        locations: vec![*position],
                     })
                 })
         }
@@ -187,17 +199,8 @@ impl<'n> ValidationRule for NoUnusedVariables<'n> {
         "NoUnusedVariables"
     }
 
-    fn validate(
-        &self,
-        ctx: &mut OperationVisitorContext,
-        error_collector: &mut ValidationErrorContext,
-    ) {
-        visit_document(
-            &mut NoUnusedVariables::new(),
-            ctx.operation,
-            ctx,
-            error_collector,
-        );
+    fn visitor<'a>(&self) -> Option<Box<dyn OperationVisitor<'a, ValidationErrorContext> + 'a>> {
+        Some(Box::new(NoUnusedVariables::new()))
     }
 }
 
@@ -355,6 +358,13 @@ fn variables_not_used() {
 
     assert_eq!(messages.len(), 1);
     assert!(messages.contains(&&"Variable \"$c\" is never used.".to_owned()));
+    assert_eq!(
+        errors[0].locations,
+        vec![Pos {
+            line: 1,
+            column: 32
+        }]
+    );
 }
 
 #[test]
@@ -375,6 +385,20 @@ fn multiple_variables_not_used() {
     assert_eq!(messages.len(), 2);
     assert!(messages.contains(&&"Variable \"$a\" is never used in operation \"Foo\".".to_owned()));
     assert!(messages.contains(&&"Variable \"$c\" is never used in operation \"Foo\".".to_owned()));
+    assert_eq!(
+        errors[0].locations,
+        vec![Pos {
+            line: 1,
+            column: 11
+        }]
+    );
+    assert_eq!(
+        errors[1].locations,
+        vec![Pos {
+            line: 1,
+            column: 35
+        }]
+    );
 }
 
 #[test]
@@ -407,6 +431,13 @@ fn variables_not_used_in_fragments() {
 
     assert_eq!(messages.len(), 1);
     assert!(messages.contains(&&"Variable \"$c\" is never used in operation \"Foo\".".to_owned()));
+    assert_eq!(
+        errors[0].locations,
+        vec![Pos {
+            line: 1,
+            column: 35
+        }]
+    );
 }
 
 #[test]
@@ -440,6 +471,20 @@ fn multiple_variables_not_used_in_fragments() {
     assert_eq!(messages.len(), 2);
     assert!(messages.contains(&&"Variable \"$a\" is never used in operation \"Foo\".".to_owned()));
     assert!(messages.contains(&&"Variable \"$c\" is never used in operation \"Foo\".".to_owned()));
+    assert_eq!(
+        errors[0].locations,
+        vec![Pos {
+            line: 1,
+            column: 11
+        }]
+    );
+    assert_eq!(
+        errors[1].locations,
+        vec![Pos {
+            line: 1,
+            column: 35
+        }]
+    );
 }
 
 #[test]
@@ -465,6 +510,13 @@ fn variables_not_used_by_unreferences_fragment() {
 
     assert_eq!(messages.len(), 1);
     assert!(messages.contains(&&"Variable \"$b\" is never used in operation \"Foo\".".to_owned()));
+    assert_eq!(
+        errors[0].locations,
+        vec![Pos {
+            line: 1,
+            column: 11
+        }]
+    );
 }
 
 #[test]
@@ -494,6 +546,20 @@ fn variables_not_used_by_fragment_used_by_other_operation() {
     assert_eq!(messages.len(), 2);
     assert!(messages.contains(&&"Variable \"$b\" is never used in operation \"Foo\".".to_owned()));
     assert!(messages.contains(&&"Variable \"$a\" is never used in operation \"Bar\".".to_owned()));
+    assert_eq!(
+        errors[0].locations,
+        vec![Pos {
+            line: 1,
+            column: 11
+        }]
+    );
+    assert_eq!(
+        errors[1].locations,
+        vec![Pos {
+            line: 4,
+            column: 19
+        }]
+    );
 }
 
 #[test]
@@ -4,13 +4,11 @@ use crate::parser::Pos;
 use super::ValidationRule;
 use crate::ast::ext::TypeDefinitionExtension;
 use crate::ast::{
-    visit_document, FieldByNameExtension, OperationVisitor, OperationVisitorContext,
-    SchemaDocumentExtension, TypeExtension, ValueExtension,
+    FieldByNameExtension, OperationVisitor, OperationVisitorContext, Schema, TypeExtension,
+    ValueExtension,
 };
 use crate::static_graphql::query::*;
-use crate::static_graphql::schema::{
-    Document as SchemaDocument, Field as FieldDefinition, TypeDefinition,
-};
+use crate::static_graphql::schema::{Field as FieldDefinition, TypeDefinition};
 use crate::validation::utils::{ValidationError, ValidationErrorContext};
 use std::borrow::Borrow;
 use std::collections::HashMap;
@@ -124,33 +122,33 @@ impl<K: Eq + Hash + Clone, V> OrderedMap<K, V> {
         }
     }
 
-    fn iter(&self) -> OrderedMapIter<K, V> {
+    fn iter(&self) -> OrderedMapIter<'_, K, V> {
         OrderedMapIter {
             map: &self.data,
             inner: self.insert_order.iter(),
         }
     }
 
-    fn get<Q: ?Sized>(&self, k: &Q) -> Option<&V>
+    fn get<Q>(&self, k: &Q) -> Option<&V>
     where
         K: Borrow<Q>,
-        Q: Hash + Eq,
+        Q: ?Sized + Hash + Eq,
     {
         self.data.get(k)
     }
 
-    fn get_mut<Q: ?Sized>(&mut self, k: &Q) -> Option<&mut V>
+    fn get_mut<Q>(&mut self, k: &Q) -> Option<&mut V>
     where
         K: Borrow<Q>,
-        Q: Hash + Eq,
+        Q: ?Sized + Hash + Eq,
     {
         self.data.get_mut(k)
     }
 
-    fn contains_key<Q: ?Sized>(&self, k: &Q) -> bool
+    fn contains_key<Q>(&self, k: &Q) -> bool
     where
         K: Borrow<Q>,
-        Q: Hash + Eq,
+        Q: ?Sized + Hash + Eq,
     {
         self.data.contains_key(k)
     }
@@ -219,7 +217,7 @@ impl<'a> OverlappingFieldsCanBeMerged<'a> {
     // GraphQL Document.
     fn find_conflicts_within_selection_set(
         &mut self,
-        schema: &'a SchemaDocument,
+        schema: &Schema<'a>,
         parent_type: Option<&'a TypeDefinition>,
         selection_set: &'a SelectionSet,
         visited_fragments: &mut Vec<&'a str>,
@@ -267,7 +265,7 @@ impl<'a> OverlappingFieldsCanBeMerged<'a> {
     // Collect all Conflicts "within" one collection of fields.
     fn collect_conflicts_within(
         &mut self,
-        schema: &'a SchemaDocument,
+        schema: &Schema<'a>,
         conflicts: &mut Vec<Conflict>,
         field_map: &OrderedMap<&'a str, Vec<AstAndDef<'a>>>,
         visited_fragments: &mut Vec<&'a str>,
@@ -314,7 +312,7 @@ impl<'a> OverlappingFieldsCanBeMerged<'a> {
     // Two types conflict if both types could not apply to a value simultaneously.
     // Composite types are ignored as their individual field types will be compared
     // later recursively. However List and Non-Null types must match.
-    fn is_type_conflict(schema: &SchemaDocument, t1: &Type, t2: &Type) -> bool {
+    fn is_type_conflict(schema: &Schema, t1: &Type, t2: &Type) -> bool {
         if let Type::ListType(t1) = t1 {
             if let Type::ListType(t2) = t2 {
                 return Self::is_type_conflict(schema, t1, t2);
@@ -355,7 +353,7 @@ impl<'a> OverlappingFieldsCanBeMerged<'a> {
     // comparing their sub-fields.
     fn find_conflict(
         &mut self,
-        schema: &'a SchemaDocument,
+        schema: &Schema<'a>,
         out_field_name: &str,
         first: &AstAndDef<'a>,
         second: &AstAndDef<'a>,
@@ -485,7 +483,7 @@ impl<'a> OverlappingFieldsCanBeMerged<'a> {
     #[allow(clippy::too_many_arguments)]
     fn find_conflicts_between_sub_selection_sets(
         &mut self,
-        schema: &'a SchemaDocument,
+        schema: &Schema<'a>,
         mutually_exclusive: bool,
         parent_type_name1: Option<&str>,
         selection_set1: &'a SelectionSet,
@@ -559,7 +557,7 @@ impl<'a> OverlappingFieldsCanBeMerged<'a> {
 
     fn collect_conflicts_between_fields_and_fragment(
         &mut self,
-        schema: &'a SchemaDocument,
+        schema: &Schema<'a>,
         conflicts: &mut Vec<Conflict>,
         field_map: &OrderedMap<&'a str, Vec<AstAndDef<'a>>>,
         fragment_name: &str,
@@ -609,7 +607,7 @@ impl<'a> OverlappingFieldsCanBeMerged<'a> {
     // any nested fragments.
     fn collect_conflicts_between_fragments(
         &mut self,
-        schema: &'a SchemaDocument,
+        schema: &Schema<'a>,
         conflicts: &mut Vec<Conflict>,
         fragment_name1: &'a str,
         fragment_name2: &'a str,
@@ -689,7 +687,7 @@ impl<'a> OverlappingFieldsCanBeMerged<'a> {
     // as well as a list of nested fragment names referenced via fragment spreads.
     fn get_referenced_fields_and_fragment_names(
         &self,
-        schema: &'a SchemaDocument,
+        schema: &Schema<'a>,
         fragment: &'a FragmentDefinition,
     ) -> (OrderedMap<&'a str, Vec<AstAndDef<'a>>>, Vec<&'a str>) {
         let TypeCondition::On(type_condition) = &fragment.type_condition;
@@ -705,7 +703,7 @@ impl<'a> OverlappingFieldsCanBeMerged<'a> {
     // each individual selection set.
     fn collect_conflicts_between(
         &mut self,
-        schema: &'a SchemaDocument,
+        schema: &Schema<'a>,
         conflicts: &mut Vec<Conflict>,
         mutually_exclusive: bool,
         field_map1: &OrderedMap<&'a str, Vec<AstAndDef<'a>>>,
@@ -742,7 +740,7 @@ impl<'a> OverlappingFieldsCanBeMerged<'a> {
     // referenced via fragment spreads.
     fn get_fields_and_fragment_names(
         &self,
-        schema: &'a SchemaDocument,
+        schema: &Schema<'a>,
         parent_type: Option<&'a TypeDefinition>,
         selection_set: &'a SelectionSet,
     ) -> (OrderedMap<&'a str, Vec<AstAndDef<'a>>>, Vec<&'a str>) {
@@ -761,7 +759,7 @@ impl<'a> OverlappingFieldsCanBeMerged<'a> {
     }
 
     fn collect_fields_and_fragment_names(
-        schema: &'a SchemaDocument,
+        schema: &Schema<'a>,
         parent_type: Option<&'a TypeDefinition>,
         selection_set: &'a SelectionSet,
         ast_and_defs: &mut OrderedMap<&'a str, Vec<AstAndDef<'a>>>,
@@ -836,7 +834,7 @@ impl<'a> OperationVisitor<'a, ValidationErrorContext> for OverlappingFieldsCanBe
         selection_set: &'a SelectionSet,
     ) {
         let parent_type = visitor_context.current_parent_type();
-        let schema = visitor_context.schema;
+        let schema = visitor_context.indexed_schema();
         let mut visited_fragments = Vec::new();
         let found_conflicts = self.find_conflicts_within_selection_set(
             schema,
@@ -850,6 +848,7 @@ impl<'a> OperationVisitor<'a, ValidationErrorContext> for OverlappingFieldsCanBe
 
             user_context.report_error(ValidationError {
                 error_code: self.error_code(),
+                extensions: None,
                 message: error_message(&reason_name, &reason_msg),
                 locations: p1,
             });
@@ -890,17 +889,8 @@ impl<'o> ValidationRule for OverlappingFieldsCanBeMerged<'o> {
         "OverlappingFieldsCanBeMerged"
     }
 
-    fn validate(
-        &self,
-        ctx: &mut OperationVisitorContext,
-        error_collector: &mut ValidationErrorContext,
-    ) {
-        visit_document(
-            &mut OverlappingFieldsCanBeMerged::new(),
-            ctx.operation,
-            ctx,
-            error_collector,
-        );
+    fn visitor<'a>(&self) -> Option<Box<dyn OperationVisitor<'a, ValidationErrorContext> + 'a>> {
+        Some(Box::new(OverlappingFieldsCanBeMerged::new()))
     }
 }
 
@@ -1,8 +1,7 @@
 use super::ValidationRule;
 use crate::ast::ext::TypeDefinitionExtension;
 use crate::ast::{
-    visit_document, ImplementingInterfaceExtension, OperationVisitor, OperationVisitorContext,
-    PossibleTypesExtension, SchemaDocumentExtension,
+    ImplementingInterfaceExtension, OperationVisitor, OperationVisitorContext, Schema,
 };
 use crate::static_graphql::query::TypeCondition;
 use crate::static_graphql::schema;
@@ -39,7 +38,7 @@ impl PossibleFragmentSpreads {
  * This function is commutative.
  */
 pub fn do_types_overlap(
-    schema: &schema::Document,
+    schema: &Schema,
     t1: &schema::TypeDefinition,
     t2: &schema::TypeDefinition,
 ) -> bool {
@@ -49,10 +48,9 @@ pub fn do_types_overlap(
 
     if t1.is_abstract_type() {
         if t2.is_abstract_type() {
-            let possible_types = t1.possible_types(schema);
-
-            return possible_types
-                .into_iter()
+            return schema
+                .possible_types(t1)
+                .iter()
                 .filter(|possible_type| t2.has_concrete_sub_type(possible_type))
                 .count()
                 > 0;
@@ -73,16 +71,21 @@ impl<'a> OperationVisitor<'a, ValidationErrorContext> for PossibleFragmentSpread
         &mut self,
         visitor_context: &mut OperationVisitorContext,
         user_context: &mut ValidationErrorContext,
-        _inline_fragment: &crate::static_graphql::query::InlineFragment,
+        inline_fragment: &crate::static_graphql::query::InlineFragment,
     ) {
         if let Some(frag_schema_type) = visitor_context.current_type() {
             if let Some(parent_type) = visitor_context.current_parent_type() {
                 if frag_schema_type.is_composite_type()
                     && parent_type.is_composite_type()
-                    && !do_types_overlap(visitor_context.schema, frag_schema_type, parent_type)
+                    && !do_types_overlap(
+                        visitor_context.indexed_schema(),
+                        frag_schema_type,
+                        parent_type,
+                    )
                 {
                     user_context.report_error(ValidationError {error_code: self.error_code(),
-                      locations: vec![],
+                      extensions: None,
+                      locations: vec![inline_fragment.position],
                       message: format!("Fragment cannot be spread here as objects of type \"{}\" can never be of type \"{}\".", parent_type.name(), frag_schema_type.name()),
                     })
                 }
@@ -102,14 +105,22 @@ impl<'a> OperationVisitor<'a, ValidationErrorContext> for PossibleFragmentSpread
         {
             let TypeCondition::On(fragment_type_name) = &actual_fragment.type_condition;
 
-            if let Some(fragment_type) = visitor_context.schema.type_by_name(fragment_type_name) {
+            if let Some(fragment_type) = visitor_context
+                .indexed_schema()
+                .type_by_name(fragment_type_name)
+            {
                 if let Some(parent_type) = visitor_context.current_parent_type() {
                     if fragment_type.is_composite_type()
                         && parent_type.is_composite_type()
-                        && !do_types_overlap(visitor_context.schema, fragment_type, parent_type)
+                        && !do_types_overlap(
+                            visitor_context.indexed_schema(),
+                            fragment_type,
+                            parent_type,
+                        )
                     {
                         user_context.report_error(ValidationError {error_code: self.error_code(),
-                        locations: vec![],
+                        extensions: None,
+                        locations: vec![fragment_spread.position],
                         message: format!("Fragment \"{}\" cannot be spread here as objects of type \"{}\" can never be of type \"{}\".", actual_fragment.name, parent_type.name(), fragment_type_name),
                       })
                     }
@@ -124,17 +135,8 @@ impl ValidationRule for PossibleFragmentSpreads {
         "PossibleFragmentSpreads"
     }
 
-    fn validate(
-        &self,
-        ctx: &mut OperationVisitorContext,
-        error_collector: &mut ValidationErrorContext,
-    ) {
-        visit_document(
-            &mut PossibleFragmentSpreads::new(),
-            ctx.operation,
-            ctx,
-            error_collector,
-        );
+    fn visitor<'a>(&self) -> Option<Box<dyn OperationVisitor<'a, ValidationErrorContext> + 'a>> {
+        Some(Box::new(PossibleFragmentSpreads::new()))
     }
 }
 
@@ -399,7 +401,14 @@ fn different_object_into_object() {
     assert_eq!(messages.len(), 1);
     assert_eq!(messages, vec![
       "Fragment \"dogFragment\" cannot be spread here as objects of type \"Cat\" can never be of type \"Dog\"."
-    ])
+    ]);
+    assert_eq!(
+        errors[0].locations,
+        vec![Pos {
+            line: 1,
+            column: 48
+        }]
+    );
 }
 
 #[test]
@@ -422,7 +431,14 @@ fn different_object_into_object_in_inline_fragment() {
         vec![
       "Fragment cannot be spread here as objects of type \"Cat\" can never be of type \"Dog\"."
     ]
-    )
+    );
+    assert_eq!(
+        errors[0].locations,
+        vec![Pos {
+            line: 2,
+            column: 15
+        }]
+    );
 }
 
 #[test]
@@ -444,7 +460,14 @@ fn object_into_not_implementing_interface() {
         vec![
           "Fragment \"humanFragment\" cannot be spread here as objects of type \"Pet\" can never be of type \"Human\"."
         ]
-    )
+    );
+    assert_eq!(
+        errors[0].locations,
+        vec![Pos {
+            line: 1,
+            column: 51
+        }]
+    );
 }
 
 #[test]
@@ -466,7 +489,14 @@ fn object_into_not_containing_union() {
         vec![
           "Fragment \"humanFragment\" cannot be spread here as objects of type \"CatOrDog\" can never be of type \"Human\"."
         ]
-    )
+    );
+    assert_eq!(
+        errors[0].locations,
+        vec![Pos {
+            line: 1,
+            column: 52
+        }]
+    );
 }
 
 #[test]
@@ -488,7 +518,14 @@ fn union_into_not_contained_object() {
         vec![
           "Fragment \"catOrDogFragment\" cannot be spread here as objects of type \"Human\" can never be of type \"CatOrDog\"."
         ]
-    )
+    );
+    assert_eq!(
+        errors[0].locations,
+        vec![Pos {
+            line: 1,
+            column: 49
+        }]
+    );
 }
 
 #[test]
@@ -510,7 +547,14 @@ fn union_into_non_overlapping_interface() {
         vec![
           "Fragment \"humanOrAlienFragment\" cannot be spread here as objects of type \"Pet\" can never be of type \"HumanOrAlien\"."
         ]
-    )
+    );
+    assert_eq!(
+        errors[0].locations,
+        vec![Pos {
+            line: 1,
+            column: 50
+        }]
+    );
 }
 
 #[test]
@@ -532,7 +576,14 @@ fn union_into_non_overlapping_union() {
         vec![
           "Fragment \"humanOrAlienFragment\" cannot be spread here as objects of type \"CatOrDog\" can never be of type \"HumanOrAlien\"."
         ]
-    )
+    );
+    assert_eq!(
+        errors[0].locations,
+        vec![Pos {
+            line: 1,
+            column: 51
+        }]
+    );
 }
 
 #[test]
@@ -554,7 +605,14 @@ fn interface_into_non_implementing_object() {
         vec![
           "Fragment \"intelligentFragment\" cannot be spread here as objects of type \"Cat\" can never be of type \"Intelligent\"."
         ]
-    )
+    );
+    assert_eq!(
+        errors[0].locations,
+        vec![Pos {
+            line: 1,
+            column: 51
+        }]
+    );
 }
 
 #[test]
@@ -578,7 +636,14 @@ fn interface_into_non_overlapping_interface() {
         vec![
           "Fragment \"intelligentFragment\" cannot be spread here as objects of type \"Pet\" can never be of type \"Intelligent\"."
         ]
-    )
+    );
+    assert_eq!(
+        errors[0].locations,
+        vec![Pos {
+            line: 2,
+            column: 14
+        }]
+    );
 }
 
 #[test]
@@ -601,5 +666,12 @@ fn interface_into_non_overlapping_interface_in_inline_fragment() {
         vec![
           "Fragment cannot be spread here as objects of type \"Pet\" can never be of type \"Intelligent\"."
         ]
-    )
+    );
+    assert_eq!(
+        errors[0].locations,
+        vec![Pos {
+            line: 2,
+            column: 14
+        }]
+    );
 }
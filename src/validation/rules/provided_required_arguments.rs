@@ -1,7 +1,6 @@
 use super::ValidationRule;
 use crate::ast::{
-    visit_document, FieldByNameExtension, InputValueHelpers, OperationVisitor,
-    OperationVisitorContext,
+    FieldByNameExtension, InputValueHelpers, OperationVisitor, OperationVisitorContext,
 };
 use crate::static_graphql::query::Value;
 use crate::static_graphql::schema::InputValue;
@@ -41,6 +40,7 @@ impl<'a> OperationVisitor<'a, ValidationErrorContext> for ProvidedRequiredArgume
 
                 for missing in missing_required_args {
                     user_context.report_error(ValidationError {error_code: self.error_code(),
+              extensions: None,
               locations: vec![field.position],
               message: format!("Field \"{}\" argument \"{}\" of type \"{}\" is required, but it was not provided.",
               field.name, missing.name, missing.value_type),
@@ -56,14 +56,16 @@ impl<'a> OperationVisitor<'a, ValidationErrorContext> for ProvidedRequiredArgume
         user_context: &mut ValidationErrorContext,
         directive: &crate::static_graphql::query::Directive,
     ) {
-        let known_directives = &visitor_context.directives;
-
-        if let Some(directive_def) = known_directives.get(&directive.name) {
+        if let Some(directive_def) = visitor_context
+            .indexed_schema()
+            .directive_by_name(&directive.name)
+        {
             let missing_required_args =
                 validate_arguments(&directive.arguments, &directive_def.arguments);
 
             for missing in missing_required_args {
                 user_context.report_error(ValidationError {error_code: self.error_code(),
+              extensions: None,
               locations: vec![directive.position],
               message: format!("Directive \"@{}\" argument \"{}\" of type \"{}\" is required, but it was not provided.",
               directive.name, missing.name, missing.value_type),
@@ -98,17 +100,8 @@ impl ValidationRule for ProvidedRequiredArguments {
         "ProvidedRequiredArguments"
     }
 
-    fn validate(
-        &self,
-        ctx: &mut OperationVisitorContext,
-        error_collector: &mut ValidationErrorContext,
-    ) {
-        visit_document(
-            &mut ProvidedRequiredArguments::new(),
-            ctx.operation,
-            ctx,
-            error_collector,
-        );
+    fn visitor<'a>(&self) -> Option<Box<dyn OperationVisitor<'a, ValidationErrorContext> + 'a>> {
+        Some(Box::new(ProvidedRequiredArguments::new()))
     }
 }
 
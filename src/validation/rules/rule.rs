@@ -1,11 +1,27 @@
-use crate::{ast::OperationVisitorContext, validation::utils::ValidationErrorContext};
+use crate::{
+    ast::{visit_document, OperationVisitor, OperationVisitorContext},
+    validation::utils::ValidationErrorContext,
+};
 
 pub trait ValidationRule: Send + Sync {
+    /// Creates the visitor checking this rule. `validate` runs the visitors of all rules of a
+    /// plan in a single traversal of the operation.
+    ///
+    /// Rules that can't be expressed as a visitor (for example, because they need their own
+    /// passes over the whole document) return `None` and implement `validate` instead.
+    fn visitor<'a>(&self) -> Option<Box<dyn OperationVisitor<'a, ValidationErrorContext> + 'a>> {
+        None
+    }
+
     fn validate(
         &self,
-        _ctx: &mut OperationVisitorContext<'_>,
-        _error_collector: &mut ValidationErrorContext,
-    );
+        ctx: &mut OperationVisitorContext<'_>,
+        error_collector: &mut ValidationErrorContext,
+    ) {
+        if let Some(mut visitor) = self.visitor() {
+            visit_document(visitor.as_mut(), ctx.operation, ctx, error_collector);
+        }
+    }
 
     fn error_code<'a>(&self) -> &'a str;
 }
@@ -1,7 +1,6 @@
 use super::ValidationRule;
 use crate::ast::{
-    collect_fields, visit_document, OperationVisitor, OperationVisitorContext,
-    SchemaDocumentExtension,
+    collect_fields, OperationVisitor, OperationVisitorContext, SchemaDocumentExtension,
 };
 use crate::static_graphql::query::OperationDefinition;
 use crate::static_graphql::schema::TypeDefinition;
@@ -34,7 +33,7 @@ impl<'a> OperationVisitor<'a, ValidationErrorContext> for SingleFieldSubscriptio
         operation: &OperationDefinition,
     ) {
         if let OperationDefinition::Subscription(subscription) = operation {
-            if let Some(subscription_type) = visitor_context.schema.subscription_type() {
+            if let Some(subscription_type) = visitor_context.indexed_schema().subscription_type() {
                 let operation_name = subscription.name.as_ref();
 
                 let selection_set_fields = collect_fields(
@@ -56,6 +55,7 @@ impl<'a> OperationVisitor<'a, ValidationErrorContext> for SingleFieldSubscriptio
 
                     user_context.report_error(ValidationError {
                         error_code: self.error_code(),
+                        extensions: None,
                         locations: vec![subscription.position],
                         message: error_message,
                     });
@@ -81,6 +81,7 @@ impl<'a> OperationVisitor<'a, ValidationErrorContext> for SingleFieldSubscriptio
                   };
 
                   user_context.report_error(ValidationError {error_code: self.error_code(),
+                    extensions: None,
                     locations: vec![subscription.position],
                     message: error_message,
                 });
@@ -95,17 +96,8 @@ impl ValidationRule for SingleFieldSubscriptions {
         "SingleFieldSubscriptions"
     }
 
-    fn validate(
-        &self,
-        ctx: &mut OperationVisitorContext,
-        error_collector: &mut ValidationErrorContext,
-    ) {
-        visit_document(
-            &mut SingleFieldSubscriptions::new(),
-            ctx.operation,
-            ctx,
-            error_collector,
-        );
+    fn visitor<'a>(&self) -> Option<Box<dyn OperationVisitor<'a, ValidationErrorContext> + 'a>> {
+        Some(Box::new(SingleFieldSubscriptions::new()))
     }
 }
 
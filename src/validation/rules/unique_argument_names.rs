@@ -3,7 +3,7 @@ use std::collections::HashMap;
 use crate::parser::Pos;
 
 use super::ValidationRule;
-use crate::ast::{visit_document, OperationVisitor, OperationVisitorContext};
+use crate::ast::{OperationVisitor, OperationVisitorContext};
 use crate::static_graphql::query::Value;
 use crate::validation::utils::{ValidationError, ValidationErrorContext};
 
@@ -40,6 +40,7 @@ impl<'a> OperationVisitor<'a, ValidationErrorContext> for UniqueArgumentNames {
             if positions.len() > 1 {
                 user_context.report_error(ValidationError {
                     error_code: self.error_code(),
+                    extensions: None,
                     message: format!("There can be only one argument named \"{}\".", arg_name),
                     locations: positions.clone(),
                 })
@@ -59,6 +60,7 @@ impl<'a> OperationVisitor<'a, ValidationErrorContext> for UniqueArgumentNames {
             if positions.len() > 1 {
                 user_context.report_error(ValidationError {
                     error_code: self.error_code(),
+                    extensions: None,
                     message: format!("There can be only one argument named \"{}\".", arg_name),
                     locations: positions.clone(),
                 })
@@ -88,17 +90,8 @@ impl ValidationRule for UniqueArgumentNames {
         "UniqueArgumentNames"
     }
 
-    fn validate(
-        &self,
-        ctx: &mut OperationVisitorContext,
-        error_collector: &mut ValidationErrorContext,
-    ) {
-        visit_document(
-            &mut UniqueArgumentNames::new(),
-            ctx.operation,
-            ctx,
-            error_collector,
-        );
+    fn visitor<'a>(&self) -> Option<Box<dyn OperationVisitor<'a, ValidationErrorContext> + 'a>> {
+        Some(Box::new(UniqueArgumentNames::new()))
     }
 }
 
@@ -6,7 +6,7 @@ use crate::static_graphql::query::{
     Directive, Field, FragmentDefinition, FragmentSpread, InlineFragment, OperationDefinition,
 };
 use crate::{
-    ast::{visit_document, OperationVisitor, OperationVisitorContext},
+    ast::{OperationVisitor, OperationVisitorContext},
     validation::utils::{ValidationError, ValidationErrorContext},
 };
 
@@ -38,11 +38,12 @@ impl UniqueDirectivesPerLocation {
         let mut exists = HashSet::new();
 
         for directive in directives {
-            if let Some(meta_directive) = ctx.directives.get(&directive.name) {
+            if let Some(meta_directive) = ctx.indexed_schema().directive_by_name(&directive.name) {
                 if !meta_directive.repeatable {
                     if exists.contains(&directive.name) {
                         err_context.report_error(ValidationError {
                             error_code: self.error_code(),
+                            extensions: None,
                             locations: vec![directive.position],
                             message: format!("Duplicate directive \"{}\"", &directive.name),
                         });
@@ -109,17 +110,8 @@ impl ValidationRule for UniqueDirectivesPerLocation {
         "UniqueDirectivesPerLocation"
     }
 
-    fn validate(
-        &self,
-        ctx: &mut OperationVisitorContext,
-        error_collector: &mut ValidationErrorContext,
-    ) {
-        visit_document(
-            &mut UniqueDirectivesPerLocation::new(),
-            ctx.operation,
-            ctx,
-            error_collector,
-        );
+    fn visitor<'a>(&self) -> Option<Box<dyn OperationVisitor<'a, ValidationErrorContext> + 'a>> {
+        Some(Box::new(UniqueDirectivesPerLocation::new()))
     }
 }
 
@@ -1,7 +1,8 @@
 use std::collections::HashMap;
 
 use super::ValidationRule;
-use crate::ast::{visit_document, AstNodeWithName, OperationVisitor, OperationVisitorContext};
+use crate::ast::{AstNodeWithName, OperationVisitor, OperationVisitorContext};
+use crate::parser::Pos;
 use crate::static_graphql::query::*;
 use crate::validation::utils::{ValidationError, ValidationErrorContext};
 
@@ -11,18 +12,29 @@ use crate::validation::utils::{ValidationError, ValidationErrorContext};
 ///
 /// See https://spec.graphql.org/draft/#sec-Fragment-Name-Uniqueness
 pub struct UniqueFragmentNames<'a> {
-    findings_counter: HashMap<&'a str, i32>,
+    known_names: HashMap<&'a str, Pos>,
 }
 
 impl<'a> OperationVisitor<'a, ValidationErrorContext> for UniqueFragmentNames<'a> {
     fn enter_fragment_definition(
         &mut self,
         _: &mut OperationVisitorContext,
-        _: &mut ValidationErrorContext,
+        user_context: &mut ValidationErrorContext,
         fragment: &'a FragmentDefinition,
     ) {
         if let Some(name) = fragment.node_name() {
-            self.store_finding(name);
+            let position = fragment.position;
+
+            if let Some(known_position) = self.known_names.get(name) {
+                user_context.report_error(ValidationError {
+                    error_code: self.error_code(),
+                    extensions: None,
+                    message: format!("There can be only one fragment named \"{}\".", name),
+                    locations: vec![*known_position, position],
+                });
+            } else {
+                self.known_names.insert(name, position);
+            }
         }
     }
 }
@@ -36,14 +48,9 @@ impl<'a> Default for UniqueFragmentNames<'a> {
 impl<'a> UniqueFragmentNames<'a> {
     pub fn new() -> Self {
         Self {
-            findings_counter: HashMap::new(),
+            known_names: HashMap::new(),
         }
     }
-
-    fn store_finding(&mut self, name: &'a str) {
-        let value = *self.findings_counter.entry(name).or_insert(0);
-        self.findings_counter.insert(name, value + 1);
-    }
 }
 
 impl<'u> ValidationRule for UniqueFragmentNames<'u> {
@@ -51,25 +58,8 @@ impl<'u> ValidationRule for UniqueFragmentNames<'u> {
         "UniqueFragmentNames"
     }
 
-    fn validate(
-        &self,
-        ctx: &mut OperationVisitorContext,
-        error_collector: &mut ValidationErrorContext,
-    ) {
-        let mut rule = UniqueFragmentNames::new();
-
-        visit_document(&mut rule, ctx.operation, ctx, error_collector);
-
-        rule.findings_counter
-            .into_iter()
-            .filter(|(_key, value)| *value > 1)
-            .for_each(|(key, _value)| {
-                error_collector.report_error(ValidationError {
-                    error_code: self.error_code(),
-                    message: format!("There can be only one fragment named \"{}\".", key),
-                    locations: vec![],
-                })
-            })
+    fn visitor<'a>(&self) -> Option<Box<dyn OperationVisitor<'a, ValidationErrorContext> + 'a>> {
+        Some(Box::new(UniqueFragmentNames::new()))
     }
 }
 
@@ -200,6 +190,10 @@ fn fragments_named_the_same() {
         messages,
         vec!["There can be only one fragment named \"fragA\"."]
     );
+    assert_eq!(
+        errors[0].locations,
+        vec![Pos { line: 4, column: 9 }, Pos { line: 7, column: 9 }]
+    );
 }
 
 #[test]
@@ -224,4 +218,8 @@ fn fragments_named_the_same_without_being_referenced() {
         messages,
         vec!["There can be only one fragment named \"fragA\"."]
     );
+    assert_eq!(
+        errors[0].locations,
+        vec![Pos { line: 1, column: 1 }, Pos { line: 4, column: 9 }]
+    );
 }
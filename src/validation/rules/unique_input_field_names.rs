@@ -0,0 +1,345 @@
+use std::collections::hash_map::Entry;
+use std::collections::{BTreeMap, HashMap};
+
+use super::ValidationRule;
+use crate::ast::{OperationVisitor, OperationVisitorContext};
+use crate::static_graphql::query::Value;
+use crate::validation::utils::{ValidationError, ValidationErrorContext};
+
+/// Unique input field names
+///
+/// A GraphQL input object value is only valid if all supplied fields are
+/// uniquely named.
+///
+/// The parser stores object values in a `BTreeMap`, so duplicated keys are
+/// already collapsed in the AST. This rule reads the fields of object values
+/// from the source instead: validated without it (with `validate`), an
+/// operation with input object values gets an error asking for the source.
+///
+/// See https://spec.graphql.org/draft/#sec-Input-Object-Field-Uniqueness
+pub struct UniqueInputFieldNames {
+    reported_missing_source: bool,
+}
+
+impl Default for UniqueInputFieldNames {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl UniqueInputFieldNames {
+    pub fn new() -> Self {
+        UniqueInputFieldNames {
+            reported_missing_source: false,
+        }
+    }
+}
+
+impl<'a> OperationVisitor<'a, ValidationErrorContext> for UniqueInputFieldNames {
+    fn enter_object_value(
+        &mut self,
+        visitor_context: &mut OperationVisitorContext<'a>,
+        user_context: &mut ValidationErrorContext,
+        _: &BTreeMap<String, Value>,
+    ) {
+        if !visitor_context.has_source() {
+            if !self.reported_missing_source {
+                self.reported_missing_source = true;
+                user_context.report_error(ValidationError {
+                    error_code: self.error_code(),
+                    extensions: None,
+                    locations: vec![visitor_context.current_position()],
+                    message: "Input object fields can't be checked for duplicates without the source of the operation, validate it with `validate_with_source`.".to_string(),
+                });
+            }
+
+            return;
+        }
+
+        let mut known_names = HashMap::new();
+
+        for (field_name, position) in visitor_context.current_object_fields() {
+            match known_names.entry(*field_name) {
+                Entry::Occupied(entry) => user_context.report_error(ValidationError {
+                    error_code: self.error_code(),
+                    extensions: None,
+                    locations: vec![*entry.get(), *position],
+                    message: format!(
+                        "There can be only one input field named \"{}\".",
+                        field_name
+                    ),
+                }),
+                Entry::Vacant(entry) => {
+                    entry.insert(*position);
+                }
+            }
+        }
+    }
+}
+
+impl ValidationRule for UniqueInputFieldNames {
+    fn error_code<'a>(&self) -> &'a str {
+        "UniqueInputFieldNames"
+    }
+
+    fn visitor<'a>(&self) -> Option<Box<dyn OperationVisitor<'a, ValidationErrorContext> + 'a>> {
+        Some(Box::new(UniqueInputFieldNames::new()))
+    }
+}
+
+#[test]
+fn input_object_with_fields() {
+    use crate::validation::test_utils::*;
+
+    let mut plan = create_plan_from_rule(Box::new(UniqueInputFieldNames::new()));
+    let errors = test_operation_with_schema(
+        "{
+          field(arg: { f: true })
+        }",
+        TEST_SCHEMA,
+        &mut plan,
+    );
+
+    assert_eq!(get_messages(&errors).len(), 0);
+}
+
+#[test]
+fn same_input_object_within_two_args() {
+    use crate::validation::test_utils::*;
+
+    let mut plan = create_plan_from_rule(Box::new(UniqueInputFieldNames::new()));
+    let errors = test_operation_with_schema(
+        "{
+          field(arg1: { f: true }, arg2: { f: true })
+        }",
+        TEST_SCHEMA,
+        &mut plan,
+    );
+
+    assert_eq!(get_messages(&errors).len(), 0);
+}
+
+#[test]
+fn multiple_input_object_fields() {
+    use crate::validation::test_utils::*;
+
+    let mut plan = create_plan_from_rule(Box::new(UniqueInputFieldNames::new()));
+    let errors = test_operation_with_schema(
+        "{
+          field(arg: { f1: \"value\", f2: \"value\", f3: \"value\" })
+        }",
+        TEST_SCHEMA,
+        &mut plan,
+    );
+
+    assert_eq!(get_messages(&errors).len(), 0);
+}
+
+#[test]
+fn allows_for_nested_input_objects_with_similar_fields() {
+    use crate::validation::test_utils::*;
+
+    let mut plan = create_plan_from_rule(Box::new(UniqueInputFieldNames::new()));
+    let errors = test_operation_with_schema(
+        "{
+          field(arg: {
+            deep: {
+              deep: {
+                id: 1
+              }
+              id: 1
+            }
+            id: 1
+          })
+        }",
+        TEST_SCHEMA,
+        &mut plan,
+    );
+
+    assert_eq!(get_messages(&errors).len(), 0);
+}
+
+#[test]
+fn ignores_selection_sets_aliases_and_strings() {
+    use crate::validation::test_utils::*;
+
+    let mut plan = create_plan_from_rule(Box::new(UniqueInputFieldNames::new()));
+    let errors = test_operation_with_schema(
+        "query q($v: [ComplexInput] = [{ intField: 1 }, { intField: 2 }]) {
+          a: field(arg: { f: \"f: {f: 1, f: 2}\", g: \"\"\"g: 1 g: 2\"\"\" }) { f f }
+          # { f: 1, f: 2 }
+          b: field(arg: { f: $v, g: ENUM, h: [{ f: 1 } { f: 2 }] })
+        }",
+        TEST_SCHEMA,
+        &mut plan,
+    );
+
+    assert_eq!(get_messages(&errors).len(), 0);
+}
+
+#[test]
+fn duplicate_input_object_fields() {
+    use crate::validation::test_utils::*;
+
+    let mut plan = create_plan_from_rule(Box::new(UniqueInputFieldNames::new()));
+    let errors = test_operation_with_schema(
+        "{
+          field(arg: { f1: \"value\", f1: \"value\" })
+        }",
+        TEST_SCHEMA,
+        &mut plan,
+    );
+
+    let messages = get_messages(&errors);
+    assert_eq!(messages.len(), 1);
+    assert_eq!(
+        messages,
+        vec!["There can be only one input field named \"f1\"."]
+    );
+    assert_eq!(
+        errors[0].locations,
+        vec![
+            Pos {
+                line: 2,
+                column: 24
+            },
+            Pos {
+                line: 2,
+                column: 37
+            }
+        ]
+    );
+}
+
+#[test]
+fn many_duplicate_input_object_fields() {
+    use crate::validation::test_utils::*;
+
+    let mut plan = create_plan_from_rule(Box::new(UniqueInputFieldNames::new()));
+    let errors = test_operation_with_schema(
+        "{
+          field(arg: { f1: \"value\", f1: \"value\", f1: \"value\" })
+        }",
+        TEST_SCHEMA,
+        &mut plan,
+    );
+
+    let messages = get_messages(&errors);
+    assert_eq!(messages.len(), 2);
+    assert_eq!(
+        messages,
+        vec![
+            "There can be only one input field named \"f1\".",
+            "There can be only one input field named \"f1\"."
+        ]
+    );
+    assert_eq!(
+        errors[0].locations,
+        vec![
+            Pos {
+                line: 2,
+                column: 24
+            },
+            Pos {
+                line: 2,
+                column: 37
+            }
+        ]
+    );
+    assert_eq!(
+        errors[1].locations,
+        vec![
+            Pos {
+                line: 2,
+                column: 24
+            },
+            Pos {
+                line: 2,
+                column: 50
+            }
+        ]
+    );
+}
+
+#[test]
+fn nested_duplicate_input_object_fields() {
+    use crate::validation::test_utils::*;
+
+    let mut plan = create_plan_from_rule(Box::new(UniqueInputFieldNames::new()));
+    let errors = test_operation_with_schema(
+        "{
+          field(arg: { f1: {f2: \"value\", f2: \"value\" }})
+        }",
+        TEST_SCHEMA,
+        &mut plan,
+    );
+
+    let messages = get_messages(&errors);
+    assert_eq!(messages.len(), 1);
+    assert_eq!(
+        messages,
+        vec!["There can be only one input field named \"f2\"."]
+    );
+    assert_eq!(
+        errors[0].locations,
+        vec![
+            Pos {
+                line: 2,
+                column: 29
+            },
+            Pos {
+                line: 2,
+                column: 42
+            }
+        ]
+    );
+}
+
+#[test]
+fn duplicate_input_object_fields_within_lists_and_defaults() {
+    use crate::validation::test_utils::*;
+
+    let mut plan = create_plan_from_rule(Box::new(UniqueInputFieldNames::new()));
+    let errors = test_operation_with_schema(
+        "query q($v: ComplexInput = { intField: 1, intField: 2 }) {
+          field(arg: [{ f: 1 }, { f: 1, f: 2 }])
+        }",
+        TEST_SCHEMA,
+        &mut plan,
+    );
+
+    let messages = get_messages(&errors);
+    assert_eq!(
+        messages,
+        vec![
+            "There can be only one input field named \"intField\".",
+            "There can be only one input field named \"f\"."
+        ]
+    );
+}
+
+#[test]
+fn requires_operation_source_for_object_values() {
+    use crate::validation::test_utils::*;
+    use crate::validation::validate::validate;
+
+    let plan = create_plan_from_rule(Box::new(UniqueInputFieldNames::new()));
+    let schema_ast = crate::parser::parse_schema(TEST_SCHEMA).expect("Failed to parse schema");
+    let validate_operation = |operation: &str| {
+        let operation_ast = crate::parser::parse_query(operation).unwrap().into_static();
+
+        get_messages(&validate(&schema_ast, &operation_ast, &plan))
+            .into_iter()
+            .cloned()
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(
+        validate_operation("{ field(arg: { f: 1, f: 2 }) a: field(arg: { g: { h: 1 } }) }"),
+        vec!["Input object fields can't be checked for duplicates without the source of the operation, validate it with `validate_with_source`."]
+    );
+    assert_eq!(
+        validate_operation("{ field(arg: [1, 2]) }"),
+        Vec::<String>::new()
+    );
+}
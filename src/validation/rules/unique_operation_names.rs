@@ -1,7 +1,10 @@
 use std::collections::HashMap;
 
 use super::ValidationRule;
-use crate::ast::{visit_document, AstNodeWithName, OperationVisitor, OperationVisitorContext};
+use crate::ast::{
+    AstNodeWithName, OperationDefinitionExtension, OperationVisitor, OperationVisitorContext,
+};
+use crate::parser::Pos;
 use crate::static_graphql::query::*;
 use crate::validation::utils::{ValidationError, ValidationErrorContext};
 
@@ -11,18 +14,29 @@ use crate::validation::utils::{ValidationError, ValidationErrorContext};
 ///
 /// See https://spec.graphql.org/draft/#sec-Operation-Name-Uniqueness
 pub struct UniqueOperationNames<'a> {
-    findings_counter: HashMap<&'a str, i32>,
+    known_names: HashMap<&'a str, Pos>,
 }
 
 impl<'a> OperationVisitor<'a, ValidationErrorContext> for UniqueOperationNames<'a> {
     fn enter_operation_definition(
         &mut self,
         _: &mut OperationVisitorContext,
-        _: &mut ValidationErrorContext,
+        user_context: &mut ValidationErrorContext,
         operation_definition: &'a OperationDefinition,
     ) {
         if let Some(name) = operation_definition.node_name() {
-            self.store_finding(name);
+            let position = operation_definition.position();
+
+            if let Some(known_position) = self.known_names.get(name) {
+                user_context.report_error(ValidationError {
+                    error_code: self.error_code(),
+                    extensions: None,
+                    message: format!("There can be only one operation named \"{}\".", name),
+                    locations: vec![*known_position, position],
+                });
+            } else {
+                self.known_names.insert(name, position);
+            }
         }
     }
 }
@@ -36,14 +50,9 @@ impl<'a> Default for UniqueOperationNames<'a> {
 impl<'a> UniqueOperationNames<'a> {
     pub fn new() -> Self {
         Self {
-            findings_counter: HashMap::new(),
+            known_names: HashMap::new(),
         }
     }
-
-    fn store_finding(&mut self, name: &'a str) {
-        let value = *self.findings_counter.entry(name).or_insert(0);
-        self.findings_counter.insert(name, value + 1);
-    }
 }
 
 impl<'u> ValidationRule for UniqueOperationNames<'u> {
@@ -51,25 +60,8 @@ impl<'u> ValidationRule for UniqueOperationNames<'u> {
         "UniqueOperationNames"
     }
 
-    fn validate(
-        &self,
-        ctx: &mut OperationVisitorContext,
-        error_collector: &mut ValidationErrorContext,
-    ) {
-        let mut rule = UniqueOperationNames::new();
-
-        visit_document(&mut rule, ctx.operation, ctx, error_collector);
-
-        rule.findings_counter
-            .into_iter()
-            .filter(|(_key, value)| *value > 1)
-            .for_each(|(key, _value)| {
-                error_collector.report_error(ValidationError {
-                    error_code: self.error_code(),
-                    message: format!("There can be only one operation named \"{}\".", key),
-                    locations: vec![],
-                })
-            })
+    fn visitor<'a>(&self) -> Option<Box<dyn OperationVisitor<'a, ValidationErrorContext> + 'a>> {
+        Some(Box::new(UniqueOperationNames::new()))
     }
 }
 
@@ -208,6 +200,10 @@ fn multiple_operations_of_same_name() {
         messages,
         vec!["There can be only one operation named \"Foo\".",]
     );
+    assert_eq!(
+        errors[0].locations,
+        vec![Pos { line: 1, column: 1 }, Pos { line: 4, column: 9 }]
+    );
 }
 
 #[test]
@@ -232,6 +228,10 @@ fn multiple_ops_of_same_name_of_different_types_mutation() {
         messages,
         vec!["There can be only one operation named \"Foo\".",]
     );
+    assert_eq!(
+        errors[0].locations,
+        vec![Pos { line: 1, column: 1 }, Pos { line: 4, column: 9 }]
+    );
 }
 
 #[test]
@@ -256,4 +256,8 @@ fn multiple_ops_of_same_name_of_different_types_subscription() {
         messages,
         vec!["There can be only one operation named \"Foo\".",]
     );
+    assert_eq!(
+        errors[0].locations,
+        vec![Pos { line: 1, column: 1 }, Pos { line: 4, column: 9 }]
+    );
 }
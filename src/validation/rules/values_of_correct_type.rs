@@ -1,20 +1,26 @@
 use std::collections::BTreeMap;
+use std::sync::Arc;
 
 use crate::parser::schema::TypeDefinition;
 
-use crate::ast::{
-    InputValueHelpers, SchemaDocumentExtension, TypeDefinitionExtension, TypeExtension,
-};
+use crate::ast::{InputValueHelpers, TypeDefinitionExtension, TypeExtension};
+use crate::introspection::is_built_in_scalar;
 use crate::static_graphql::query::Value;
-use crate::validation::utils::ValidationError;
+use crate::validation::custom_scalars::ScalarValidators;
+use crate::validation::utils::{did_you_mean, suggestion_list, ValidationError};
 use crate::{
-    ast::{visit_document, OperationVisitor, OperationVisitorContext},
+    ast::{OperationVisitor, OperationVisitorContext},
     validation::utils::ValidationErrorContext,
 };
 
 use super::ValidationRule;
 
-pub struct ValuesOfCorrectType {}
+pub struct ValuesOfCorrectType {
+    /// The input object type of each object value being visited, to check their fields.
+    input_objects: Vec<Option<String>>,
+    /// Validators of custom scalar literals. Custom scalars without a validator accept any value.
+    scalar_validators: Arc<ScalarValidators>,
+}
 
 impl Default for ValuesOfCorrectType {
     fn default() -> Self {
@@ -24,11 +30,76 @@ impl Default for ValuesOfCorrectType {
 
 impl ValuesOfCorrectType {
     pub fn new() -> Self {
-        Self {}
+        Self::with_scalar_validators(ScalarValidators::new())
+    }
+
+    /// Checks the literals of custom scalars with `scalar_validators`, for example the ones of
+    /// `ScalarValidators::from_specified_by`.
+    pub fn with_scalar_validators(scalar_validators: ScalarValidators) -> Self {
+        Self {
+            input_objects: vec![],
+            scalar_validators: Arc::new(scalar_validators),
+        }
     }
 
     pub fn is_custom_scalar(&self, type_name: &str) -> bool {
-        !matches!(type_name, "String" | "Int" | "Float" | "Boolean" | "ID")
+        !is_built_in_scalar(type_name)
+    }
+
+    /// Checks a literal of a custom scalar with the validator registered for it, if any.
+    pub fn validate_custom_scalar(
+        &mut self,
+        visitor_context: &mut OperationVisitorContext,
+        user_context: &mut ValidationErrorContext,
+        scalar_name: &str,
+        raw_value: &Value,
+    ) {
+        let result = self
+            .scalar_validators
+            .get(scalar_name)
+            .map(|validator| validator.validate_literal(raw_value));
+
+        if let Some(Err(reason)) = result {
+            user_context.report_error(ValidationError {
+                error_code: self.error_code(),
+                extensions: None,
+                message: format!(
+                    "Expected value of type \"{}\", found {}; {}",
+                    scalar_name, raw_value, reason
+                ),
+                locations: vec![visitor_context.current_position()],
+            })
+        }
+    }
+
+    /// Lists and objects are only checked when they are given for a custom scalar (like a `JSON`
+    /// scalar) with a validator. Their items and fields are visited, and checked, on their own
+    /// otherwise. `raw_value` builds the literal, only when it's checked.
+    fn validate_composite_value(
+        &mut self,
+        visitor_context: &mut OperationVisitorContext,
+        user_context: &mut ValidationErrorContext,
+        raw_value: impl FnOnce() -> Value,
+    ) {
+        let is_named_type = visitor_context
+            .current_input_type_literal()
+            .is_some_and(|t| !t.is_list_type() && t.of_type().is_named_type());
+
+        if let (true, Some(TypeDefinition::Scalar(scalar_type_def))) =
+            (is_named_type, visitor_context.current_input_type())
+        {
+            if self.is_custom_scalar(&scalar_type_def.name)
+                && self.scalar_validators.get(&scalar_type_def.name).is_some()
+            {
+                let scalar_name = scalar_type_def.name.clone();
+                self.validate_custom_scalar(
+                    visitor_context,
+                    user_context,
+                    &scalar_name,
+                    &raw_value(),
+                );
+            }
+        }
     }
 
     pub fn validate_value(
@@ -40,15 +111,16 @@ impl ValuesOfCorrectType {
         if let Some(input_type) = visitor_context.current_input_type_literal() {
             let named_type = input_type.inner_type();
 
-            if let Some(type_def) = visitor_context.schema.type_by_name(named_type) {
+            if let Some(type_def) = visitor_context.indexed_schema().type_by_name(named_type) {
                 if !type_def.is_leaf_type() {
                     user_context.report_error(ValidationError {
                         error_code: self.error_code(),
+                        extensions: None,
                         message: format!(
                             "Expected value of type \"{}\", found {}.",
                             named_type, raw_value
                         ),
-                        locations: vec![],
+                        locations: vec![visitor_context.current_position()],
                     })
                 }
 
@@ -63,42 +135,60 @@ impl ValuesOfCorrectType {
                         | ("String", Value::String(_)) => return,
                         (expected, value) => {
                             if self.is_custom_scalar(expected) {
+                                self.validate_custom_scalar(
+                                    visitor_context,
+                                    user_context,
+                                    expected,
+                                    value,
+                                );
                                 return;
                             }
 
                             user_context.report_error(ValidationError {
                                 error_code: self.error_code(),
+                                extensions: None,
                                 message: format!(
                                     "Expected value of type \"{}\", found {}.",
                                     expected, value
                                 ),
-                                locations: vec![],
+                                locations: vec![visitor_context.current_position()],
                             })
                         }
                     }
                 }
 
                 if let TypeDefinition::Enum(enum_type_def) = &type_def {
+                    let value_str = raw_value.to_string();
+                    let suggestions = did_you_mean(
+                        Some("the enum value"),
+                        &suggestion_list(
+                            &value_str,
+                            enum_type_def.values.iter().map(|v| v.name.as_str()),
+                        ),
+                    );
+
                     match raw_value {
                         Value::Enum(enum_value) => {
                             if !enum_type_def.values.iter().any(|v| v.name.eq(enum_value)) {
                                 user_context.report_error(ValidationError {
                                     error_code: self.error_code(),
+                                    extensions: None,
                                     message: format!(
-                                        "Value \"{}\" does not exist in \"{}\" enum.",
-                                        enum_value, enum_type_def.name
+                                        "Value \"{}\" does not exist in \"{}\" enum.{}",
+                                        enum_value, enum_type_def.name, suggestions
                                     ),
-                                    locations: vec![],
+                                    locations: vec![visitor_context.current_position()],
                                 })
                             }
                         }
                         value => user_context.report_error(ValidationError {
                             error_code: self.error_code(),
+                            extensions: None,
                             message: format!(
-                                "Enum \"{}\" cannot represent non-enum value: {}",
-                                enum_type_def.name, value
+                                "Enum \"{}\" cannot represent non-enum value: {}.{}",
+                                enum_type_def.name, value, suggestions
                             ),
-                            locations: vec![],
+                            locations: vec![visitor_context.current_position()],
                         }),
                     }
                 }
@@ -114,12 +204,15 @@ impl<'a> OperationVisitor<'a, ValidationErrorContext> for ValuesOfCorrectType {
         user_context: &mut ValidationErrorContext,
         _: (),
     ) {
+        let position = visitor_context.current_position();
+
         if let Some(input_type) = visitor_context.current_input_type_literal() {
             if input_type.is_non_null() {
                 user_context.report_error(ValidationError {
                     error_code: self.error_code(),
+                    extensions: None,
                     message: format!("Expected value of type \"{}\", found null", input_type),
-                    locations: vec![],
+                    locations: vec![position],
                 })
             }
         }
@@ -131,41 +224,98 @@ impl<'a> OperationVisitor<'a, ValidationErrorContext> for ValuesOfCorrectType {
         user_context: &mut ValidationErrorContext,
         object_value: &BTreeMap<String, Value>,
     ) {
-        if let Some(TypeDefinition::InputObject(input_object_def)) =
-            visitor_context.current_input_type()
-        {
+        let position = visitor_context.current_position();
+
+        self.validate_composite_value(visitor_context, user_context, || {
+            Value::Object(object_value.clone())
+        });
+
+        let input_object_def = match visitor_context.current_input_type() {
+            Some(TypeDefinition::InputObject(input_object_def)) => Some(input_object_def),
+            _ => None,
+        };
+        self.input_objects
+            .push(input_object_def.map(|def| def.name.clone()));
+
+        if let Some(input_object_def) = input_object_def {
             input_object_def.fields.iter().for_each(|field| {
                 if field.is_required() && !object_value.contains_key(&field.name) {
                     user_context.report_error(ValidationError {
                         error_code: self.error_code(),
+                        extensions: None,
                         message: format!(
                             "Field \"{}.{}\" of required type \"{}\" was not provided.",
                             input_object_def.name, field.name, field.value_type
                         ),
-                        locations: vec![],
+                        locations: vec![position],
                     })
                 }
             });
+        }
+    }
 
-            object_value.keys().for_each(|field_name| {
-                if !input_object_def
-                    .fields
-                    .iter()
-                    .any(|f| f.name.eq(field_name))
-                {
-                    user_context.report_error(ValidationError {
-                        error_code: self.error_code(),
-                        message: format!(
-                            "Field \"{}\" is not defined by type \"{}\".",
-                            field_name, input_object_def.name
-                        ),
-                        locations: vec![],
-                    })
-                }
-            });
+    fn leave_object_value(
+        &mut self,
+        _: &mut OperationVisitorContext<'a>,
+        _: &mut ValidationErrorContext,
+        _: &BTreeMap<String, Value>,
+    ) {
+        self.input_objects.pop();
+    }
+
+    fn enter_object_field(
+        &mut self,
+        visitor_context: &mut OperationVisitorContext<'a>,
+        user_context: &mut ValidationErrorContext,
+        (field_name, _): &(String, Value),
+    ) {
+        let position = visitor_context.current_position();
+
+        let input_object_def = match self
+            .input_objects
+            .last()
+            .and_then(|name| name.as_ref())
+            .and_then(|name| visitor_context.indexed_schema().type_by_name(name))
+        {
+            Some(TypeDefinition::InputObject(input_object_def)) => input_object_def,
+            _ => return,
+        };
+
+        if !input_object_def
+            .fields
+            .iter()
+            .any(|f| f.name.eq(field_name))
+        {
+            let suggestions = suggestion_list(
+                field_name,
+                input_object_def.fields.iter().map(|f| f.name.as_str()),
+            );
+
+            user_context.report_error(ValidationError {
+                error_code: self.error_code(),
+                extensions: None,
+                message: format!(
+                    "Field \"{}\" is not defined by type \"{}\".{}",
+                    field_name,
+                    input_object_def.name,
+                    did_you_mean(None, &suggestions)
+                ),
+                locations: vec![position],
+            })
         }
     }
 
+    fn enter_list_value(
+        &mut self,
+        visitor_context: &mut OperationVisitorContext<'a>,
+        user_context: &mut ValidationErrorContext,
+        list_value: &Vec<Value>,
+    ) {
+        self.validate_composite_value(visitor_context, user_context, || {
+            Value::List(list_value.clone())
+        });
+    }
+
     fn enter_enum_value(
         &mut self,
         visitor_context: &mut OperationVisitorContext<'a>,
@@ -190,17 +340,11 @@ impl ValidationRule for ValuesOfCorrectType {
         "ValuesOfCorrectType"
     }
 
-    fn validate(
-        &self,
-        ctx: &mut OperationVisitorContext,
-        error_collector: &mut ValidationErrorContext,
-    ) {
-        visit_document(
-            &mut ValuesOfCorrectType::new(),
-            ctx.operation,
-            ctx,
-            error_collector,
-        );
+    fn visitor<'a>(&self) -> Option<Box<dyn OperationVisitor<'a, ValidationErrorContext> + 'a>> {
+        Some(Box::new(ValuesOfCorrectType {
+            input_objects: vec![],
+            scalar_validators: self.scalar_validators.clone(),
+        }))
     }
 }
 
@@ -500,6 +644,13 @@ fn invalid_int_into_string() {
         messages,
         vec!["Expected value of type \"String\", found 1."]
     );
+    assert_eq!(
+        errors[0].locations,
+        vec![Pos {
+            line: 4,
+            column: 39
+        }]
+    );
 }
 
 #[test]
@@ -524,6 +675,13 @@ fn invalid_float_into_string() {
         messages,
         vec!["Expected value of type \"String\", found 1."]
     );
+    assert_eq!(
+        errors[0].locations,
+        vec![Pos {
+            line: 4,
+            column: 39
+        }]
+    );
 }
 
 #[test]
@@ -548,6 +706,13 @@ fn invalid_bool_into_string() {
         messages,
         vec!["Expected value of type \"String\", found true."]
     );
+    assert_eq!(
+        errors[0].locations,
+        vec![Pos {
+            line: 4,
+            column: 39
+        }]
+    );
 }
 
 #[test]
@@ -572,6 +737,13 @@ fn unquoted_string_to_string() {
         messages,
         vec!["Expected value of type \"String\", found BAR."]
     );
+    assert_eq!(
+        errors[0].locations,
+        vec![Pos {
+            line: 4,
+            column: 39
+        }]
+    );
 }
 
 #[test]
@@ -596,6 +768,13 @@ fn invalid_string_into_int() {
         messages,
         vec!["Expected value of type \"Int\", found \"3\"."]
     );
+    assert_eq!(
+        errors[0].locations,
+        vec![Pos {
+            line: 4,
+            column: 33
+        }]
+    );
 }
 
 #[test]
@@ -642,6 +821,13 @@ fn unquoted_string_into_int() {
     let messages = get_messages(&errors);
     assert_eq!(messages.len(), 1);
     assert_eq!(messages, vec!["Expected value of type \"Int\", found FOO."]);
+    assert_eq!(
+        errors[0].locations,
+        vec![Pos {
+            line: 4,
+            column: 33
+        }]
+    );
 }
 
 #[test]
@@ -663,6 +849,13 @@ fn simple_float_into_int() {
     let messages = get_messages(&errors);
     assert_eq!(messages.len(), 1);
     assert_eq!(messages, vec!["Expected value of type \"Int\", found 3."]);
+    assert_eq!(
+        errors[0].locations,
+        vec![Pos {
+            line: 4,
+            column: 33
+        }]
+    );
 }
 
 #[test]
@@ -687,6 +880,13 @@ fn float_into_int() {
         messages,
         vec!["Expected value of type \"Int\", found 3.333."]
     );
+    assert_eq!(
+        errors[0].locations,
+        vec![Pos {
+            line: 4,
+            column: 33
+        }]
+    );
 }
 
 #[test]
@@ -711,6 +911,13 @@ fn string_into_float() {
         messages,
         vec!["Expected value of type \"Float\", found \"3.333\"."]
     );
+    assert_eq!(
+        errors[0].locations,
+        vec![Pos {
+            line: 4,
+            column: 37
+        }]
+    );
 }
 
 #[test]
@@ -735,6 +942,13 @@ fn boolean_into_float() {
         messages,
         vec!["Expected value of type \"Float\", found true."]
     );
+    assert_eq!(
+        errors[0].locations,
+        vec![Pos {
+            line: 4,
+            column: 37
+        }]
+    );
 }
 
 #[test]
@@ -759,6 +973,13 @@ fn unquoted_into_float() {
         messages,
         vec!["Expected value of type \"Float\", found FOO."]
     );
+    assert_eq!(
+        errors[0].locations,
+        vec![Pos {
+            line: 4,
+            column: 37
+        }]
+    );
 }
 
 #[test]
@@ -783,6 +1004,13 @@ fn int_into_boolean() {
         messages,
         vec!["Expected value of type \"Boolean\", found 2."]
     );
+    assert_eq!(
+        errors[0].locations,
+        vec![Pos {
+            line: 4,
+            column: 41
+        }]
+    );
 }
 
 #[test]
@@ -807,6 +1035,13 @@ fn float_into_boolean() {
         messages,
         vec!["Expected value of type \"Boolean\", found 2."]
     );
+    assert_eq!(
+        errors[0].locations,
+        vec![Pos {
+            line: 4,
+            column: 41
+        }]
+    );
 }
 
 #[test]
@@ -831,6 +1066,13 @@ fn string_into_boolean() {
         messages,
         vec!["Expected value of type \"Boolean\", found \"true\"."]
     );
+    assert_eq!(
+        errors[0].locations,
+        vec![Pos {
+            line: 4,
+            column: 41
+        }]
+    );
 }
 
 #[test]
@@ -855,6 +1097,13 @@ fn unquoted_into_boolean() {
         messages,
         vec!["Expected value of type \"Boolean\", found TRUE."]
     );
+    assert_eq!(
+        errors[0].locations,
+        vec![Pos {
+            line: 4,
+            column: 41
+        }]
+    );
 }
 
 #[test]
@@ -876,6 +1125,13 @@ fn float_into_id() {
     let messages = get_messages(&errors);
     assert_eq!(messages.len(), 1);
     assert_eq!(messages, vec!["Expected value of type \"ID\", found 1."]);
+    assert_eq!(
+        errors[0].locations,
+        vec![Pos {
+            line: 4,
+            column: 31
+        }]
+    );
 }
 
 #[test]
@@ -897,6 +1153,13 @@ fn bool_into_id() {
     let messages = get_messages(&errors);
     assert_eq!(messages.len(), 1);
     assert_eq!(messages, vec!["Expected value of type \"ID\", found true."]);
+    assert_eq!(
+        errors[0].locations,
+        vec![Pos {
+            line: 4,
+            column: 31
+        }]
+    );
 }
 
 #[test]
@@ -921,6 +1184,13 @@ fn unquoted_into_id() {
         messages,
         vec!["Expected value of type \"ID\", found SOMETHING."]
     );
+    assert_eq!(
+        errors[0].locations,
+        vec![Pos {
+            line: 4,
+            column: 31
+        }]
+    );
 }
 
 #[test]
@@ -943,7 +1213,14 @@ fn int_into_enum() {
     assert_eq!(messages.len(), 1);
     assert_eq!(
         messages,
-        vec!["Enum \"DogCommand\" cannot represent non-enum value: 2"]
+        vec!["Enum \"DogCommand\" cannot represent non-enum value: 2."]
+    );
+    assert_eq!(
+        errors[0].locations,
+        vec![Pos {
+            line: 4,
+            column: 41
+        }]
     );
 }
 
@@ -967,7 +1244,14 @@ fn float_into_enum() {
     assert_eq!(messages.len(), 1);
     assert_eq!(
         messages,
-        vec!["Enum \"DogCommand\" cannot represent non-enum value: 1"]
+        vec!["Enum \"DogCommand\" cannot represent non-enum value: 1."]
+    );
+    assert_eq!(
+        errors[0].locations,
+        vec![Pos {
+            line: 4,
+            column: 41
+        }]
     );
 }
 
@@ -991,7 +1275,14 @@ fn string_into_enum() {
     assert_eq!(messages.len(), 1);
     assert_eq!(
         messages,
-        vec!["Enum \"DogCommand\" cannot represent non-enum value: \"SIT\""]
+        vec!["Enum \"DogCommand\" cannot represent non-enum value: \"SIT\". Did you mean the enum value \"SIT\"?"]
+    );
+    assert_eq!(
+        errors[0].locations,
+        vec![Pos {
+            line: 4,
+            column: 41
+        }]
     );
 }
 
@@ -1015,7 +1306,14 @@ fn boolean_into_enum() {
     assert_eq!(messages.len(), 1);
     assert_eq!(
         messages,
-        vec!["Enum \"DogCommand\" cannot represent non-enum value: true"]
+        vec!["Enum \"DogCommand\" cannot represent non-enum value: true."]
+    );
+    assert_eq!(
+        errors[0].locations,
+        vec![Pos {
+            line: 4,
+            column: 41
+        }]
     );
 }
 
@@ -1041,6 +1339,13 @@ fn unknown_enum_value_into_enum() {
         messages,
         vec!["Value \"JUGGLE\" does not exist in \"DogCommand\" enum."]
     );
+    assert_eq!(
+        errors[0].locations,
+        vec![Pos {
+            line: 4,
+            column: 41
+        }]
+    );
 }
 
 #[test]
@@ -1063,7 +1368,14 @@ fn different_case_enum_value_into_enum() {
     assert_eq!(messages.len(), 1);
     assert_eq!(
         messages,
-        vec!["Value \"sit\" does not exist in \"DogCommand\" enum."]
+        vec!["Value \"sit\" does not exist in \"DogCommand\" enum. Did you mean the enum value \"SIT\"?"]
+    );
+    assert_eq!(
+        errors[0].locations,
+        vec![Pos {
+            line: 4,
+            column: 41
+        }]
     );
 }
 
@@ -1169,6 +1481,13 @@ fn incorrect_item_type() {
         messages,
         vec!["Expected value of type \"String\", found 2."]
     );
+    assert_eq!(
+        errors[0].locations,
+        vec![Pos {
+            line: 4,
+            column: 55
+        }]
+    );
 }
 
 #[test]
@@ -1193,6 +1512,13 @@ fn single_value_of_incorrect_type() {
         messages,
         vec!["Expected value of type \"String\", found 1."]
     );
+    assert_eq!(
+        errors[0].locations,
+        vec![Pos {
+            line: 4,
+            column: 47
+        }]
+    );
 }
 
 #[test]
@@ -1419,7 +1745,21 @@ fn incorrect_value_type() {
             "Expected value of type \"Int\", found \"two\".",
             "Expected value of type \"Int\", found \"one\"."
         ]
-    )
+    );
+    assert_eq!(
+        errors[0].locations,
+        vec![Pos {
+            line: 4,
+            column: 32
+        }]
+    );
+    assert_eq!(
+        errors[1].locations,
+        vec![Pos {
+            line: 4,
+            column: 45
+        }]
+    );
 }
 
 #[test]
@@ -1444,6 +1784,13 @@ fn incorrect_value_and_missing_argument() {
         messages,
         vec!["Expected value of type \"Int\", found \"one\"."]
     );
+    assert_eq!(
+        errors[0].locations,
+        vec![Pos {
+            line: 4,
+            column: 32
+        }]
+    );
 }
 
 #[test]
@@ -1468,6 +1815,13 @@ fn null_value() {
         messages,
         vec!["Expected value of type \"Int!\", found null"]
     );
+    assert_eq!(
+        errors[0].locations,
+        vec![Pos {
+            line: 4,
+            column: 32
+        }]
+    );
 }
 
 #[test]
@@ -1625,7 +1979,14 @@ fn partial_object_missing_required() {
         vec![
             "Field \"ComplexInput.requiredField\" of required type \"Boolean!\" was not provided."
         ]
-    )
+    );
+    assert_eq!(
+        errors[0].locations,
+        vec![Pos {
+            line: 4,
+            column: 41
+        }]
+    );
 }
 
 #[test]
@@ -1652,7 +2013,14 @@ fn partial_object_invalid_field_type() {
     assert_eq!(
         messages,
         vec!["Expected value of type \"String\", found 2."]
-    )
+    );
+    assert_eq!(
+        errors[0].locations,
+        vec![Pos {
+            line: 5,
+            column: 40
+        }]
+    );
 }
 
 #[test]
@@ -1679,7 +2047,14 @@ fn partial_object_null_to_non_null_field() {
     assert_eq!(
         messages,
         vec!["Expected value of type \"Boolean!\", found null"]
-    )
+    );
+    assert_eq!(
+        errors[0].locations,
+        vec![Pos {
+            line: 6,
+            column: 29
+        }]
+    );
 }
 
 #[test]
@@ -1705,8 +2080,15 @@ fn partial_object_unknown_field_arg() {
     assert_eq!(messages.len(), 1);
     assert_eq!(
         messages,
-        vec!["Field \"invalidField\" is not defined by type \"ComplexInput\"."]
-    )
+        vec!["Field \"invalidField\" is not defined by type \"ComplexInput\". Did you mean \"intField\"?"]
+    );
+    assert_eq!(
+        errors[0].locations,
+        vec![Pos {
+            line: 6,
+            column: 15
+        }]
+    );
 }
 
 #[test]
@@ -1783,7 +2165,21 @@ fn with_directives_of_invalid_types() {
             "Expected value of type \"Boolean\", found \"yes\".",
             "Expected value of type \"Boolean\", found ENUM."
         ]
-    )
+    );
+    assert_eq!(
+        errors[0].locations,
+        vec![Pos {
+            line: 3,
+            column: 28
+        }]
+    );
+    assert_eq!(
+        errors[1].locations,
+        vec![Pos {
+            line: 4,
+            column: 28
+        }]
+    );
 }
 
 #[test]
@@ -1859,6 +2255,27 @@ fn variables_with_invalid_default_null_values() {
             "Expected value of type \"Boolean!\", found null"
         ]
     );
+    assert_eq!(
+        errors[0].locations,
+        vec![Pos {
+            line: 3,
+            column: 22
+        }]
+    );
+    assert_eq!(
+        errors[1].locations,
+        vec![Pos {
+            line: 4,
+            column: 25
+        }]
+    );
+    assert_eq!(
+        errors[2].locations,
+        vec![Pos {
+            line: 5,
+            column: 47
+        }]
+    );
 }
 
 #[test]
@@ -1889,6 +2306,27 @@ fn variables_with_invalid_default_values() {
             "Expected value of type \"ComplexInput\", found \"NotVeryComplex\"."
         ]
     );
+    assert_eq!(
+        errors[0].locations,
+        vec![Pos {
+            line: 3,
+            column: 21
+        }]
+    );
+    assert_eq!(
+        errors[1].locations,
+        vec![Pos {
+            line: 4,
+            column: 24
+        }]
+    );
+    assert_eq!(
+        errors[2].locations,
+        vec![Pos {
+            line: 5,
+            column: 30
+        }]
+    );
 }
 
 #[test]
@@ -1916,6 +2354,20 @@ fn variables_with_complex_invalid_default_values() {
             "Expected value of type \"Boolean\", found 123.",
         ]
     );
+    assert_eq!(
+        errors[0].locations,
+        vec![Pos {
+            line: 3,
+            column: 62
+        }]
+    );
+    assert_eq!(
+        errors[1].locations,
+        vec![Pos {
+            line: 3,
+            column: 47
+        }]
+    );
 }
 
 #[test]
@@ -1940,6 +2392,13 @@ fn complex_variables_missing_required_field() {
             "Field \"ComplexInput.requiredField\" of required type \"Boolean!\" was not provided.",
         ]
     );
+    assert_eq!(
+        errors[0].locations,
+        vec![Pos {
+            line: 2,
+            column: 55
+        }]
+    );
 }
 
 #[test]
@@ -1962,4 +2421,130 @@ fn list_variables_with_invalid_item() {
         messages,
         vec!["Expected value of type \"String\", found 2."]
     );
+    assert_eq!(
+        errors[0].locations,
+        vec![Pos {
+            line: 2,
+            column: 50
+        }]
+    );
+}
+
+#[cfg(test)]
+static CUSTOM_SCALARS_TEST_SCHEMA: &str = "
+scalar DateTime @specifiedBy(url: \"https://scalars.graphql.org/andimarek/date-time\")
+scalar JSON
+
+type Query {
+  events(after: DateTime, between: [DateTime!]): [String]
+  config(value: JSON): String
+}
+";
+
+#[test]
+fn custom_scalars_without_validator_accept_any_value() {
+    use crate::validation::test_utils::*;
+
+    let mut plan = create_plan_from_rule(Box::new(ValuesOfCorrectType::new()));
+    let errors = test_operation_with_schema(
+        "
+        {
+          events(after: 123, between: [true])
+          config(value: { a: [1, \"b\"] })
+        }",
+        CUSTOM_SCALARS_TEST_SCHEMA,
+        &mut plan,
+    );
+
+    let messages = get_messages(&errors);
+    assert_eq!(messages.len(), 0);
+}
+
+#[test]
+fn custom_scalar_validators_check_literals() {
+    use crate::validation::custom_scalars::DateTimeValidator;
+    use crate::validation::test_utils::*;
+
+    let mut scalar_validators = ScalarValidators::new();
+    scalar_validators.add_validator("DateTime", DateTimeValidator);
+    let mut plan = create_plan_from_rule(Box::new(ValuesOfCorrectType::with_scalar_validators(
+        scalar_validators,
+    )));
+    let errors = test_operation_with_schema(
+        "
+        {
+          valid: events(after: \"2023-01-31T10:15:30Z\", between: [\"2023-01-31T10:15:30Z\"])
+          invalid: events(after: 123, between: [\"2023-01-31\"])
+        }",
+        CUSTOM_SCALARS_TEST_SCHEMA,
+        &mut plan,
+    );
+
+    let messages = get_messages(&errors);
+    assert_eq!(messages.len(), 2);
+    assert_eq!(
+        messages,
+        vec![
+            "Expected value of type \"DateTime\", found 123; Expected an RFC 3339 date-time string.",
+            "Expected value of type \"DateTime\", found \"2023-01-31\"; Expected an RFC 3339 date-time string."
+        ]
+    );
+}
+
+#[test]
+fn custom_scalar_validators_check_list_and_object_literals() {
+    use crate::validation::test_utils::*;
+
+    let mut scalar_validators = ScalarValidators::new();
+    scalar_validators.add_validator("JSON", |value: &Value| match value {
+        Value::Object(_) => Ok(()),
+        _ => Err("Expected a JSON object.".to_string()),
+    });
+    let mut plan = create_plan_from_rule(Box::new(ValuesOfCorrectType::with_scalar_validators(
+        scalar_validators,
+    )));
+    let errors = test_operation_with_schema(
+        "
+        {
+          valid: config(value: { a: [1, 2] })
+          invalid: config(value: [1, 2])
+        }",
+        CUSTOM_SCALARS_TEST_SCHEMA,
+        &mut plan,
+    );
+
+    let messages = get_messages(&errors);
+    assert_eq!(messages.len(), 1);
+    assert_eq!(
+        messages,
+        vec!["Expected value of type \"JSON\", found [1, 2]; Expected a JSON object."]
+    );
+}
+
+#[test]
+fn custom_scalar_validators_from_specified_by() {
+    use crate::validation::test_utils::*;
+
+    let schema = crate::parser::parse_schema::<String>(CUSTOM_SCALARS_TEST_SCHEMA)
+        .expect("Failed to parse schema")
+        .into_static();
+    let mut plan = create_plan_from_rule(Box::new(ValuesOfCorrectType::with_scalar_validators(
+        ScalarValidators::from_specified_by(&schema),
+    )));
+    let errors = test_operation_with_schema(
+        "
+        {
+          events(after: \"yesterday\")
+        }",
+        CUSTOM_SCALARS_TEST_SCHEMA,
+        &mut plan,
+    );
+
+    let messages = get_messages(&errors);
+    assert_eq!(
+        messages,
+        vec![
+            "Expected value of type \"DateTime\", found \"yesterday\"; Expected an RFC 3339 date-time string."
+        ]
+    );
 }
@@ -1,7 +1,7 @@
 use super::ValidationRule;
 use crate::ast::{
-    visit_document, OperationVisitor, OperationVisitorContext, SchemaDocumentExtension,
-    TypeDefinitionExtension, TypeExtension,
+    OperationVisitor, OperationVisitorContext, SchemaDocumentExtension, TypeDefinitionExtension,
+    TypeExtension,
 };
 use crate::validation::utils::ValidationError;
 use crate::validation::utils::ValidationErrorContext;
@@ -35,6 +35,7 @@ impl<'a> OperationVisitor<'a, ValidationErrorContext> for VariablesAreInputTypes
             if !var_schema_type.is_input_type() {
                 user_context.report_error(ValidationError {
                     error_code: self.error_code(),
+                    extensions: None,
                     message: format!(
                         "Variable \"${}\" cannot be non-input type \"{}\".",
                         variable_definition.name, variable_definition.var_type
@@ -51,17 +52,8 @@ impl ValidationRule for VariablesAreInputTypes {
         "VariablesAreInputTypes"
     }
 
-    fn validate(
-        &self,
-        ctx: &mut OperationVisitorContext,
-        error_collector: &mut ValidationErrorContext,
-    ) {
-        visit_document(
-            &mut VariablesAreInputTypes::new(),
-            ctx.operation,
-            ctx,
-            error_collector,
-        );
+    fn visitor<'a>(&self) -> Option<Box<dyn OperationVisitor<'a, ValidationErrorContext> + 'a>> {
+        Some(Box::new(VariablesAreInputTypes::new()))
     }
 }
 
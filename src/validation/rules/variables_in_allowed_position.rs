@@ -1,10 +1,8 @@
 use std::collections::{HashMap, HashSet};
 
 use crate::{
-    ast::{
-        visit_document, AstNodeWithName, OperationVisitor, OperationVisitorContext,
-        SchemaDocumentExtension,
-    },
+    ast::{AstNodeWithName, OperationVisitor, OperationVisitorContext, SchemaDocumentExtension},
+    parser::Pos,
     static_graphql::query::{Type, Value, VariableDefinition},
     validation::utils::{ValidationError, ValidationErrorContext},
 };
@@ -19,7 +17,7 @@ use super::ValidationRule;
 #[derive(Default)]
 pub struct VariablesInAllowedPosition<'a> {
     spreads: HashMap<Scope<'a>, HashSet<&'a str>>,
-    variable_usages: HashMap<Scope<'a>, Vec<(&'a str, &'a Type)>>,
+    variable_usages: HashMap<Scope<'a>, Vec<(&'a str, &'a Type, Pos)>>,
     variable_defs: HashMap<Scope<'a>, Vec<&'a VariableDefinition>>,
     current_scope: Option<Scope<'a>>,
 }
@@ -49,7 +47,7 @@ impl<'a> VariablesInAllowedPosition<'a> {
         visited.insert(from.clone());
 
         if let Some(usages) = self.variable_usages.get(from) {
-            for (var_name, var_type) in usages {
+            for (var_name, var_type, position) in usages {
                 if let Some(var_def) = var_defs.iter().find(|var_def| var_def.name == *var_name) {
                     let expected_type = match (&var_def.default_value, &var_def.var_type) {
                         (Some(_), Type::ListType(inner)) => Type::NonNullType(inner.clone()),
@@ -63,15 +61,19 @@ impl<'a> VariablesInAllowedPosition<'a> {
                         (_, t) => t.clone(),
                     };
 
-                    if !visitor_context.schema.is_subtype(&expected_type, var_type) {
+                    if !visitor_context
+                        .indexed_schema()
+                        .is_subtype(&expected_type, var_type)
+                    {
                         user_context.report_error(ValidationError {
                           error_code: self.error_code(),
+                          extensions: None,
                             message: format!("Variable \"${}\" of type \"{}\" used in position expecting type \"{}\".",
                                 var_name,
                                 expected_type,
                                 var_type,
                             ),
-                            locations: vec![var_def.position],
+                            locations: vec![var_def.position, *position],
                         });
                     }
                 }
@@ -168,6 +170,8 @@ impl<'a> OperationVisitor<'a, ValidationErrorContext> for VariablesInAllowedPosi
         _: &mut ValidationErrorContext,
         variable_name: &'a str,
     ) {
+        let position = visitor_context.current_position();
+
         if let (Some(scope), Some(input_type)) = (
             &self.current_scope,
             visitor_context.current_input_type_literal(),
@@ -175,7 +179,7 @@ impl<'a> OperationVisitor<'a, ValidationErrorContext> for VariablesInAllowedPosi
             self.variable_usages
                 .entry(scope.clone())
                 .or_default()
-                .push((variable_name, input_type));
+                .push((variable_name, input_type, position));
         }
     }
 }
@@ -185,17 +189,8 @@ impl<'v> ValidationRule for VariablesInAllowedPosition<'v> {
         "VariablesInAllowedPosition"
     }
 
-    fn validate(
-        &self,
-        ctx: &mut OperationVisitorContext,
-        error_collector: &mut ValidationErrorContext,
-    ) {
-        visit_document(
-            &mut VariablesInAllowedPosition::new(),
-            ctx.operation,
-            ctx,
-            error_collector,
-        );
+    fn visitor<'a>(&self) -> Option<Box<dyn OperationVisitor<'a, ValidationErrorContext> + 'a>> {
+        Some(Box::new(VariablesInAllowedPosition::new()))
     }
 }
 
@@ -427,7 +422,20 @@ fn int_to_int_nonnull() {
     assert_eq!(
         messages,
         vec!["Variable \"$intArg\" of type \"Int\" used in position expecting type \"Int!\"."]
-    )
+    );
+    assert_eq!(
+        errors[0].locations,
+        vec![
+            Pos {
+                line: 1,
+                column: 13
+            },
+            Pos {
+                line: 3,
+                column: 47
+            }
+        ]
+    );
 }
 
 #[test]
@@ -453,7 +461,20 @@ fn int_to_int_nonnull_within_fragment() {
     assert_eq!(
         messages,
         vec!["Variable \"$intArg\" of type \"Int\" used in position expecting type \"Int!\"."]
-    )
+    );
+    assert_eq!(
+        errors[0].locations,
+        vec![
+            Pos {
+                line: 4,
+                column: 21
+            },
+            Pos {
+                line: 2,
+                column: 45
+            }
+        ]
+    );
 }
 
 #[test]
@@ -482,7 +503,20 @@ fn int_to_int_nonnull_within_nested_fragment() {
     assert_eq!(
         messages,
         vec!["Variable \"$intArg\" of type \"Int\" used in position expecting type \"Int!\"."]
-    )
+    );
+    assert_eq!(
+        errors[0].locations,
+        vec![
+            Pos {
+                line: 7,
+                column: 21
+            },
+            Pos {
+                line: 5,
+                column: 45
+            }
+        ]
+    );
 }
 
 #[test]
@@ -507,7 +541,20 @@ fn string_over_boolean() {
         vec![
       "Variable \"$stringVar\" of type \"String\" used in position expecting type \"Boolean\"."
     ]
-    )
+    );
+    assert_eq!(
+        errors[0].locations,
+        vec![
+            Pos {
+                line: 1,
+                column: 13
+            },
+            Pos {
+                line: 3,
+                column: 41
+            }
+        ]
+    );
 }
 
 #[test]
@@ -532,7 +579,20 @@ fn string_over_string_list() {
         vec![
       "Variable \"$stringVar\" of type \"String\" used in position expecting type \"[String]\"."
     ]
-    )
+    );
+    assert_eq!(
+        errors[0].locations,
+        vec![
+            Pos {
+                line: 1,
+                column: 13
+            },
+            Pos {
+                line: 3,
+                column: 47
+            }
+        ]
+    );
 }
 
 #[test]
@@ -555,7 +615,20 @@ fn boolean_to_boolean_nonnull_in_directive() {
         vec![
       "Variable \"$boolVar\" of type \"Boolean\" used in position expecting type \"Boolean!\"."
     ]
-    )
+    );
+    assert_eq!(
+        errors[0].locations,
+        vec![
+            Pos {
+                line: 1,
+                column: 13
+            },
+            Pos {
+                line: 2,
+                column: 28
+            }
+        ]
+    );
 }
 
 #[test]
@@ -578,7 +651,20 @@ fn string_to_boolean_nonnull_in_directive() {
         vec![
       "Variable \"$stringVar\" of type \"String\" used in position expecting type \"Boolean!\"."
     ]
-    )
+    );
+    assert_eq!(
+        errors[0].locations,
+        vec![
+            Pos {
+                line: 1,
+                column: 13
+            },
+            Pos {
+                line: 2,
+                column: 28
+            }
+        ]
+    );
 }
 
 #[test]
@@ -601,7 +687,20 @@ fn string_list_to_string_nonnull_list() {
     assert_eq!(messages.len(), 1);
     assert_eq!(messages, vec![
       "Variable \"$stringListVar\" of type \"[String]\" used in position expecting type \"[String!]\"."
-    ])
+    ]);
+    assert_eq!(
+        errors[0].locations,
+        vec![
+            Pos {
+                line: 1,
+                column: 13
+            },
+            Pos {
+                line: 4,
+                column: 61
+            }
+        ]
+    );
 }
 
 #[test]
@@ -624,7 +723,20 @@ fn int_to_int_non_null_with_null_default_value() {
     assert_eq!(
         messages,
         vec!["Variable \"$intVar\" of type \"Int\" used in position expecting type \"Int!\"."]
-    )
+    );
+    assert_eq!(
+        errors[0].locations,
+        vec![
+            Pos {
+                line: 1,
+                column: 13
+            },
+            Pos {
+                line: 3,
+                column: 47
+            }
+        ]
+    );
 }
 
 #[test]
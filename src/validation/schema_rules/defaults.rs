@@ -0,0 +1,25 @@
+use crate::validation::validate_schema::SchemaValidationPlan;
+
+use super::{
+    DirectiveDefinitions, EnumValues, FieldDefinitions, InputObjectCircularRefs, InputObjectFields,
+    InterfaceImplementations, KnownTypeReferences, PossibleTypeExtensions, ReservedNames,
+    RootOperationTypes, UnionMembers,
+};
+
+pub fn default_schema_rules_validation_plan() -> SchemaValidationPlan {
+    let mut plan = SchemaValidationPlan::new();
+
+    plan.add_rule(Box::new(RootOperationTypes::new()));
+    plan.add_rule(Box::new(KnownTypeReferences::new()));
+    plan.add_rule(Box::new(ReservedNames::new()));
+    plan.add_rule(Box::new(DirectiveDefinitions::new()));
+    plan.add_rule(Box::new(FieldDefinitions::new()));
+    plan.add_rule(Box::new(InterfaceImplementations::new()));
+    plan.add_rule(Box::new(UnionMembers::new()));
+    plan.add_rule(Box::new(EnumValues::new()));
+    plan.add_rule(Box::new(InputObjectFields::new()));
+    plan.add_rule(Box::new(InputObjectCircularRefs::new()));
+    plan.add_rule(Box::new(PossibleTypeExtensions::new()));
+
+    plan
+}
@@ -0,0 +1,135 @@
+use std::collections::HashSet;
+
+use super::{is_deprecated, SchemaValidationContext, SchemaValidationRule};
+use crate::ast::{SchemaVisitor, TypeDefinitionExtension, TypeExtension};
+use crate::static_graphql::schema::DirectiveDefinition;
+use crate::validation::utils::ValidationError;
+
+/// Directive definitions
+///
+/// A directive definition must declare uniquely named arguments of input
+/// types, and its required arguments can't be deprecated.
+///
+/// See https://spec.graphql.org/draft/#sec-Type-System.Directives.Validation
+pub struct DirectiveDefinitions;
+
+impl Default for DirectiveDefinitions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DirectiveDefinitions {
+    pub fn new() -> Self {
+        DirectiveDefinitions
+    }
+}
+
+impl<'a> SchemaVisitor<SchemaValidationContext<'a>> for DirectiveDefinitions {
+    fn enter_directive_definition(
+        &self,
+        node: &DirectiveDefinition,
+        ctx: &mut SchemaValidationContext<'a>,
+    ) {
+        let mut known_arguments = HashSet::new();
+
+        for argument in &node.arguments {
+            if !known_arguments.insert(&argument.name) {
+                ctx.report_error(ValidationError {
+                    error_code: self.error_code(),
+                    extensions: None,
+                    locations: vec![argument.position],
+                    message: format!(
+                        "Argument \"@{}({}:)\" can only be defined once.",
+                        node.name, argument.name
+                    ),
+                });
+            }
+
+            if let Some(argument_type) = ctx.type_by_name(argument.value_type.inner_type()) {
+                if !argument_type.is_input_type() {
+                    ctx.report_error(ValidationError {
+                        error_code: self.error_code(),
+                        extensions: None,
+                        locations: vec![argument.position],
+                        message: format!(
+                            "The type of @{}({}:) must be Input Type but got: {}.",
+                            node.name, argument.name, argument.value_type
+                        ),
+                    });
+                }
+            }
+
+            if argument.value_type.is_non_null()
+                && argument.default_value.is_none()
+                && is_deprecated(&argument.directives)
+            {
+                ctx.report_error(ValidationError {
+                    error_code: self.error_code(),
+                    extensions: None,
+                    locations: vec![argument.position],
+                    message: format!(
+                        "Required argument @{}({}:) cannot be deprecated.",
+                        node.name, argument.name
+                    ),
+                });
+            }
+        }
+    }
+}
+
+impl SchemaValidationRule for DirectiveDefinitions {
+    fn error_code<'a>(&self) -> &'a str {
+        "DirectiveDefinitions"
+    }
+
+    fn validate(&self, ctx: &mut SchemaValidationContext) {
+        let schema = ctx.schema;
+        self.visit_schema_document(schema, ctx);
+    }
+}
+
+#[test]
+fn accepts_valid_directives() {
+    use crate::validation::test_utils::*;
+
+    let plan = create_schema_plan_from_rule(Box::new(DirectiveDefinitions {}));
+    let errors = test_schema(
+        "
+        directive @cache(ttl: Int!, scope: Scope = PUBLIC) on FIELD_DEFINITION
+
+        enum Scope {
+          PUBLIC
+          PRIVATE
+        }",
+        &plan,
+    );
+
+    assert_eq!(get_messages(&errors).len(), 0);
+}
+
+#[test]
+fn rejects_invalid_arguments() {
+    use crate::validation::test_utils::*;
+
+    let plan = create_schema_plan_from_rule(Box::new(DirectiveDefinitions {}));
+    let errors = test_schema(
+        "
+        directive @cache(ttl: Int, ttl: Int, owner: User, scope: String! @deprecated) on FIELD_DEFINITION
+
+        type User {
+          id: ID
+        }",
+        &plan,
+    );
+
+    let messages = get_messages(&errors);
+    assert_eq!(
+        messages,
+        vec![
+            "Argument \"@cache(ttl:)\" can only be defined once.",
+            "The type of @cache(owner:) must be Input Type but got: User.",
+            "Required argument @cache(scope:) cannot be deprecated."
+        ]
+    );
+}
@@ -0,0 +1,120 @@
+use std::collections::HashSet;
+
+use super::{SchemaValidationContext, SchemaValidationRule};
+use crate::ast::SchemaVisitor;
+use crate::static_graphql::schema::EnumType;
+use crate::validation::utils::ValidationError;
+
+/// Enum values
+///
+/// An Enum type must define one or more unique values, and none of them can
+/// be named `true`, `false` or `null`.
+///
+/// See https://spec.graphql.org/draft/#sec-Enums.Type-Validation
+pub struct EnumValues;
+
+impl Default for EnumValues {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EnumValues {
+    pub fn new() -> Self {
+        EnumValues
+    }
+}
+
+impl<'a> SchemaVisitor<SchemaValidationContext<'a>> for EnumValues {
+    fn enter_enum_type(&self, node: &EnumType, ctx: &mut SchemaValidationContext<'a>) {
+        if node.values.is_empty() {
+            ctx.report_error(ValidationError {
+                error_code: self.error_code(),
+                extensions: None,
+                locations: vec![node.position],
+                message: format!("Enum type {} must define one or more values.", node.name),
+            });
+        }
+
+        let mut known_values = HashSet::new();
+
+        for value in &node.values {
+            if matches!(value.name.as_str(), "true" | "false" | "null") {
+                ctx.report_error(ValidationError {
+                    error_code: self.error_code(),
+                    extensions: None,
+                    locations: vec![value.position],
+                    message: format!(
+                        "Enum type {} cannot include value: {}.",
+                        node.name, value.name
+                    ),
+                });
+            }
+
+            if !known_values.insert(&value.name) {
+                ctx.report_error(ValidationError {
+                    error_code: self.error_code(),
+                    extensions: None,
+                    locations: vec![value.position],
+                    message: format!(
+                        "Enum value \"{}.{}\" can only be defined once.",
+                        node.name, value.name
+                    ),
+                });
+            }
+        }
+    }
+}
+
+impl SchemaValidationRule for EnumValues {
+    fn error_code<'a>(&self) -> &'a str {
+        "EnumValues"
+    }
+
+    fn validate(&self, ctx: &mut SchemaValidationContext) {
+        let schema = ctx.schema;
+        self.visit_schema_document(schema, ctx);
+    }
+}
+
+#[test]
+fn accepts_unique_values() {
+    use crate::validation::test_utils::*;
+
+    let plan = create_schema_plan_from_rule(Box::new(EnumValues {}));
+    let errors = test_schema(
+        "
+        enum Color {
+          RED
+          GREEN
+        }",
+        &plan,
+    );
+
+    assert_eq!(get_messages(&errors).len(), 0);
+}
+
+#[test]
+fn rejects_invalid_values() {
+    use crate::validation::test_utils::*;
+
+    let plan = create_schema_plan_from_rule(Box::new(EnumValues {}));
+    let errors = test_schema(
+        "
+        enum Color {
+          RED
+          RED
+          null
+        }",
+        &plan,
+    );
+
+    let messages = get_messages(&errors);
+    assert_eq!(
+        messages,
+        vec![
+            "Enum value \"Color.RED\" can only be defined once.",
+            "Enum type Color cannot include value: null."
+        ]
+    );
+}
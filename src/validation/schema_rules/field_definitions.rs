@@ -0,0 +1,234 @@
+use super::{is_deprecated, SchemaValidationContext, SchemaValidationRule};
+use crate::ast::{SchemaVisitor, TypeDefinitionExtension, TypeExtension};
+use crate::parser::Pos;
+use crate::static_graphql::schema::{Field, InterfaceType, ObjectType, TypeDefinition};
+use crate::validation::utils::ValidationError;
+
+/// Field definitions
+///
+/// Object and Interface types must define one or more fields, every field
+/// must have an output type and every field argument must have an input type.
+/// Required arguments can't be deprecated.
+///
+/// See https://spec.graphql.org/draft/#sec-Objects.Type-Validation
+pub struct FieldDefinitions;
+
+impl Default for FieldDefinitions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FieldDefinitions {
+    pub fn new() -> Self {
+        FieldDefinitions
+    }
+
+    fn validate_fields(
+        &self,
+        ctx: &mut SchemaValidationContext,
+        type_name: &str,
+        fields: &[Field],
+        position: Pos,
+    ) {
+        if fields.is_empty() {
+            ctx.report_error(ValidationError {
+                error_code: self.error_code(),
+                extensions: None,
+                locations: vec![position],
+                message: format!("Type {} must define one or more fields.", type_name),
+            });
+        }
+
+        for field in fields {
+            if let Some(TypeDefinition::InputObject(_)) =
+                ctx.type_by_name(field.field_type.inner_type())
+            {
+                ctx.report_error(ValidationError {
+                    error_code: self.error_code(),
+                    extensions: None,
+                    locations: vec![field.position],
+                    message: format!(
+                        "The type of {}.{} must be Output Type but got: {}.",
+                        type_name, field.name, field.field_type
+                    ),
+                });
+            }
+
+            for argument in &field.arguments {
+                if let Some(argument_type) = ctx.type_by_name(argument.value_type.inner_type()) {
+                    if !argument_type.is_input_type() {
+                        ctx.report_error(ValidationError {
+                            error_code: self.error_code(),
+                            extensions: None,
+                            locations: vec![argument.position],
+                            message: format!(
+                                "The type of {}.{}({}:) must be Input Type but got: {}.",
+                                type_name, field.name, argument.name, argument.value_type
+                            ),
+                        });
+                    }
+                }
+
+                if argument.value_type.is_non_null()
+                    && argument.default_value.is_none()
+                    && is_deprecated(&argument.directives)
+                {
+                    ctx.report_error(ValidationError {
+                        error_code: self.error_code(),
+                        extensions: None,
+                        locations: vec![argument.position],
+                        message: format!(
+                            "Required argument {}.{}({}:) cannot be deprecated.",
+                            type_name, field.name, argument.name
+                        ),
+                    });
+                }
+            }
+        }
+    }
+}
+
+impl<'a> SchemaVisitor<SchemaValidationContext<'a>> for FieldDefinitions {
+    fn enter_object_type(&self, node: &ObjectType, ctx: &mut SchemaValidationContext<'a>) {
+        self.validate_fields(ctx, &node.name, &node.fields, node.position);
+    }
+
+    fn enter_interface_type(&self, node: &InterfaceType, ctx: &mut SchemaValidationContext<'a>) {
+        self.validate_fields(ctx, &node.name, &node.fields, node.position);
+    }
+}
+
+impl SchemaValidationRule for FieldDefinitions {
+    fn error_code<'a>(&self) -> &'a str {
+        "FieldDefinitions"
+    }
+
+    fn validate(&self, ctx: &mut SchemaValidationContext) {
+        let schema = ctx.schema;
+        self.visit_schema_document(schema, ctx);
+    }
+}
+
+#[test]
+fn accepts_valid_fields() {
+    use crate::validation::test_utils::*;
+
+    let plan = create_schema_plan_from_rule(Box::new(FieldDefinitions {}));
+    let errors = test_schema(
+        "
+        type Query {
+          user(filter: Filter, role: Role, limit: Int! = 10 @deprecated): User
+        }
+
+        interface User {
+          name: String
+          role: Role
+        }
+
+        enum Role {
+          ADMIN
+        }
+
+        input Filter {
+          active: Boolean
+        }",
+        &plan,
+    );
+
+    assert_eq!(get_messages(&errors).len(), 0);
+}
+
+#[test]
+fn rejects_types_without_fields() {
+    use crate::validation::test_utils::*;
+
+    let plan = create_schema_plan_from_rule(Box::new(FieldDefinitions {}));
+    let errors = test_schema(
+        "
+        type Query
+
+        interface Node",
+        &plan,
+    );
+
+    let messages = get_messages(&errors);
+    assert_eq!(
+        messages,
+        vec![
+            "Type Query must define one or more fields.",
+            "Type Node must define one or more fields."
+        ]
+    );
+}
+
+#[test]
+fn rejects_input_types_as_field_types() {
+    use crate::validation::test_utils::*;
+
+    let plan = create_schema_plan_from_rule(Box::new(FieldDefinitions {}));
+    let errors = test_schema(
+        "
+        type Query {
+          field: [Filter!]
+        }
+
+        input Filter {
+          active: Boolean
+        }",
+        &plan,
+    );
+
+    let messages = get_messages(&errors);
+    assert_eq!(
+        messages,
+        vec!["The type of Query.field must be Output Type but got: [Filter!]."]
+    );
+    assert_eq!(
+        errors[0].locations,
+        vec![Pos {
+            line: 3,
+            column: 11
+        }]
+    );
+}
+
+#[test]
+fn rejects_output_types_as_arguments() {
+    use crate::validation::test_utils::*;
+
+    let plan = create_schema_plan_from_rule(Box::new(FieldDefinitions {}));
+    let errors = test_schema(
+        "
+        type Query {
+          field(arg: Query, other: String!): String
+        }",
+        &plan,
+    );
+
+    let messages = get_messages(&errors);
+    assert_eq!(
+        messages,
+        vec!["The type of Query.field(arg:) must be Input Type but got: Query."]
+    );
+}
+
+#[test]
+fn rejects_deprecated_required_arguments() {
+    use crate::validation::test_utils::*;
+
+    let plan = create_schema_plan_from_rule(Box::new(FieldDefinitions {}));
+    let errors = test_schema(
+        "
+        type Query {
+          field(arg: String! @deprecated): String
+        }",
+        &plan,
+    );
+
+    let messages = get_messages(&errors);
+    assert_eq!(
+        messages,
+        vec!["Required argument Query.field(arg:) cannot be deprecated."]
+    );
+}
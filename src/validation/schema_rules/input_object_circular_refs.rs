@@ -0,0 +1,203 @@
+use std::collections::HashMap;
+
+use super::{SchemaValidationContext, SchemaValidationRule};
+use crate::static_graphql::schema::{self, InputValue, Type, TypeDefinition};
+use crate::validation::utils::ValidationError;
+
+/// Input object circular references
+///
+/// An Input Object type can't reference itself through a chain of non-null
+/// fields, because such a value could never be provided.
+///
+/// See https://spec.graphql.org/draft/#sec-Input-Objects.Type-Validation
+pub struct InputObjectCircularRefs;
+
+impl Default for InputObjectCircularRefs {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl InputObjectCircularRefs {
+    pub fn new() -> Self {
+        InputObjectCircularRefs
+    }
+}
+
+struct CircularRefsDetector<'a> {
+    visited_types: Vec<&'a str>,
+    field_path: Vec<&'a InputValue>,
+    field_path_index_by_type_name: HashMap<&'a str, usize>,
+}
+
+impl<'a> CircularRefsDetector<'a> {
+    fn new() -> Self {
+        CircularRefsDetector {
+            visited_types: vec![],
+            field_path: vec![],
+            field_path_index_by_type_name: HashMap::new(),
+        }
+    }
+
+    /// This does a straight-forward DFS to find cycles.
+    /// It does not terminate when a cycle was found but continues to explore
+    /// the graph to find all possible cycles.
+    fn detect_cycle_recursive(
+        &mut self,
+        rule: &InputObjectCircularRefs,
+        ctx: &mut SchemaValidationContext<'a>,
+        input_object: &'a schema::InputObjectType,
+    ) {
+        if self.visited_types.contains(&input_object.name.as_str()) {
+            return;
+        }
+
+        self.visited_types.push(&input_object.name);
+        self.field_path_index_by_type_name
+            .insert(&input_object.name, self.field_path.len());
+
+        for field in &input_object.fields {
+            if let Type::NonNullType(inner_type) = &field.value_type {
+                if let Type::NamedType(type_name) = inner_type.as_ref() {
+                    if let Some(TypeDefinition::InputObject(field_type)) =
+                        ctx.type_by_name(type_name)
+                    {
+                        let cycle_index = self
+                            .field_path_index_by_type_name
+                            .get(field_type.name.as_str())
+                            .copied();
+
+                        self.field_path.push(field);
+
+                        match cycle_index {
+                            None => self.detect_cycle_recursive(rule, ctx, field_type),
+                            Some(cycle_index) => {
+                                let cycle_path = &self.field_path[cycle_index..];
+                                let path_str = cycle_path
+                                    .iter()
+                                    .map(|field| field.name.as_str())
+                                    .collect::<Vec<_>>()
+                                    .join(".");
+
+                                ctx.report_error(ValidationError {
+                                    error_code: rule.error_code(),
+                                    extensions: None,
+                                    locations: cycle_path.iter().map(|f| f.position).collect(),
+                                    message: format!(
+                                        "Cannot reference Input Object \"{}\" within itself through a series of non-null fields: \"{}\".",
+                                        field_type.name, path_str
+                                    ),
+                                });
+                            }
+                        }
+
+                        self.field_path.pop();
+                    }
+                }
+            }
+        }
+
+        self.field_path_index_by_type_name
+            .remove(input_object.name.as_str());
+    }
+}
+
+impl SchemaValidationRule for InputObjectCircularRefs {
+    fn error_code<'a>(&self) -> &'a str {
+        "InputObjectCircularRefs"
+    }
+
+    fn validate(&self, ctx: &mut SchemaValidationContext) {
+        let schema = ctx.schema;
+        let mut detector = CircularRefsDetector::new();
+
+        for definition in &schema.definitions {
+            if let schema::Definition::TypeDefinition(TypeDefinition::InputObject(input_object)) =
+                definition
+            {
+                detector.detect_cycle_recursive(self, ctx, input_object);
+            }
+        }
+    }
+}
+
+#[test]
+fn accepts_nullable_and_list_cycles() {
+    use crate::validation::test_utils::*;
+
+    let plan = create_schema_plan_from_rule(Box::new(InputObjectCircularRefs {}));
+    let errors = test_schema(
+        "
+        input A {
+          b: B!
+          self: A
+          list: [A!]!
+        }
+
+        input B {
+          a: A
+        }",
+        &plan,
+    );
+
+    assert_eq!(get_messages(&errors).len(), 0);
+}
+
+#[test]
+fn rejects_non_null_self_reference() {
+    use crate::validation::test_utils::*;
+
+    let plan = create_schema_plan_from_rule(Box::new(InputObjectCircularRefs {}));
+    let errors = test_schema(
+        "
+        input A {
+          self: A!
+        }",
+        &plan,
+    );
+
+    let messages = get_messages(&errors);
+    assert_eq!(
+        messages,
+        vec!["Cannot reference Input Object \"A\" within itself through a series of non-null fields: \"self\"."]
+    );
+    assert_eq!(
+        errors[0].locations,
+        vec![crate::parser::Pos {
+            line: 3,
+            column: 11
+        }]
+    );
+}
+
+#[test]
+fn rejects_non_null_cycles_through_multiple_types() {
+    use crate::validation::test_utils::*;
+
+    let plan = create_schema_plan_from_rule(Box::new(InputObjectCircularRefs {}));
+    let errors = test_schema(
+        "
+        input A {
+          b: B!
+        }
+
+        input B {
+          c: C!
+        }
+
+        input C {
+          a: A!
+          b: B!
+        }",
+        &plan,
+    );
+
+    let messages = get_messages(&errors);
+    assert_eq!(
+        messages,
+        vec![
+            "Cannot reference Input Object \"A\" within itself through a series of non-null fields: \"b.c.a\".",
+            "Cannot reference Input Object \"B\" within itself through a series of non-null fields: \"c.b\"."
+        ]
+    );
+}
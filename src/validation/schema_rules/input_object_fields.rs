@@ -0,0 +1,147 @@
+use super::{is_deprecated, SchemaValidationContext, SchemaValidationRule};
+use crate::ast::{SchemaVisitor, TypeDefinitionExtension, TypeExtension};
+use crate::static_graphql::schema::InputObjectType;
+use crate::validation::utils::ValidationError;
+
+/// Input object fields
+///
+/// An Input Object type must define one or more fields, all of them must
+/// have an input type, and required fields can't be deprecated.
+///
+/// See https://spec.graphql.org/draft/#sec-Input-Objects.Type-Validation
+pub struct InputObjectFields;
+
+impl Default for InputObjectFields {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl InputObjectFields {
+    pub fn new() -> Self {
+        InputObjectFields
+    }
+}
+
+impl<'a> SchemaVisitor<SchemaValidationContext<'a>> for InputObjectFields {
+    fn enter_input_object_type(
+        &self,
+        node: &InputObjectType,
+        ctx: &mut SchemaValidationContext<'a>,
+    ) {
+        if node.fields.is_empty() {
+            ctx.report_error(ValidationError {
+                error_code: self.error_code(),
+                extensions: None,
+                locations: vec![node.position],
+                message: format!(
+                    "Input Object type {} must define one or more fields.",
+                    node.name
+                ),
+            });
+        }
+
+        for field in &node.fields {
+            if let Some(field_type) = ctx.type_by_name(field.value_type.inner_type()) {
+                if !field_type.is_input_type() {
+                    ctx.report_error(ValidationError {
+                        error_code: self.error_code(),
+                        extensions: None,
+                        locations: vec![field.position],
+                        message: format!(
+                            "The type of {}.{} must be Input Type but got: {}.",
+                            node.name, field.name, field.value_type
+                        ),
+                    });
+                }
+            }
+
+            if field.value_type.is_non_null()
+                && field.default_value.is_none()
+                && is_deprecated(&field.directives)
+            {
+                ctx.report_error(ValidationError {
+                    error_code: self.error_code(),
+                    extensions: None,
+                    locations: vec![field.position],
+                    message: format!(
+                        "Required input field {}.{} cannot be deprecated.",
+                        node.name, field.name
+                    ),
+                });
+            }
+        }
+    }
+}
+
+impl SchemaValidationRule for InputObjectFields {
+    fn error_code<'a>(&self) -> &'a str {
+        "InputObjectFields"
+    }
+
+    fn validate(&self, ctx: &mut SchemaValidationContext) {
+        let schema = ctx.schema;
+        self.visit_schema_document(schema, ctx);
+    }
+}
+
+#[test]
+fn accepts_input_fields() {
+    use crate::validation::test_utils::*;
+
+    let plan = create_schema_plan_from_rule(Box::new(InputObjectFields {}));
+    let errors = test_schema(
+        "
+        input Filter {
+          name: String
+          role: Role
+          nested: [Filter!]
+          legacy: Int @deprecated
+        }
+
+        enum Role {
+          ADMIN
+        }",
+        &plan,
+    );
+
+    assert_eq!(get_messages(&errors).len(), 0);
+}
+
+#[test]
+fn rejects_invalid_input_fields() {
+    use crate::validation::test_utils::*;
+
+    let plan = create_schema_plan_from_rule(Box::new(InputObjectFields {}));
+    let errors = test_schema(
+        "
+        input Empty
+
+        input Filter {
+          user: User
+          id: ID! @deprecated(reason: \"no longer used\")
+        }
+
+        type User {
+          id: ID
+        }",
+        &plan,
+    );
+
+    let messages = get_messages(&errors);
+    assert_eq!(
+        messages,
+        vec![
+            "Input Object type Empty must define one or more fields.",
+            "The type of Filter.user must be Input Type but got: User.",
+            "Required input field Filter.id cannot be deprecated."
+        ]
+    );
+    assert_eq!(
+        errors[1].locations,
+        vec![crate::parser::Pos {
+            line: 5,
+            column: 11
+        }]
+    );
+}
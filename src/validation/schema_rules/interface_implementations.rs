@@ -0,0 +1,461 @@
+use std::collections::HashSet;
+
+use super::{SchemaValidationContext, SchemaValidationRule};
+use crate::ast::{
+    ImplementingInterfaceExtension, SchemaDocumentExtension, SchemaVisitor,
+    TypeDefinitionExtension, TypeExtension,
+};
+use crate::parser::Pos;
+use crate::static_graphql::schema::{Field, InterfaceType, ObjectType, TypeDefinition};
+use crate::validation::utils::ValidationError;
+
+/// Interface implementations
+///
+/// An Object or Interface type is only valid if every interface it implements
+/// is an Interface type, and if it provides every field of the interface with
+/// a compatible (covariant) type and compatible arguments. Interfaces
+/// implemented by those interfaces must be declared as well.
+///
+/// See https://spec.graphql.org/draft/#IsValidImplementation()
+pub struct InterfaceImplementations;
+
+impl Default for InterfaceImplementations {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl InterfaceImplementations {
+    pub fn new() -> Self {
+        InterfaceImplementations
+    }
+
+    fn validate_interfaces(
+        &self,
+        ctx: &mut SchemaValidationContext,
+        type_name: &str,
+        fields: &[Field],
+        interfaces: &[String],
+        position: Pos,
+    ) {
+        let mut implemented = HashSet::new();
+
+        for interface_name in interfaces {
+            if interface_name == type_name {
+                ctx.report_error(ValidationError {
+                    error_code: self.error_code(),
+                    extensions: None,
+                    locations: vec![position],
+                    message: format!(
+                        "Type {} cannot implement itself because it would create a circular reference.",
+                        type_name
+                    ),
+                });
+                continue;
+            }
+
+            if !implemented.insert(interface_name) {
+                ctx.report_error(ValidationError {
+                    error_code: self.error_code(),
+                    extensions: None,
+                    locations: vec![position],
+                    message: format!(
+                        "Type {} can only implement {} once.",
+                        type_name, interface_name
+                    ),
+                });
+                continue;
+            }
+
+            match ctx.type_by_name(interface_name) {
+                Some(TypeDefinition::Interface(interface)) => {
+                    self.validate_ancestors(ctx, type_name, interfaces, interface, position);
+                    self.validate_fields(ctx, type_name, fields, interface, position);
+                }
+                Some(other_type) => ctx.report_error(ValidationError {
+                    error_code: self.error_code(),
+                    extensions: None,
+                    locations: vec![position],
+                    message: format!(
+                        "Type {} must only implement Interface types, it cannot implement {}.",
+                        type_name,
+                        other_type.name()
+                    ),
+                }),
+                // Unknown types are reported by `KnownTypeReferences`
+                None => {}
+            }
+        }
+    }
+
+    fn validate_ancestors(
+        &self,
+        ctx: &mut SchemaValidationContext,
+        type_name: &str,
+        interfaces: &[String],
+        interface: &InterfaceType,
+        position: Pos,
+    ) {
+        for transitive in interface.interfaces() {
+            if !interfaces.contains(&transitive) {
+                ctx.report_error(ValidationError {
+                    error_code: self.error_code(),
+                    extensions: None,
+                    locations: vec![position],
+                    message: match transitive == type_name {
+                        true => format!(
+                            "Type {} cannot implement {} because it would create a circular reference.",
+                            type_name, interface.name
+                        ),
+                        false => format!(
+                            "Type {} must implement {} because it is implemented by {}.",
+                            type_name, transitive, interface.name
+                        ),
+                    },
+                });
+            }
+        }
+    }
+
+    fn validate_fields(
+        &self,
+        ctx: &mut SchemaValidationContext,
+        type_name: &str,
+        fields: &[Field],
+        interface: &InterfaceType,
+        position: Pos,
+    ) {
+        for interface_field in &interface.fields {
+            let field_name = &interface_field.name;
+            let type_field = match fields.iter().find(|f| f.name.eq(field_name)) {
+                Some(type_field) => type_field,
+                None => {
+                    ctx.report_error(ValidationError {
+                        error_code: self.error_code(),
+                        extensions: None,
+                        locations: vec![interface_field.position, position],
+                        message: format!(
+                            "Interface field {}.{} expected but {} does not provide it.",
+                            interface.name, field_name, type_name
+                        ),
+                    });
+                    continue;
+                }
+            };
+
+            if !ctx
+                .schema
+                .is_subtype(&type_field.field_type, &interface_field.field_type)
+            {
+                ctx.report_error(ValidationError {
+                    error_code: self.error_code(),
+                    extensions: None,
+                    locations: vec![interface_field.position, type_field.position],
+                    message: format!(
+                        "Interface field {}.{} expects type {} but {}.{} is type {}.",
+                        interface.name,
+                        field_name,
+                        interface_field.field_type,
+                        type_name,
+                        field_name,
+                        type_field.field_type
+                    ),
+                });
+            }
+
+            for interface_arg in &interface_field.arguments {
+                let arg_name = &interface_arg.name;
+
+                match type_field.arguments.iter().find(|a| a.name.eq(arg_name)) {
+                    None => ctx.report_error(ValidationError {
+                        error_code: self.error_code(),
+                        extensions: None,
+                        locations: vec![interface_arg.position, type_field.position],
+                        message: format!(
+                            "Interface field argument {}.{}({}:) expected but {}.{} does not provide it.",
+                            interface.name, field_name, arg_name, type_name, field_name
+                        ),
+                    }),
+                    // Arguments are invariant, so the types have to be the same.
+                    Some(type_arg) if type_arg.value_type != interface_arg.value_type => {
+                        ctx.report_error(ValidationError {
+                            error_code: self.error_code(),
+                            extensions: None,
+                            locations: vec![interface_arg.position, type_arg.position],
+                            message: format!(
+                                "Interface field argument {}.{}({}:) expects type {} but {}.{}({}:) is type {}.",
+                                interface.name,
+                                field_name,
+                                arg_name,
+                                interface_arg.value_type,
+                                type_name,
+                                field_name,
+                                arg_name,
+                                type_arg.value_type
+                            ),
+                        })
+                    }
+                    Some(_) => {}
+                }
+            }
+
+            for type_arg in &type_field.arguments {
+                let is_provided_by_interface = interface_field
+                    .arguments
+                    .iter()
+                    .any(|a| a.name.eq(&type_arg.name));
+
+                if !is_provided_by_interface
+                    && type_arg.value_type.is_non_null()
+                    && type_arg.default_value.is_none()
+                {
+                    ctx.report_error(ValidationError {
+                        error_code: self.error_code(),
+                        extensions: None,
+                        locations: vec![type_arg.position, interface_field.position],
+                        message: format!(
+                            "Argument {}.{}({}:) must not be required type {} if not provided by the Interface field {}.{}.",
+                            type_name,
+                            field_name,
+                            type_arg.name,
+                            type_arg.value_type,
+                            interface.name,
+                            field_name
+                        ),
+                    });
+                }
+            }
+        }
+    }
+}
+
+impl<'a> SchemaVisitor<SchemaValidationContext<'a>> for InterfaceImplementations {
+    fn enter_object_type(&self, node: &ObjectType, ctx: &mut SchemaValidationContext<'a>) {
+        self.validate_interfaces(
+            ctx,
+            &node.name,
+            &node.fields,
+            &node.implements_interfaces,
+            node.position,
+        );
+    }
+
+    fn enter_interface_type(&self, node: &InterfaceType, ctx: &mut SchemaValidationContext<'a>) {
+        self.validate_interfaces(
+            ctx,
+            &node.name,
+            &node.fields,
+            &node.implements_interfaces,
+            node.position,
+        );
+    }
+}
+
+impl SchemaValidationRule for InterfaceImplementations {
+    fn error_code<'a>(&self) -> &'a str {
+        "InterfaceImplementations"
+    }
+
+    fn validate(&self, ctx: &mut SchemaValidationContext) {
+        let schema = ctx.schema;
+        self.visit_schema_document(schema, ctx);
+    }
+}
+
+#[test]
+fn accepts_valid_implementations() {
+    use crate::validation::test_utils::*;
+
+    let plan = create_schema_plan_from_rule(Box::new(InterfaceImplementations {}));
+    let errors = test_schema(
+        "
+        type Query {
+          node: Node
+        }
+
+        interface Node {
+          id: ID!
+          friends(first: Int): [Node]
+        }
+
+        interface Resource implements Node {
+          id: ID!
+          friends(first: Int): [Resource!]
+        }
+
+        type User implements Resource & Node {
+          id: ID!
+          friends(first: Int, after: String, sort: Boolean! = true): [User!]!
+        }",
+        &plan,
+    );
+
+    assert_eq!(get_messages(&errors).len(), 0);
+}
+
+#[test]
+fn rejects_non_interface_and_duplicated_implementations() {
+    use crate::validation::test_utils::*;
+
+    let plan = create_schema_plan_from_rule(Box::new(InterfaceImplementations {}));
+    let errors = test_schema(
+        "
+        type Query implements Query & Other & Node & Node {
+          id: ID
+        }
+
+        type Other {
+          id: ID
+        }
+
+        interface Node {
+          id: ID
+        }",
+        &plan,
+    );
+
+    let messages = get_messages(&errors);
+    assert_eq!(
+        messages,
+        vec![
+            "Type Query cannot implement itself because it would create a circular reference.",
+            "Type Query must only implement Interface types, it cannot implement Other.",
+            "Type Query can only implement Node once."
+        ]
+    );
+}
+
+#[test]
+fn rejects_missing_fields() {
+    use crate::validation::test_utils::*;
+
+    let plan = create_schema_plan_from_rule(Box::new(InterfaceImplementations {}));
+    let errors = test_schema(
+        "
+        interface Node {
+          id: ID!
+        }
+
+        type User implements Node {
+          name: String
+        }",
+        &plan,
+    );
+
+    let messages = get_messages(&errors);
+    assert_eq!(
+        messages,
+        vec!["Interface field Node.id expected but User does not provide it."]
+    );
+    assert_eq!(
+        errors[0].locations,
+        vec![
+            Pos {
+                line: 3,
+                column: 11
+            },
+            Pos { line: 6, column: 9 }
+        ]
+    );
+}
+
+#[test]
+fn rejects_non_covariant_field_types() {
+    use crate::validation::test_utils::*;
+
+    let plan = create_schema_plan_from_rule(Box::new(InterfaceImplementations {}));
+    let errors = test_schema(
+        "
+        interface Node {
+          id: ID!
+          parent: Node
+        }
+
+        type Other {
+          id: ID
+        }
+
+        type User implements Node {
+          id: ID
+          parent: Other
+        }",
+        &plan,
+    );
+
+    let messages = get_messages(&errors);
+    assert_eq!(
+        messages,
+        vec![
+            "Interface field Node.id expects type ID! but User.id is type ID.",
+            "Interface field Node.parent expects type Node but User.parent is type Other."
+        ]
+    );
+}
+
+#[test]
+fn rejects_incompatible_arguments() {
+    use crate::validation::test_utils::*;
+
+    let plan = create_schema_plan_from_rule(Box::new(InterfaceImplementations {}));
+    let errors = test_schema(
+        "
+        interface Node {
+          field(a: String, b: Int): String
+        }
+
+        type User implements Node {
+          field(a: String!, c: Boolean!): String
+        }",
+        &plan,
+    );
+
+    let messages = get_messages(&errors);
+    assert_eq!(
+        messages,
+        vec![
+            "Interface field argument Node.field(a:) expects type String but User.field(a:) is type String!.",
+            "Interface field argument Node.field(b:) expected but User.field does not provide it.",
+            "Argument User.field(c:) must not be required type Boolean! if not provided by the Interface field Node.field."
+        ]
+    );
+}
+
+#[test]
+fn rejects_missing_transitive_interfaces() {
+    use crate::validation::test_utils::*;
+
+    let plan = create_schema_plan_from_rule(Box::new(InterfaceImplementations {}));
+    let errors = test_schema(
+        "
+        interface Node {
+          id: ID
+        }
+
+        interface Resource implements Node {
+          id: ID
+        }
+
+        type User implements Resource {
+          id: ID
+        }
+
+        interface A implements B & A {
+          id: ID
+        }
+
+        interface B implements A {
+          id: ID
+        }",
+        &plan,
+    );
+
+    let messages = get_messages(&errors);
+    assert_eq!(
+        messages,
+        vec![
+            "Type User must implement Node because it is implemented by Resource.",
+            "Type A cannot implement itself because it would create a circular reference.",
+            "Type B cannot implement A because it would create a circular reference."
+        ]
+    );
+}
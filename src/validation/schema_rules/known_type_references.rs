@@ -0,0 +1,190 @@
+use super::{SchemaValidationContext, SchemaValidationRule};
+use crate::ast::{SchemaVisitor, TypeExtension};
+use crate::parser::Pos;
+use crate::static_graphql::schema::{
+    DirectiveDefinition, Field, InputObjectType, InputValue, InterfaceType, ObjectType, Type,
+    UnionType,
+};
+use crate::validation::utils::ValidationError;
+
+/// Known type references
+///
+/// Every type referenced by a schema (field types, arguments, input fields,
+/// implemented interfaces and union members) must be defined in the schema
+/// or be one of the built-in scalars.
+///
+/// See https://spec.graphql.org/draft/#sec-Type-System
+pub struct KnownTypeReferences;
+
+impl Default for KnownTypeReferences {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl KnownTypeReferences {
+    pub fn new() -> Self {
+        KnownTypeReferences
+    }
+
+    fn validate_type_name(&self, ctx: &mut SchemaValidationContext, name: &str, position: Pos) {
+        if !ctx.is_known_type(name) {
+            ctx.report_error(ValidationError {
+                error_code: self.error_code(),
+                extensions: None,
+                locations: vec![position],
+                message: format!("Unknown type \"{}\".", name),
+            });
+        }
+    }
+
+    fn validate_type(&self, ctx: &mut SchemaValidationContext, t: &Type, position: Pos) {
+        self.validate_type_name(ctx, t.inner_type(), position);
+    }
+
+    fn validate_arguments(&self, ctx: &mut SchemaValidationContext, arguments: &[InputValue]) {
+        for argument in arguments {
+            self.validate_type(ctx, &argument.value_type, argument.position);
+        }
+    }
+
+    fn validate_field(&self, ctx: &mut SchemaValidationContext, field: &Field) {
+        self.validate_type(ctx, &field.field_type, field.position);
+        self.validate_arguments(ctx, &field.arguments);
+    }
+
+    fn validate_interfaces(
+        &self,
+        ctx: &mut SchemaValidationContext,
+        interfaces: &[String],
+        position: Pos,
+    ) {
+        for interface in interfaces {
+            self.validate_type_name(ctx, interface, position);
+        }
+    }
+}
+
+impl<'a> SchemaVisitor<SchemaValidationContext<'a>> for KnownTypeReferences {
+    fn enter_object_type(&self, node: &ObjectType, ctx: &mut SchemaValidationContext<'a>) {
+        self.validate_interfaces(ctx, &node.implements_interfaces, node.position);
+    }
+
+    fn enter_object_type_field(
+        &self,
+        node: &Field,
+        _type_: &ObjectType,
+        ctx: &mut SchemaValidationContext<'a>,
+    ) {
+        self.validate_field(ctx, node);
+    }
+
+    fn enter_interface_type(&self, node: &InterfaceType, ctx: &mut SchemaValidationContext<'a>) {
+        self.validate_interfaces(ctx, &node.implements_interfaces, node.position);
+    }
+
+    fn enter_interface_type_field(
+        &self,
+        node: &Field,
+        _type_: &InterfaceType,
+        ctx: &mut SchemaValidationContext<'a>,
+    ) {
+        self.validate_field(ctx, node);
+    }
+
+    fn enter_input_object_type_field(
+        &self,
+        node: &InputValue,
+        _input_type: &InputObjectType,
+        ctx: &mut SchemaValidationContext<'a>,
+    ) {
+        self.validate_type(ctx, &node.value_type, node.position);
+    }
+
+    fn enter_union_type(&self, node: &UnionType, ctx: &mut SchemaValidationContext<'a>) {
+        for member in &node.types {
+            self.validate_type_name(ctx, member, node.position);
+        }
+    }
+
+    fn enter_directive_definition(
+        &self,
+        node: &DirectiveDefinition,
+        ctx: &mut SchemaValidationContext<'a>,
+    ) {
+        self.validate_arguments(ctx, &node.arguments);
+    }
+}
+
+impl SchemaValidationRule for KnownTypeReferences {
+    fn error_code<'a>(&self) -> &'a str {
+        "KnownTypeReferences"
+    }
+
+    fn validate(&self, ctx: &mut SchemaValidationContext) {
+        let schema = ctx.schema;
+        self.visit_schema_document(schema, ctx);
+    }
+}
+
+#[test]
+fn accepts_known_and_built_in_types() {
+    use crate::validation::test_utils::*;
+
+    let plan = create_schema_plan_from_rule(Box::new(KnownTypeReferences {}));
+    let errors = test_schema(
+        "
+        type Query {
+          user(id: ID!, filter: Filter): User
+          count: Int
+        }
+
+        type User {
+          name: String
+        }
+
+        input Filter {
+          active: Boolean
+        }",
+        &plan,
+    );
+
+    assert_eq!(get_messages(&errors).len(), 0);
+}
+
+#[test]
+fn rejects_unknown_types() {
+    use crate::validation::test_utils::*;
+
+    let plan = create_schema_plan_from_rule(Box::new(KnownTypeReferences {}));
+    let errors = test_schema(
+        "
+        type Query implements Node {
+          user(filter: Filter): [User!]
+        }
+
+        union Result = Query | Other
+
+        directive @dir(arg: DirArg) on FIELD",
+        &plan,
+    );
+
+    let messages = get_messages(&errors);
+    assert_eq!(
+        messages,
+        vec![
+            "Unknown type \"Node\".",
+            "Unknown type \"User\".",
+            "Unknown type \"Filter\".",
+            "Unknown type \"Other\".",
+            "Unknown type \"DirArg\"."
+        ]
+    );
+    assert_eq!(
+        errors[1].locations,
+        vec![Pos {
+            line: 3,
+            column: 11
+        }]
+    );
+}
@@ -0,0 +1,29 @@
+pub mod defaults;
+pub mod rule;
+
+pub mod directive_definitions;
+pub mod enum_values;
+pub mod field_definitions;
+pub mod input_object_circular_refs;
+pub mod input_object_fields;
+pub mod interface_implementations;
+pub mod known_type_references;
+pub mod possible_type_extensions;
+pub mod reserved_names;
+pub mod root_operation_types;
+pub mod union_members;
+
+pub use self::defaults::*;
+pub use self::rule::*;
+
+pub use self::directive_definitions::*;
+pub use self::enum_values::*;
+pub use self::field_definitions::*;
+pub use self::input_object_circular_refs::*;
+pub use self::input_object_fields::*;
+pub use self::interface_implementations::*;
+pub use self::known_type_references::*;
+pub use self::possible_type_extensions::*;
+pub use self::reserved_names::*;
+pub use self::root_operation_types::*;
+pub use self::union_members::*;
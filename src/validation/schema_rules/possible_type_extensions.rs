@@ -0,0 +1,180 @@
+use super::{SchemaValidationContext, SchemaValidationRule};
+use crate::ast::{SchemaVisitor, TypeDefinitionExtension, TypeExtensionHelpers};
+use crate::static_graphql::schema::{Definition, TypeDefinition, TypeExtension};
+use crate::validation::utils::{did_you_mean, suggestion_list, ValidationError};
+
+/// Possible type extensions
+///
+/// A type extension is only valid if the type it extends is defined, and is
+/// of the same kind as the extension.
+///
+/// See https://spec.graphql.org/draft/#sec-Object-Extensions.Type-Validation
+pub struct PossibleTypeExtensions;
+
+impl Default for PossibleTypeExtensions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PossibleTypeExtensions {
+    pub fn new() -> Self {
+        PossibleTypeExtensions
+    }
+}
+
+fn is_same_kind(type_def: &TypeDefinition, extension: &TypeExtension) -> bool {
+    matches!(
+        (type_def, extension),
+        (TypeDefinition::Scalar(_), TypeExtension::Scalar(_))
+            | (TypeDefinition::Object(_), TypeExtension::Object(_))
+            | (TypeDefinition::Interface(_), TypeExtension::Interface(_))
+            | (TypeDefinition::Union(_), TypeExtension::Union(_))
+            | (TypeDefinition::Enum(_), TypeExtension::Enum(_))
+            | (
+                TypeDefinition::InputObject(_),
+                TypeExtension::InputObject(_)
+            )
+    )
+}
+
+impl<'a> SchemaVisitor<SchemaValidationContext<'a>> for PossibleTypeExtensions {
+    fn enter_type_extension(&self, node: &TypeExtension, ctx: &mut SchemaValidationContext<'a>) {
+        let type_name = node.name();
+
+        match ctx.type_by_name(type_name) {
+            Some(type_def) if !is_same_kind(type_def, node) => {
+                ctx.report_error(ValidationError {
+                    error_code: self.error_code(),
+                    extensions: None,
+                    locations: vec![type_def.position(), node.position()],
+                    message: format!("Cannot extend non-{} type \"{}\".", node.kind(), type_name),
+                });
+            }
+            Some(_) => {}
+            None => {
+                let type_names =
+                    ctx.schema
+                        .definitions
+                        .iter()
+                        .filter_map(|definition| match definition {
+                            Definition::TypeDefinition(type_def) => Some(type_def.name()),
+                            _ => None,
+                        });
+
+                ctx.report_error(ValidationError {
+                    error_code: self.error_code(),
+                    extensions: None,
+                    locations: vec![node.position()],
+                    message: format!(
+                        "Cannot extend type \"{}\" because it is not defined.{}",
+                        type_name,
+                        did_you_mean(None, &suggestion_list(type_name, type_names))
+                    ),
+                });
+            }
+        }
+    }
+}
+
+impl SchemaValidationRule for PossibleTypeExtensions {
+    fn error_code<'a>(&self) -> &'a str {
+        "PossibleTypeExtensions"
+    }
+
+    fn validate(&self, ctx: &mut SchemaValidationContext) {
+        let schema = ctx.schema;
+        self.visit_schema_document(schema, ctx);
+    }
+}
+
+#[test]
+fn accepts_extensions_of_defined_types() {
+    use crate::validation::test_utils::*;
+
+    let plan = create_schema_plan_from_rule(Box::new(PossibleTypeExtensions {}));
+    let errors = test_schema(
+        "
+        type Query {
+          a: Int
+        }
+
+        extend type Query {
+          b: Int
+        }
+
+        enum Color {
+          RED
+        }
+
+        extend enum Color {
+          GREEN
+        }",
+        &plan,
+    );
+
+    assert_eq!(get_messages(&errors).len(), 0);
+}
+
+#[test]
+fn rejects_extensions_of_unknown_types() {
+    use crate::validation::test_utils::*;
+
+    let plan = create_schema_plan_from_rule(Box::new(PossibleTypeExtensions {}));
+    let errors = test_schema(
+        "
+        type Query {
+          a: Int
+        }
+
+        extend type Querry {
+          b: Int
+        }",
+        &plan,
+    );
+
+    assert_eq!(
+        get_messages(&errors),
+        vec!["Cannot extend type \"Querry\" because it is not defined. Did you mean \"Query\"?"]
+    );
+    assert_eq!(
+        errors[0].locations,
+        vec![Pos {
+            line: 6,
+            column: 16
+        }]
+    );
+}
+
+#[test]
+fn rejects_extensions_of_a_different_kind() {
+    use crate::validation::test_utils::*;
+
+    let plan = create_schema_plan_from_rule(Box::new(PossibleTypeExtensions {}));
+    let errors = test_schema(
+        "
+        type Query {
+          a: Int
+        }
+
+        extend input Query {
+          b: Int
+        }",
+        &plan,
+    );
+
+    assert_eq!(
+        get_messages(&errors),
+        vec!["Cannot extend non-input object type \"Query\"."]
+    );
+    assert_eq!(
+        errors[0].locations,
+        vec![
+            Pos { line: 2, column: 9 },
+            Pos {
+                line: 6,
+                column: 16
+            }
+        ]
+    );
+}
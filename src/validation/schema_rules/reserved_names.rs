@@ -0,0 +1,183 @@
+use super::{SchemaValidationContext, SchemaValidationRule};
+use crate::ast::{SchemaVisitor, TypeDefinitionExtension};
+use crate::introspection::is_introspection_type;
+use crate::parser::Pos;
+use crate::static_graphql::schema::{
+    DirectiveDefinition, EnumType, EnumValue, Field, InputObjectType, InputValue, InterfaceType,
+    ObjectType, TypeDefinition,
+};
+use crate::validation::utils::ValidationError;
+
+/// Reserved names
+///
+/// Names of types, fields, arguments, enum values and directives must not
+/// begin with "__", which is reserved by GraphQL introspection.
+///
+/// See https://spec.graphql.org/draft/#sec-Names.Reserved-Names
+pub struct ReservedNames;
+
+impl Default for ReservedNames {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ReservedNames {
+    pub fn new() -> Self {
+        ReservedNames
+    }
+
+    fn validate_name(&self, ctx: &mut SchemaValidationContext, name: &str, position: Pos) {
+        if name.starts_with("__") {
+            ctx.report_error(ValidationError {
+                error_code: self.error_code(),
+                extensions: None,
+                locations: vec![position],
+                message: format!(
+                    "Name \"{}\" must not begin with \"__\", which is reserved by GraphQL introspection.",
+                    name
+                ),
+            });
+        }
+    }
+
+    fn validate_arguments(&self, ctx: &mut SchemaValidationContext, arguments: &[InputValue]) {
+        for argument in arguments {
+            self.validate_name(ctx, &argument.name, argument.position);
+        }
+    }
+
+    fn validate_field(&self, ctx: &mut SchemaValidationContext, type_name: &str, field: &Field) {
+        if !is_introspection_type(type_name) {
+            self.validate_name(ctx, &field.name, field.position);
+            self.validate_arguments(ctx, &field.arguments);
+        }
+    }
+}
+
+impl<'a> SchemaVisitor<SchemaValidationContext<'a>> for ReservedNames {
+    fn enter_type_definition(&self, node: &TypeDefinition, ctx: &mut SchemaValidationContext<'a>) {
+        if !is_introspection_type(node.name()) {
+            self.validate_name(ctx, node.name(), node.position());
+        }
+    }
+
+    fn enter_object_type_field(
+        &self,
+        node: &Field,
+        type_: &ObjectType,
+        ctx: &mut SchemaValidationContext<'a>,
+    ) {
+        self.validate_field(ctx, &type_.name, node);
+    }
+
+    fn enter_interface_type_field(
+        &self,
+        node: &Field,
+        type_: &InterfaceType,
+        ctx: &mut SchemaValidationContext<'a>,
+    ) {
+        self.validate_field(ctx, &type_.name, node);
+    }
+
+    fn enter_input_object_type_field(
+        &self,
+        node: &InputValue,
+        input_type: &InputObjectType,
+        ctx: &mut SchemaValidationContext<'a>,
+    ) {
+        if !is_introspection_type(&input_type.name) {
+            self.validate_name(ctx, &node.name, node.position);
+        }
+    }
+
+    fn enter_enum_value(
+        &self,
+        node: &EnumValue,
+        enum_: &EnumType,
+        ctx: &mut SchemaValidationContext<'a>,
+    ) {
+        if !is_introspection_type(&enum_.name) {
+            self.validate_name(ctx, &node.name, node.position);
+        }
+    }
+
+    fn enter_directive_definition(
+        &self,
+        node: &DirectiveDefinition,
+        ctx: &mut SchemaValidationContext<'a>,
+    ) {
+        self.validate_name(ctx, &node.name, node.position);
+        self.validate_arguments(ctx, &node.arguments);
+    }
+}
+
+impl SchemaValidationRule for ReservedNames {
+    fn error_code<'a>(&self) -> &'a str {
+        "ReservedNames"
+    }
+
+    fn validate(&self, ctx: &mut SchemaValidationContext) {
+        let schema = ctx.schema;
+        self.visit_schema_document(schema, ctx);
+    }
+}
+
+#[test]
+fn accepts_regular_names_and_introspection_types() {
+    use crate::validation::test_utils::*;
+
+    let plan = create_schema_plan_from_rule(Box::new(ReservedNames {}));
+    let errors = test_schema(
+        "
+        type Query {
+          test(arg: String): String
+        }
+
+        type __Type {
+          name: String
+        }",
+        &plan,
+    );
+
+    assert_eq!(get_messages(&errors).len(), 0);
+}
+
+#[test]
+fn rejects_names_starting_with_double_underscore() {
+    use crate::validation::test_utils::*;
+
+    let plan = create_schema_plan_from_rule(Box::new(ReservedNames {}));
+    let errors = test_schema(
+        "
+        type Query {
+          __test(__arg: String): __Custom
+        }
+
+        enum __Custom {
+          __VALUE
+        }
+
+        directive @__dir on FIELD",
+        &plan,
+    );
+
+    let messages = get_messages(&errors);
+    assert_eq!(
+        messages,
+        vec![
+            "Name \"__test\" must not begin with \"__\", which is reserved by GraphQL introspection.",
+            "Name \"__arg\" must not begin with \"__\", which is reserved by GraphQL introspection.",
+            "Name \"__Custom\" must not begin with \"__\", which is reserved by GraphQL introspection.",
+            "Name \"__VALUE\" must not begin with \"__\", which is reserved by GraphQL introspection.",
+            "Name \"__dir\" must not begin with \"__\", which is reserved by GraphQL introspection."
+        ]
+    );
+    assert_eq!(
+        errors[0].locations,
+        vec![Pos {
+            line: 3,
+            column: 11
+        }]
+    );
+}
@@ -0,0 +1,256 @@
+use super::{SchemaValidationContext, SchemaValidationRule};
+use crate::ast::{SchemaVisitor, TypeDefinitionExtension};
+use crate::parser::Pos;
+use crate::static_graphql::schema::{self, Document};
+use crate::validation::utils::ValidationError;
+
+/// Root operation types
+///
+/// A GraphQL schema must provide a query root operation type, and every
+/// root operation type it provides must be an Object type.
+///
+/// See https://spec.graphql.org/draft/#sec-Root-Operation-Types
+pub struct RootOperationTypes;
+
+impl Default for RootOperationTypes {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RootOperationTypes {
+    pub fn new() -> Self {
+        RootOperationTypes
+    }
+
+    fn validate_root_type(
+        &self,
+        ctx: &mut SchemaValidationContext,
+        operation: &str,
+        type_name: Option<&str>,
+        is_required: bool,
+        position: Option<Pos>,
+    ) {
+        match type_name.map(|name| (name, ctx.type_by_name(name))) {
+            Some((_, Some(type_def))) if type_def.is_object_type() => {}
+            Some((_, Some(type_def))) => ctx.report_error(ValidationError {
+                error_code: self.error_code(),
+                extensions: None,
+                locations: position.into_iter().collect(),
+                message: match is_required {
+                    true => format!(
+                        "{} root type must be Object type, it cannot be {}.",
+                        operation,
+                        type_def.name()
+                    ),
+                    false => format!(
+                        "{} root type must be Object type if provided, it cannot be {}.",
+                        operation,
+                        type_def.name()
+                    ),
+                },
+            }),
+            Some((name, None)) => ctx.report_error(ValidationError {
+                error_code: self.error_code(),
+                extensions: None,
+                locations: position.into_iter().collect(),
+                message: format!("Unknown type \"{}\".", name),
+            }),
+            // Without a schema definition there is no node to point to, same as graphql-js.
+            None if is_required => ctx.report_error(ValidationError {
+                error_code: self.error_code(),
+                extensions: None,
+                locations: position.into_iter().collect(),
+                message: format!("{} root type must be provided.", operation),
+            }),
+            None => {}
+        }
+    }
+}
+
+impl<'a> SchemaVisitor<SchemaValidationContext<'a>> for RootOperationTypes {
+    fn enter_document(&self, document: &Document, ctx: &mut SchemaValidationContext<'a>) {
+        let schema_definition =
+            document
+                .definitions
+                .iter()
+                .find_map(|definition| match definition {
+                    schema::Definition::SchemaDefinition(schema_definition) => {
+                        Some(schema_definition)
+                    }
+                    _ => None,
+                });
+
+        match schema_definition {
+            Some(schema_definition) => {
+                let position = Some(schema_definition.position);
+
+                self.validate_root_type(
+                    ctx,
+                    "Query",
+                    schema_definition.query.as_deref(),
+                    true,
+                    position,
+                );
+                self.validate_root_type(
+                    ctx,
+                    "Mutation",
+                    schema_definition.mutation.as_deref(),
+                    false,
+                    position,
+                );
+                self.validate_root_type(
+                    ctx,
+                    "Subscription",
+                    schema_definition.subscription.as_deref(),
+                    false,
+                    position,
+                );
+            }
+            None => {
+                // Without a schema definition, root types are picked by their conventional names.
+                for (operation, is_required) in [
+                    ("Query", true),
+                    ("Mutation", false),
+                    ("Subscription", false),
+                ] {
+                    let type_def = ctx.type_by_name(operation);
+
+                    self.validate_root_type(
+                        ctx,
+                        operation,
+                        type_def.map(|_| operation),
+                        is_required,
+                        type_def.map(|type_def| type_def.position()),
+                    );
+                }
+            }
+        }
+    }
+}
+
+impl SchemaValidationRule for RootOperationTypes {
+    fn error_code<'a>(&self) -> &'a str {
+        "RootOperationTypes"
+    }
+
+    fn validate(&self, ctx: &mut SchemaValidationContext) {
+        let schema = ctx.schema;
+        self.visit_schema_document(schema, ctx);
+    }
+}
+
+#[test]
+fn accepts_schema_with_query_type() {
+    use crate::validation::test_utils::*;
+
+    let plan = create_schema_plan_from_rule(Box::new(RootOperationTypes {}));
+    let errors = test_schema(
+        "
+        type Query {
+          test: String
+        }",
+        &plan,
+    );
+
+    assert_eq!(get_messages(&errors).len(), 0);
+}
+
+#[test]
+fn accepts_schema_definition_with_custom_root_types() {
+    use crate::validation::test_utils::*;
+
+    let plan = create_schema_plan_from_rule(Box::new(RootOperationTypes {}));
+    let errors = test_schema(
+        "
+        schema {
+          query: QueryRoot
+          mutation: MutationRoot
+        }
+
+        type QueryRoot {
+          test: String
+        }
+
+        type MutationRoot {
+          test: String
+        }",
+        &plan,
+    );
+
+    assert_eq!(get_messages(&errors).len(), 0);
+}
+
+#[test]
+fn rejects_schema_without_query_type() {
+    use crate::validation::test_utils::*;
+
+    let plan = create_schema_plan_from_rule(Box::new(RootOperationTypes {}));
+    let errors = test_schema(
+        "
+        type Mutation {
+          test: String
+        }",
+        &plan,
+    );
+
+    let messages = get_messages(&errors);
+    assert_eq!(messages, vec!["Query root type must be provided."]);
+}
+
+#[test]
+fn rejects_non_object_root_types() {
+    use crate::validation::test_utils::*;
+
+    let plan = create_schema_plan_from_rule(Box::new(RootOperationTypes {}));
+    let errors = test_schema(
+        "
+        schema {
+          query: SomeInput
+          subscription: SomeUnion
+        }
+
+        input SomeInput {
+          test: String
+        }
+
+        type SomeObject {
+          test: String
+        }
+
+        union SomeUnion = SomeObject",
+        &plan,
+    );
+
+    let messages = get_messages(&errors);
+    assert_eq!(
+        messages,
+        vec![
+            "Query root type must be Object type, it cannot be SomeInput.",
+            "Subscription root type must be Object type if provided, it cannot be SomeUnion."
+        ]
+    );
+    assert_eq!(errors[0].locations, vec![Pos { line: 2, column: 9 }]);
+}
+
+#[test]
+fn rejects_unknown_root_types() {
+    use crate::validation::test_utils::*;
+
+    let plan = create_schema_plan_from_rule(Box::new(RootOperationTypes {}));
+    let errors = test_schema(
+        "
+        schema {
+          query: Query
+          mutation: Mutation
+        }
+
+        type Query {
+          test: String
+        }",
+        &plan,
+    );
+
+    let messages = get_messages(&errors);
+    assert_eq!(messages, vec!["Unknown type \"Mutation\"."]);
+}
@@ -0,0 +1,51 @@
+use crate::ast::Schema;
+use crate::introspection::is_built_in_scalar;
+use crate::static_graphql::schema::{self, TypeDefinition};
+use crate::validation::utils::{ValidationError, ValidationErrorContext};
+
+/// SchemaValidationContext
+pub struct SchemaValidationContext<'a> {
+    pub schema: &'a schema::Document,
+    pub error_collector: ValidationErrorContext,
+    indexed_schema: Schema<'a>,
+}
+
+impl<'a> SchemaValidationContext<'a> {
+    pub fn new(schema: &'a schema::Document) -> Self {
+        SchemaValidationContext {
+            schema,
+            error_collector: ValidationErrorContext::new(),
+            indexed_schema: Schema::new(schema),
+        }
+    }
+
+    /// The lookup tables of `schema`, built once for all the rules.
+    pub fn indexed_schema(&self) -> &Schema<'a> {
+        &self.indexed_schema
+    }
+
+    pub fn report_error(&mut self, error: ValidationError) {
+        self.error_collector.report_error(error);
+    }
+
+    pub fn type_by_name(&self, name: &str) -> Option<&'a TypeDefinition> {
+        self.indexed_schema.type_by_name(name)
+    }
+
+    /// Returns `true` when `name` refers to a type defined in the document, or to a built-in scalar.
+    pub fn is_known_type(&self, name: &str) -> bool {
+        is_built_in_scalar(name) || self.type_by_name(name).is_some()
+    }
+}
+
+pub trait SchemaValidationRule: Send + Sync {
+    fn validate(&self, _ctx: &mut SchemaValidationContext<'_>);
+
+    fn error_code<'a>(&self) -> &'a str;
+}
+
+pub(crate) fn is_deprecated(directives: &[schema::Directive]) -> bool {
+    directives
+        .iter()
+        .any(|directive| directive.name == "deprecated")
+}
@@ -0,0 +1,142 @@
+use std::collections::HashSet;
+
+use super::{SchemaValidationContext, SchemaValidationRule};
+use crate::ast::{SchemaVisitor, TypeDefinitionExtension};
+use crate::static_graphql::schema::UnionType;
+use crate::validation::utils::ValidationError;
+
+/// Union members
+///
+/// A Union type must include one or more unique member types, and all of
+/// them must be Object types.
+///
+/// See https://spec.graphql.org/draft/#sec-Unions.Type-Validation
+pub struct UnionMembers;
+
+impl Default for UnionMembers {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl UnionMembers {
+    pub fn new() -> Self {
+        UnionMembers
+    }
+}
+
+impl<'a> SchemaVisitor<SchemaValidationContext<'a>> for UnionMembers {
+    fn enter_union_type(&self, node: &UnionType, ctx: &mut SchemaValidationContext<'a>) {
+        if node.types.is_empty() {
+            ctx.report_error(ValidationError {
+                error_code: self.error_code(),
+                extensions: None,
+                locations: vec![node.position],
+                message: format!(
+                    "Union type {} must define one or more member types.",
+                    node.name
+                ),
+            });
+        }
+
+        let mut included_types = HashSet::new();
+
+        for member in &node.types {
+            if !included_types.insert(member) {
+                ctx.report_error(ValidationError {
+                    error_code: self.error_code(),
+                    extensions: None,
+                    locations: vec![node.position],
+                    message: format!(
+                        "Union type {} can only include type {} once.",
+                        node.name, member
+                    ),
+                });
+                continue;
+            }
+
+            if let Some(member_type) = ctx.type_by_name(member) {
+                if !member_type.is_object_type() {
+                    ctx.report_error(ValidationError {
+                        error_code: self.error_code(),
+                        extensions: None,
+                        locations: vec![node.position],
+                        message: format!(
+                            "Union type {} can only include Object types, it cannot include {}.",
+                            node.name, member
+                        ),
+                    });
+                }
+            }
+        }
+    }
+}
+
+impl SchemaValidationRule for UnionMembers {
+    fn error_code<'a>(&self) -> &'a str {
+        "UnionMembers"
+    }
+
+    fn validate(&self, ctx: &mut SchemaValidationContext) {
+        let schema = ctx.schema;
+        self.visit_schema_document(schema, ctx);
+    }
+}
+
+#[test]
+fn accepts_object_members() {
+    use crate::validation::test_utils::*;
+
+    let plan = create_schema_plan_from_rule(Box::new(UnionMembers {}));
+    let errors = test_schema(
+        "
+        type A {
+          id: ID
+        }
+
+        type B {
+          id: ID
+        }
+
+        union AOrB = A | B",
+        &plan,
+    );
+
+    assert_eq!(get_messages(&errors).len(), 0);
+}
+
+#[test]
+fn rejects_invalid_members() {
+    use crate::validation::test_utils::*;
+
+    let plan = create_schema_plan_from_rule(Box::new(UnionMembers {}));
+    let errors = test_schema(
+        "
+        type A {
+          id: ID
+        }
+
+        input B {
+          id: ID
+        }
+
+        union AOrB = A | B | A | String",
+        &plan,
+    );
+
+    let messages = get_messages(&errors);
+    assert_eq!(
+        messages,
+        vec![
+            "Union type AOrB can only include Object types, it cannot include B.",
+            "Union type AOrB can only include type A once."
+        ]
+    );
+    assert_eq!(
+        errors[0].locations,
+        vec![crate::parser::Pos {
+            line: 10,
+            column: 9
+        }]
+    );
+}
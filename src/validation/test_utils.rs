@@ -1,7 +1,10 @@
 use super::rules::ValidationRule;
+use super::schema_rules::SchemaValidationRule;
 use super::utils::ValidationError;
-use super::validate::validate;
+use super::validate::validate_with_source;
 use super::validate::ValidationPlan;
+use super::validate_schema::{validate_schema, SchemaValidationPlan};
+pub use crate::parser::Pos;
 
 #[cfg(test)]
 pub static INTROSPECTION_SCHEMA: &str = "
@@ -230,10 +233,12 @@ directive @repeatable repeatable on FIELD | FRAGMENT_DEFINITION
 
 #[cfg(test)]
 pub fn create_plan_from_rule(rule: Box<dyn ValidationRule>) -> ValidationPlan {
-    let mut rules = Vec::new();
-    rules.push(rule);
+    ValidationPlan::from(vec![rule])
+}
 
-    ValidationPlan { rules }
+#[cfg(test)]
+pub fn create_schema_plan_from_rule(rule: Box<dyn SchemaValidationRule>) -> SchemaValidationPlan {
+    SchemaValidationPlan::from(vec![rule])
 }
 
 #[cfg(test)]
@@ -260,7 +265,7 @@ type Query {
 
     let operation_ast = crate::parser::parse_query(operation).unwrap().into_static();
 
-    validate(&schema_ast, &operation_ast, plan)
+    validate_with_source(&schema_ast, &operation_ast, operation, plan)
 }
 
 #[cfg(test)]
@@ -272,12 +277,21 @@ fn string_to_static_str(s: String) -> &'static str {
 pub fn test_operation_with_schema<'a>(
     operation: &'a str,
     schema: &'a str,
-    plan: &'a ValidationPlan,
+    plan: &'a mut ValidationPlan,
 ) -> Vec<ValidationError> {
     let schema_clone = string_to_static_str(schema.to_string() + INTROSPECTION_SCHEMA);
     let schema_ast = crate::parser::parse_schema(schema_clone).expect("Failed to parse schema");
 
     let operation_ast = crate::parser::parse_query(operation).unwrap().into_static();
 
-    validate(&schema_ast, &operation_ast, plan)
+    validate_with_source(&schema_ast, &operation_ast, operation, plan)
+}
+
+#[cfg(test)]
+pub fn test_schema(schema: &str, plan: &SchemaValidationPlan) -> Vec<ValidationError> {
+    let schema_ast = crate::parser::parse_schema::<String>(schema)
+        .expect("Failed to parse schema")
+        .into_static();
+
+    validate_schema(&schema_ast, plan)
 }
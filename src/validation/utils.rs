@@ -2,6 +2,7 @@ use crate::parser::Pos;
 use serde::ser::*;
 use serde::{Serialize, Serializer};
 use serde_with::{serde_as, SerializeAs};
+use std::cmp::Ordering;
 use std::fmt::Debug;
 
 #[derive(Debug, Default)]
@@ -19,7 +20,7 @@ impl ValidationErrorContext {
     }
 }
 
-struct PositionDef;
+pub(crate) struct PositionDef;
 
 impl SerializeAs<Pos> for PositionDef {
     fn serialize_as<S>(value: &Pos, serializer: S) -> Result<S::Ok, S::Error>
@@ -33,6 +34,8 @@ impl SerializeAs<Pos> for PositionDef {
     }
 }
 
+pub type ValidationErrorExtensions = serde_json::Map<String, serde_json::Value>;
+
 #[serde_as]
 #[derive(Serialize, Debug, Clone)]
 pub struct ValidationError {
@@ -41,6 +44,268 @@ pub struct ValidationError {
     pub message: String,
     #[serde(skip_serializing)]
     pub error_code: &'static str,
+    /// Serialized as the `extensions` entry of a GraphQL error. Rules usually leave it empty,
+    /// it's filled by `validate` (and `validate_schema`) based on the validation plan
+    /// (`{"code": "<error_code>"}` by default).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub extensions: Option<ValidationErrorExtensions>,
+}
+
+impl ValidationError {
+    /// Sets an extension entry, unless the error already has a value for `key`.
+    pub fn with_extension(mut self, key: &str, value: serde_json::Value) -> Self {
+        self.extensions
+            .get_or_insert_with(ValidationErrorExtensions::new)
+            .entry(key)
+            .or_insert(value);
+
+        self
+    }
+
+    /// Sets `extensions.code` to the error code of the rule that reported this error.
+    pub fn with_code_extension(self) -> Self {
+        let code = self.error_code.into();
+
+        self.with_extension("code", code)
+    }
+
+    /// Applies the error extensions configured on a validation plan.
+    pub(crate) fn with_plan_extensions(
+        mut self,
+        code_extension: bool,
+        extensions: &ValidationErrorExtensions,
+    ) -> Self {
+        if code_extension {
+            self = self.with_code_extension();
+        }
+
+        for (key, value) in extensions {
+            self = self.with_extension(key, value.clone());
+        }
+
+        self
+    }
+}
+
+const MAX_SUGGESTIONS: usize = 5;
+
+/// Formats a list of suggestions the same way graphql-js `didYouMean` does, for example
+/// ` Did you mean "a", "b", or "c"?`. Returns an empty string when there are no suggestions.
+///
+/// An optional `sub_message` is inserted before the suggestions
+/// (` Did you mean the enum value "A"?`).
+pub fn did_you_mean<S: AsRef<str>>(sub_message: Option<&str>, suggestions: &[S]) -> String {
+    let mut message = String::from(" Did you mean ");
+
+    if let Some(sub_message) = sub_message {
+        message.push_str(sub_message);
+        message.push(' ');
+    }
+
+    let suggestions: Vec<String> = suggestions
+        .iter()
+        .take(MAX_SUGGESTIONS)
+        .map(|s| format!("\"{}\"", s.as_ref()))
+        .collect();
+
+    match suggestions.as_slice() {
+        [] => return String::new(),
+        [only] => message.push_str(only),
+        [first, second] => {
+            message.push_str(first);
+            message.push_str(" or ");
+            message.push_str(second);
+        }
+        [rest @ .., last] => {
+            message.push_str(&rest.join(", "));
+            message.push_str(", or ");
+            message.push_str(last);
+        }
+    }
+
+    message.push('?');
+    message
+}
+
+/// Given an invalid input string and a list of valid options, returns a filtered list of
+/// valid options sorted based on their similarity with the input (see graphql-js `suggestionList`).
+pub fn suggestion_list<'a, I>(input: &str, options: I) -> Vec<&'a str>
+where
+    I: IntoIterator<Item = &'a str>,
+{
+    let lexical_distance = LexicalDistance::new(input);
+    let threshold = input.chars().count() * 4 / 10 + 1;
+
+    let mut options_by_distance: Vec<(&str, usize)> = Vec::new();
+
+    for option in options {
+        if options_by_distance
+            .iter()
+            .any(|(known, _)| *known == option)
+        {
+            continue;
+        }
+
+        if let Some(distance) = lexical_distance.measure(option, threshold) {
+            options_by_distance.push((option, distance));
+        }
+    }
+
+    options_by_distance.sort_by(|(a, a_distance), (b, b_distance)| {
+        a_distance
+            .cmp(b_distance)
+            .then_with(|| natural_compare(a, b))
+    });
+
+    options_by_distance
+        .into_iter()
+        .map(|(option, _)| option)
+        .collect()
+}
+
+/// Computes the lexical distance between strings A and B.
+///
+/// The "distance" between two strings is given by counting the minimum number
+/// of edits needed to transform string A into string B. An edit can be an
+/// insertion, deletion, or substitution of a single character, or a swap of two
+/// adjacent characters. Any case change counts as a single edit.
+///
+/// This is the Damerau-Levenshtein "optimal string alignment" distance, as
+/// implemented by graphql-js.
+struct LexicalDistance<'a> {
+    input: &'a str,
+    input_lower_case: String,
+    input_chars: Vec<char>,
+}
+
+impl<'a> LexicalDistance<'a> {
+    fn new(input: &'a str) -> Self {
+        let input_lower_case = input.to_lowercase();
+        let input_chars = input_lower_case.chars().collect();
+
+        LexicalDistance {
+            input,
+            input_lower_case,
+            input_chars,
+        }
+    }
+
+    fn measure(&self, option: &str, threshold: usize) -> Option<usize> {
+        if self.input == option {
+            return Some(0);
+        }
+
+        let option_lower_case = option.to_lowercase();
+
+        // Any case change counts as a single edit
+        if self.input_lower_case == option_lower_case {
+            return Some(1);
+        }
+
+        let option_chars: Vec<char> = option_lower_case.chars().collect();
+        let (a, b) = if option_chars.len() < self.input_chars.len() {
+            (&self.input_chars, &option_chars)
+        } else {
+            (&option_chars, &self.input_chars)
+        };
+
+        if a.len() - b.len() > threshold {
+            return None;
+        }
+
+        let mut rows = vec![vec![0; b.len() + 1]; 3];
+
+        for (j, cell) in rows[0].iter_mut().enumerate() {
+            *cell = j;
+        }
+
+        for i in 1..=a.len() {
+            let mut smallest_cell = i;
+            rows[i % 3][0] = i;
+
+            for j in 1..=b.len() {
+                let cost = usize::from(a[i - 1] != b[j - 1]);
+                let up_row = &rows[(i - 1) % 3];
+
+                let mut current_cell = (up_row[j] + 1)
+                    .min(rows[i % 3][j - 1] + 1)
+                    .min(up_row[j - 1] + cost);
+
+                if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                    // transposition
+                    current_cell = current_cell.min(rows[(i - 2) % 3][j - 2] + 1);
+                }
+
+                smallest_cell = smallest_cell.min(current_cell);
+                rows[i % 3][j] = current_cell;
+            }
+
+            // Early exit, since distance can't go smaller than smallest element of the previous row.
+            if smallest_cell > threshold {
+                return None;
+            }
+        }
+
+        let distance = rows[a.len() % 3][b.len()];
+
+        if distance <= threshold {
+            Some(distance)
+        } else {
+            None
+        }
+    }
+}
+
+/// Compares two strings, treating sequences of digits as numbers ("a2" < "a10").
+/// Matches graphql-js `naturalCompare`.
+pub fn natural_compare(a: &str, b: &str) -> Ordering {
+    let a = a.as_bytes();
+    let b = b.as_bytes();
+    let mut a_index = 0;
+    let mut b_index = 0;
+
+    while a_index < a.len() && b_index < b.len() {
+        let a_char = a[a_index];
+        let b_char = b[b_index];
+
+        if a_char.is_ascii_digit() && b_char.is_ascii_digit() {
+            let (a_num, a_next) = read_number(a, a_index);
+            let (b_num, b_next) = read_number(b, b_index);
+            a_index = a_next;
+            b_index = b_next;
+
+            match a_num.cmp(&b_num) {
+                Ordering::Equal => {}
+                ordering => return ordering,
+            }
+        } else {
+            match a_char.cmp(&b_char) {
+                Ordering::Equal => {}
+                ordering => return ordering,
+            }
+
+            a_index += 1;
+            b_index += 1;
+        }
+    }
+
+    a.len().cmp(&b.len())
+}
+
+/// Reads a number starting at `index`. A leading zero is read as a number on its own.
+fn read_number(bytes: &[u8], mut index: usize) -> (u64, usize) {
+    let mut num: u64 = 0;
+
+    loop {
+        num = num
+            .saturating_mul(10)
+            .saturating_add(u64::from(bytes[index] - b'0'));
+        index += 1;
+
+        if num == 0 || index >= bytes.len() || !bytes[index].is_ascii_digit() {
+            return (num, index);
+        }
+    }
 }
 
 #[test]
@@ -49,6 +314,7 @@ fn serialization_test() {
         locations: vec![Pos { line: 1, column: 2 }],
         message: "test".to_string(),
         error_code: "test",
+        extensions: None,
     };
     let serialized = serde_json::to_string(&error).unwrap();
     assert_eq!(
@@ -63,6 +329,7 @@ fn serialization_test_vec() {
         locations: vec![Pos { line: 1, column: 2 }],
         message: "test".to_string(),
         error_code: "test",
+        extensions: None,
     };
     let serialized = serde_json::to_string(&vec![error]).unwrap();
     assert_eq!(
@@ -70,3 +337,88 @@ fn serialization_test_vec() {
         r#"[{"locations":[{"line":1,"column":2}],"message":"test"}]"#
     );
 }
+
+#[test]
+fn did_you_mean_formatting() {
+    let empty: [&str; 0] = [];
+    assert_eq!(did_you_mean(None, &empty), "");
+    assert_eq!(did_you_mean(None, &["A"]), " Did you mean \"A\"?");
+    assert_eq!(
+        did_you_mean(None, &["A", "B"]),
+        " Did you mean \"A\" or \"B\"?"
+    );
+    assert_eq!(
+        did_you_mean(None, &["A", "B", "C"]),
+        " Did you mean \"A\", \"B\", or \"C\"?"
+    );
+    assert_eq!(
+        did_you_mean(None, &["A", "B", "C", "D", "E", "F"]),
+        " Did you mean \"A\", \"B\", \"C\", \"D\", or \"E\"?"
+    );
+    assert_eq!(
+        did_you_mean(Some("the enum value"), &["A"]),
+        " Did you mean the enum value \"A\"?"
+    );
+}
+
+#[test]
+fn suggestion_list_results() {
+    assert_eq!(suggestion_list("", vec!["a"]), vec!["a"]);
+    assert_eq!(suggestion_list("a", vec!["a"]), vec!["a"]);
+    assert_eq!(suggestion_list("abc", vec!["ABC"]), vec!["ABC"]);
+    assert_eq!(suggestion_list("abc", vec!["acb"]), vec!["acb"]);
+    assert_eq!(
+        suggestion_list(
+            "GraphQl",
+            vec!["graphics", "SQL", "GraphQL", "quarks", "mark"]
+        ),
+        vec!["GraphQL", "graphics"]
+    );
+    assert_eq!(
+        suggestion_list("aaaa", vec!["aaaa", "aaaa", "aaaa"]),
+        vec!["aaaa"]
+    );
+}
+
+#[test]
+fn suggestion_list_threshold() {
+    assert_eq!(suggestion_list("aaaa", vec!["aaab"]), vec!["aaab"]);
+    assert_eq!(suggestion_list("aaaa", vec!["aabb"]), vec!["aabb"]);
+    assert_eq!(suggestion_list("aaaa", vec!["abbb"]), Vec::<&str>::new());
+    assert_eq!(suggestion_list("ab", vec!["ca"]), Vec::<&str>::new());
+}
+
+#[test]
+fn suggestion_list_sorting() {
+    assert_eq!(
+        suggestion_list("abc", vec!["a", "ab", "abc"]),
+        vec!["abc", "ab", "a"]
+    );
+    assert_eq!(
+        suggestion_list("boo", vec!["moo", "foo", "zoo"]),
+        vec!["foo", "moo", "zoo"]
+    );
+    assert_eq!(
+        suggestion_list("abc", vec!["a1", "a12", "a2"]),
+        vec!["a1", "a2", "a12"]
+    );
+}
+
+#[test]
+fn serialization_test_extensions() {
+    let error = ValidationError {
+        locations: vec![Pos { line: 1, column: 2 }],
+        message: "test".to_string(),
+        error_code: "TestRule",
+        extensions: None,
+    }
+    .with_code_extension()
+    .with_extension("code", serde_json::json!("Other"))
+    .with_extension("http", serde_json::json!({ "status": 400 }));
+
+    let serialized = serde_json::to_string(&error).unwrap();
+    assert_eq!(
+        serialized,
+        r#"{"locations":[{"line":1,"column":2}],"message":"test","extensions":{"code":"TestRule","http":{"status":400}}}"#
+    );
+}
@@ -1,29 +1,49 @@
 use super::{
     rules::ValidationRule,
-    utils::{ValidationError, ValidationErrorContext},
+    utils::{ValidationError, ValidationErrorContext, ValidationErrorExtensions},
 };
 
 use crate::{
-    ast::OperationVisitorContext,
-    static_graphql::{query, schema},
+    ast::{visit_document, IntoSchema, OperationVisitorContext, ParallelVisitorWithContexts},
+    static_graphql::query,
 };
 
 pub struct ValidationPlan {
     pub rules: Vec<Box<dyn ValidationRule>>,
+    /// Adds `extensions.code` (the error code of the rule) to every reported error. Enabled by default.
+    pub error_code_extension: bool,
+    /// Extensions added to every reported error, for example `{"http": {"status": 400}}`.
+    pub error_extensions: ValidationErrorExtensions,
 }
 
 impl ValidationPlan {
     pub fn new() -> Self {
-        Self { rules: vec![] }
+        Self::from(vec![])
     }
 
     pub fn from(rules: Vec<Box<dyn ValidationRule>>) -> Self {
-        Self { rules }
+        Self {
+            rules,
+            error_code_extension: true,
+            error_extensions: ValidationErrorExtensions::new(),
+        }
     }
 
     pub fn add_rule(&mut self, rule: Box<dyn ValidationRule>) {
         self.rules.push(rule);
     }
+
+    pub fn set_error_code_extension(&mut self, enabled: bool) {
+        self.error_code_extension = enabled;
+    }
+
+    pub fn add_error_extension(&mut self, key: &str, value: serde_json::Value) {
+        self.error_extensions.insert(key.to_string(), value);
+    }
+
+    fn apply_error_extensions(&self, error: ValidationError) -> ValidationError {
+        error.with_plan_extensions(self.error_code_extension, &self.error_extensions)
+    }
 }
 
 impl Default for ValidationPlan {
@@ -32,20 +52,91 @@ impl Default for ValidationPlan {
     }
 }
 
+/// Validates `operation` against `schema`, which is either a schema `Document` or a prebuilt
+/// `Schema`. When validating many operations against the same schema, build the `Schema` once.
+///
+/// Errors are returned in the order of the rules of `validation_plan`, and in document order
+/// for each rule.
+///
+/// Without the source of the operation, `UniqueInputFieldNames` can't find duplicated input
+/// object fields: it reports an error for operations with input object values, validate them
+/// with `validate_with_source`.
 pub fn validate<'a>(
-    schema: &'a schema::Document,
+    schema: impl IntoSchema<'a>,
     operation: &'a query::Document,
     validation_plan: &'a ValidationPlan,
 ) -> Vec<ValidationError> {
-    let mut error_collector = ValidationErrorContext::new();
     let mut validation_context = OperationVisitorContext::new(operation, schema);
 
-    validation_plan
-        .rules
-        .iter()
-        .for_each(|rule| rule.validate(&mut validation_context, &mut error_collector));
+    run_validation_plan(&mut validation_context, validation_plan)
+}
+
+/// Same as `validate`, but also receives the raw source `operation` was parsed from.
+///
+/// The parser drops the positions of arguments and values, and collapses duplicated input
+/// object fields: with the source, errors get the exact locations of arguments and values, and
+/// `UniqueInputFieldNames` reports duplicated fields.
+pub fn validate_with_source<'a>(
+    schema: impl IntoSchema<'a>,
+    operation: &'a query::Document,
+    source: &'a str,
+    validation_plan: &'a ValidationPlan,
+) -> Vec<ValidationError> {
+    let mut validation_context =
+        OperationVisitorContext::new_with_source(operation, schema, source);
 
-    error_collector.errors
+    run_validation_plan(&mut validation_context, validation_plan)
+}
+
+fn run_validation_plan<'a>(
+    validation_context: &mut OperationVisitorContext<'a>,
+    validation_plan: &'a ValidationPlan,
+) -> Vec<ValidationError> {
+    // The visitors of all rules run together. Each rule collects its errors on its own, so they
+    // can be returned in the order of the plan.
+    let mut errors_by_rule: Vec<Vec<ValidationError>> = vec![vec![]; validation_plan.rules.len()];
+
+    let mut parallel_visitor = ParallelVisitorWithContexts::new();
+    let mut visitor_rule_indexes = vec![];
+    let mut standalone_rules = vec![];
+
+    for (index, rule) in validation_plan.rules.iter().enumerate() {
+        match rule.visitor() {
+            Some(visitor) => {
+                parallel_visitor.add_visitor(visitor, ValidationErrorContext::new());
+                visitor_rule_indexes.push(index);
+            }
+            None => standalone_rules.push((index, rule)),
+        }
+    }
+
+    if !parallel_visitor.is_empty() {
+        visit_document(
+            &mut parallel_visitor,
+            validation_context.operation,
+            validation_context,
+            &mut (),
+        );
+    }
+
+    for (index, error_collector) in visitor_rule_indexes
+        .into_iter()
+        .zip(parallel_visitor.into_contexts())
+    {
+        errors_by_rule[index] = error_collector.errors;
+    }
+
+    for (index, rule) in standalone_rules {
+        let mut error_collector = ValidationErrorContext::new();
+        rule.validate(validation_context, &mut error_collector);
+        errors_by_rule[index] = error_collector.errors;
+    }
+
+    errors_by_rule
+        .into_iter()
+        .flatten()
+        .map(|error| validation_plan.apply_error_extensions(error))
+        .collect()
 }
 
 #[test]
@@ -169,3 +260,247 @@ fn fragment_loop_through_multiple_frags() {
     ]
     )
 }
+
+#[test]
+fn errors_have_code_extension_by_default() {
+    use crate::validation::rules::KnownFragmentNames;
+    use crate::validation::test_utils::*;
+
+    let mut plan = create_plan_from_rule(Box::new(KnownFragmentNames {}));
+    let errors = test_operation_with_schema("{ dog { ...Unknown } }", TEST_SCHEMA, &mut plan);
+
+    assert_eq!(errors.len(), 1);
+    assert_eq!(
+        serde_json::to_value(&errors[0]).unwrap(),
+        serde_json::json!({
+            "message": "Unknown fragment \"Unknown\".",
+            "locations": [{ "line": 1, "column": 12 }],
+            "extensions": { "code": "KnownFragmentNames" }
+        })
+    );
+}
+
+#[test]
+fn error_extensions_are_configurable() {
+    use crate::validation::rules::KnownFragmentNames;
+    use crate::validation::test_utils::*;
+
+    let mut plan = create_plan_from_rule(Box::new(KnownFragmentNames {}));
+    plan.set_error_code_extension(false);
+    let errors = test_operation_with_schema("{ dog { ...Unknown } }", TEST_SCHEMA, &mut plan);
+
+    assert_eq!(errors[0].extensions, None);
+
+    plan.set_error_code_extension(true);
+    plan.add_error_extension("http", serde_json::json!({ "status": 400 }));
+    let errors = test_operation_with_schema("{ dog { ...Unknown } }", TEST_SCHEMA, &mut plan);
+
+    assert_eq!(
+        serde_json::to_value(&errors[0].extensions).unwrap(),
+        serde_json::json!({ "code": "KnownFragmentNames", "http": { "status": 400 } })
+    );
+}
+
+#[test]
+fn prebuilt_schema_is_reusable_across_validations() {
+    use crate::ast::Schema;
+    use crate::validation::rules::default_rules_validation_plan;
+    use crate::validation::test_utils::*;
+
+    let schema_document = crate::parser::parse_schema::<String>(TEST_SCHEMA)
+        .expect("Failed to parse schema")
+        .into_static();
+    let schema = Schema::new(&schema_document);
+    let plan = default_rules_validation_plan();
+
+    let valid_source = "{ dog { name } }";
+    let valid = crate::parser::parse_query::<String>(valid_source)
+        .unwrap()
+        .into_static();
+    let invalid_source = "{ dog { unknown } }";
+    let invalid = crate::parser::parse_query::<String>(invalid_source)
+        .unwrap()
+        .into_static();
+
+    assert_eq!(
+        validate_with_source(&schema, &valid, valid_source, &plan).len(),
+        0
+    );
+    assert_eq!(
+        get_messages(&validate_with_source(
+            &schema,
+            &invalid,
+            invalid_source,
+            &plan
+        )),
+        vec!["Cannot query field \"unknown\" on type \"Dog\"."]
+    );
+    assert_eq!(
+        get_messages(&validate_with_source(
+            &schema,
+            &invalid,
+            invalid_source,
+            &plan
+        )),
+        get_messages(&validate_with_source(
+            &schema_document,
+            &invalid,
+            invalid_source,
+            &plan
+        ))
+    );
+}
+
+#[test]
+fn rules_without_visitor_run_on_their_own() {
+    use crate::validation::rules::KnownTypeNames;
+    use crate::validation::test_utils::*;
+
+    struct OperationCount;
+
+    impl ValidationRule for OperationCount {
+        fn validate(
+            &self,
+            ctx: &mut OperationVisitorContext<'_>,
+            error_collector: &mut ValidationErrorContext,
+        ) {
+            error_collector.report_error(ValidationError {
+                error_code: self.error_code(),
+                extensions: None,
+                locations: vec![],
+                message: format!("{} definitions.", ctx.operation.definitions.len()),
+            });
+        }
+
+        fn error_code<'a>(&self) -> &'a str {
+            "OperationCount"
+        }
+    }
+
+    let mut plan = ValidationPlan::from(vec![Box::new(OperationCount), Box::new(KnownTypeNames)]);
+    let errors = test_operation_with_schema(
+        "query { dog { ... on Unknown { name } } }",
+        TEST_SCHEMA,
+        &mut plan,
+    );
+
+    assert_eq!(
+        get_messages(&errors),
+        vec!["1 definitions.", "Unknown type \"Unknown\"."]
+    );
+}
+
+#[test]
+fn errors_follow_the_order_of_the_plan() {
+    use crate::validation::rules::{FieldsOnCorrectType, KnownTypeNames};
+    use crate::validation::test_utils::*;
+
+    let operation = "
+        {
+          unknownA
+          dog { ... on UnknownType { unknownB } }
+          ... on OtherUnknownType { name }
+        }";
+
+    let mut plan = ValidationPlan::from(vec![
+        Box::new(KnownTypeNames),
+        Box::new(FieldsOnCorrectType),
+    ]);
+    let errors = test_operation_with_schema(operation, TEST_SCHEMA, &mut plan);
+
+    assert_eq!(
+        get_messages(&errors),
+        vec![
+            "Unknown type \"UnknownType\".",
+            "Unknown type \"OtherUnknownType\".",
+            "Cannot query field \"unknownA\" on type \"QueryRoot\".",
+        ]
+    );
+
+    let mut plan = ValidationPlan::from(vec![
+        Box::new(FieldsOnCorrectType),
+        Box::new(KnownTypeNames),
+    ]);
+    let errors = test_operation_with_schema(operation, TEST_SCHEMA, &mut plan);
+
+    assert_eq!(
+        get_messages(&errors),
+        vec![
+            "Cannot query field \"unknownA\" on type \"QueryRoot\".",
+            "Unknown type \"UnknownType\".",
+            "Unknown type \"OtherUnknownType\".",
+        ]
+    );
+}
+
+#[test]
+fn errors_of_rules_sharing_or_missing_error_codes_follow_the_order_of_the_plan() {
+    use crate::validation::rules::KnownTypeNames;
+    use crate::validation::test_utils::*;
+
+    struct ReportOnce(&'static str, &'static str);
+
+    impl ValidationRule for ReportOnce {
+        fn validate(
+            &self,
+            _: &mut OperationVisitorContext<'_>,
+            error_collector: &mut ValidationErrorContext,
+        ) {
+            error_collector.report_error(ValidationError {
+                error_code: self.0,
+                extensions: None,
+                locations: vec![],
+                message: self.1.to_string(),
+            });
+        }
+
+        fn error_code<'a>(&self) -> &'a str {
+            self.0
+        }
+    }
+
+    let mut plan = ValidationPlan::from(vec![
+        Box::new(ReportOnce("KnownTypeNames", "first")),
+        Box::new(KnownTypeNames),
+        Box::new(ReportOnce("KnownTypeNames", "third")),
+        Box::new(ReportOnce("CustomRule", "fourth")),
+    ]);
+    let errors = test_operation_with_schema(
+        "{ dog { ... on UnknownType { name } } }",
+        TEST_SCHEMA,
+        &mut plan,
+    );
+
+    assert_eq!(
+        get_messages(&errors),
+        vec!["first", "Unknown type \"UnknownType\".", "third", "fourth"]
+    );
+}
+
+#[test]
+fn values_are_located_at_their_field_without_source() {
+    use crate::validation::rules::ValuesOfCorrectType;
+    use crate::validation::test_utils::*;
+
+    let schema =
+        crate::parser::parse_schema::<String>(&(TEST_SCHEMA.to_string() + INTROSPECTION_SCHEMA))
+            .expect("Failed to parse schema")
+            .into_static();
+    let source = "{\n  complicatedArgs {\n    intArgField(intArg: \"3\")\n  }\n}";
+    let operation = crate::parser::parse_query::<String>(source)
+        .unwrap()
+        .into_static();
+    let plan = create_plan_from_rule(Box::new(ValuesOfCorrectType::new()));
+
+    assert_eq!(
+        validate_with_source(&schema, &operation, source, &plan)[0].locations,
+        vec![Pos {
+            line: 3,
+            column: 25
+        }]
+    );
+    assert_eq!(
+        validate(&schema, &operation, &plan)[0].locations,
+        vec![Pos { line: 3, column: 5 }]
+    );
+}
@@ -0,0 +1,185 @@
+use std::borrow::Cow;
+
+use super::{
+    schema_rules::{SchemaValidationContext, SchemaValidationRule},
+    utils::{ValidationError, ValidationErrorExtensions},
+};
+
+use crate::ast::merge_type_extensions;
+use crate::static_graphql::schema;
+
+pub struct SchemaValidationPlan {
+    pub rules: Vec<Box<dyn SchemaValidationRule>>,
+    /// Adds `extensions.code` (the error code of the rule) to every reported error. Enabled by default.
+    pub error_code_extension: bool,
+    /// Extensions added to every reported error, same as `ValidationPlan::error_extensions`.
+    pub error_extensions: ValidationErrorExtensions,
+}
+
+impl SchemaValidationPlan {
+    pub fn new() -> Self {
+        Self::from(vec![])
+    }
+
+    pub fn from(rules: Vec<Box<dyn SchemaValidationRule>>) -> Self {
+        Self {
+            rules,
+            error_code_extension: true,
+            error_extensions: ValidationErrorExtensions::new(),
+        }
+    }
+
+    pub fn add_rule(&mut self, rule: Box<dyn SchemaValidationRule>) {
+        self.rules.push(rule);
+    }
+
+    pub fn set_error_code_extension(&mut self, enabled: bool) {
+        self.error_code_extension = enabled;
+    }
+
+    pub fn add_error_extension(&mut self, key: &str, value: serde_json::Value) {
+        self.error_extensions.insert(key.to_string(), value);
+    }
+}
+
+impl Default for SchemaValidationPlan {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Validates a type system (SDL) document, see `default_schema_rules_validation_plan`
+/// for the rules checked by default.
+///
+/// Type extensions are merged into the types they extend first (see `merge_type_extensions`),
+/// so the rules see the complete types.
+pub fn validate_schema<'a>(
+    schema: &'a schema::Document,
+    validation_plan: &'a SchemaValidationPlan,
+) -> Vec<ValidationError> {
+    let has_type_extensions = schema
+        .definitions
+        .iter()
+        .any(|definition| matches!(definition, schema::Definition::TypeExtension(_)));
+    let schema = if has_type_extensions {
+        Cow::Owned(merge_type_extensions(schema))
+    } else {
+        Cow::Borrowed(schema)
+    };
+    let mut validation_context = SchemaValidationContext::new(&schema);
+
+    validation_plan
+        .rules
+        .iter()
+        .for_each(|rule| rule.validate(&mut validation_context));
+
+    validation_context
+        .error_collector
+        .errors
+        .into_iter()
+        .map(|error| {
+            error.with_plan_extensions(
+                validation_plan.error_code_extension,
+                &validation_plan.error_extensions,
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn valid_schema_has_no_errors() {
+    use crate::validation::schema_rules::default_schema_rules_validation_plan;
+    use crate::validation::test_utils::*;
+
+    let plan = default_schema_rules_validation_plan();
+    let errors = test_schema(TEST_SCHEMA, &plan);
+
+    assert_eq!(get_messages(&errors).len(), 0);
+}
+
+#[test]
+fn broken_schema_reports_all_errors() {
+    use crate::validation::schema_rules::default_schema_rules_validation_plan;
+    use crate::validation::test_utils::*;
+
+    let plan = default_schema_rules_validation_plan();
+    let errors = test_schema(
+        "
+        interface Node {
+          id: ID!
+        }
+
+        type User implements Node {
+          name: String
+        }
+
+        union Result = User | Node
+
+        input Filter {
+          user: User
+        }",
+        &plan,
+    );
+
+    let messages = get_messages(&errors);
+    assert_eq!(
+        messages,
+        vec![
+            "Query root type must be provided.",
+            "Interface field Node.id expected but User does not provide it.",
+            "Union type Result can only include Object types, it cannot include Node.",
+            "The type of Filter.user must be Input Type but got: User."
+        ]
+    );
+}
+
+#[test]
+fn error_extensions_are_configurable() {
+    use crate::validation::schema_rules::default_schema_rules_validation_plan;
+    use crate::validation::test_utils::*;
+
+    let mut plan = default_schema_rules_validation_plan();
+    plan.set_error_code_extension(false);
+    let errors = test_schema("type Query { a: Unknown }", &plan);
+
+    assert_eq!(errors[0].extensions, None);
+
+    plan.set_error_code_extension(true);
+    plan.add_error_extension("http", serde_json::json!({ "status": 400 }));
+    let errors = test_schema("type Query { a: Unknown }", &plan);
+
+    assert_eq!(
+        serde_json::to_value(&errors[0].extensions).unwrap(),
+        serde_json::json!({ "code": "KnownTypeReferences", "http": { "status": 400 } })
+    );
+}
+
+#[test]
+fn type_extensions_are_merged_before_validation() {
+    use crate::validation::schema_rules::default_schema_rules_validation_plan;
+    use crate::validation::test_utils::*;
+
+    let plan = default_schema_rules_validation_plan();
+    let errors = test_schema(
+        "
+        interface Node {
+          id: ID!
+        }
+
+        type Query
+
+        extend type Query implements Node {
+          id: ID!
+        }
+
+        extend type Mutation {
+          id: ID!
+        }",
+        &plan,
+    );
+
+    assert_eq!(
+        get_messages(&errors),
+        vec!["Cannot extend type \"Mutation\" because it is not defined."]
+    );
+}